@@ -5,7 +5,7 @@
 //
 //! Module for alpha channel items
 use crate::private::Sealed;
-use crate::{Ch16, Ch32, Ch8, Channel};
+use crate::{Ch16, Ch16f, Ch32, Ch32h, Ch64, Ch8, Channel};
 use std::any::Any;
 use std::fmt::Debug;
 use std::marker::PhantomData;
@@ -17,11 +17,22 @@ use std::ops::Mul;
 ///
 /// This trait is *sealed*, and cannot be implemented outside of this crate.
 pub trait AChannel:
-    Any + Copy + Debug + Default + Mul<Output = Self> + PartialEq + Sealed
+    Any
+    + Copy
+    + Debug
+    + Default
+    + Mul<Output = Self>
+    + PartialEq
+    + Send
+    + Sync
+    + Sealed
 {
     /// `Channel` type
     type Chan: Channel;
 
+    /// Whether the alpha value is stored (`false` for opaque)
+    const TRANSLUCENT: bool;
+
     /// Get the alpha `Channel` value.
     ///
     /// [Channel::MIN](../trait.Channel.html#associatedconstant.MIN) is fully
@@ -44,6 +55,7 @@ pub struct Opaque<C> {
 /// [Alpha channel](trait.AChannel.html) for translucent or transparent pixels
 /// and [Raster](../struct.Raster.html)s.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(transparent)]
 pub struct Translucent<C: Channel> {
     value: C,
 }
@@ -52,12 +64,16 @@ pub struct Translucent<C: Channel> {
 ///
 /// This trait is *sealed*, and cannot be implemented outside of this crate.
 pub trait Mode:
-    Any + Copy + Clone + Debug + Default + PartialEq + Sealed
+    Any + Copy + Clone + Debug + Default + PartialEq + Send + Sync + Sealed
 {
     /// Encode one `Channel` using the alpha mode.
     fn encode<C: Channel>(c: C, a: C) -> C;
     /// Decode one `Channel` using the alpha mode.
     fn decode<C: Channel>(c: C, a: C) -> C;
+    /// Encode one unclamped `f64` component using the alpha mode.
+    fn encode_f64(c: f64, a: f64) -> f64;
+    /// Decode one unclamped `f64` component using the alpha mode.
+    fn decode_f64(c: f64, a: f64) -> f64;
 }
 
 /// Each `Channel` is "straight" (not premultiplied with alpha)
@@ -87,11 +103,26 @@ impl<C: Channel> From<Opaque<C>> for Ch16 {
         Ch16::MAX
     }
 }
+impl<C: Channel> From<Opaque<C>> for Ch16f {
+    fn from(_value: Opaque<C>) -> Self {
+        Ch16f::MAX
+    }
+}
 impl<C: Channel> From<Opaque<C>> for Ch32 {
     fn from(_value: Opaque<C>) -> Self {
         Ch32::MAX
     }
 }
+impl<C: Channel> From<Opaque<C>> for Ch32h {
+    fn from(_value: Opaque<C>) -> Self {
+        Ch32h::MAX
+    }
+}
+impl<C: Channel> From<Opaque<C>> for Ch64 {
+    fn from(_value: Opaque<C>) -> Self {
+        Ch64::MAX
+    }
+}
 
 impl<C, A> From<Translucent<A>> for Opaque<C>
 where
@@ -113,6 +144,7 @@ impl<C: Channel> Mul<Self> for Opaque<C> {
 
 impl<C: Channel> AChannel for Opaque<C> {
     type Chan = C;
+    const TRANSLUCENT: bool = false;
 
     /// Get the alpha `Channel` value.
     ///
@@ -130,8 +162,7 @@ where
     H: Channel,
 {
     fn from(value: H) -> Self {
-        let value = value.into();
-        Translucent { value }
+        Translucent::new(C::from(value))
     }
 }
 impl From<u8> for Translucent<Ch8> {
@@ -144,11 +175,26 @@ impl From<u16> for Translucent<Ch16> {
         Ch16::new(value).into()
     }
 }
+impl From<f32> for Translucent<Ch16f> {
+    fn from(value: f32) -> Self {
+        Ch16f::new(value).into()
+    }
+}
 impl From<f32> for Translucent<Ch32> {
     fn from(value: f32) -> Self {
         Ch32::new(value).into()
     }
 }
+impl From<f32> for Translucent<Ch32h> {
+    fn from(value: f32) -> Self {
+        Ch32h::new(value).into()
+    }
+}
+impl From<f64> for Translucent<Ch64> {
+    fn from(value: f64) -> Self {
+        Ch64::new(value).into()
+    }
+}
 
 impl<C: Channel> Mul<Self> for Translucent<C> {
     type Output = Self;
@@ -160,7 +206,12 @@ impl<C: Channel> Mul<Self> for Translucent<C> {
 
 impl<C: Channel> Translucent<C> {
     /// Create a new `Translucent` alpha value.
+    ///
+    /// The value is clamped between
+    /// [Channel::MIN](../trait.Channel.html#associatedconstant.MIN) and
+    /// [Channel::MAX](../trait.Channel.html#associatedconstant.MAX).
     pub fn new(value: C) -> Self {
+        let value = value.max(C::MIN).min(C::MAX);
         Translucent { value }
     }
 }
@@ -178,6 +229,7 @@ where
 
 impl<C: Channel> AChannel for Translucent<C> {
     type Chan = C;
+    const TRANSLUCENT: bool = true;
 
     /// Get the alpha `Channel` value.
     ///
@@ -199,6 +251,14 @@ impl Mode for Straight {
     fn decode<C: Channel>(c: C, _a: C) -> C {
         c
     }
+    /// Encode one unclamped `f64` component using the alpha mode.
+    fn encode_f64(c: f64, _a: f64) -> f64 {
+        c
+    }
+    /// Decode one unclamped `f64` component using the alpha mode.
+    fn decode_f64(c: f64, _a: f64) -> f64 {
+        c
+    }
 }
 
 impl Mode for Premultiplied {
@@ -210,4 +270,16 @@ impl Mode for Premultiplied {
     fn decode<C: Channel>(c: C, a: C) -> C {
         c / a
     }
+    /// Encode one unclamped `f64` component using the alpha mode.
+    fn encode_f64(c: f64, a: f64) -> f64 {
+        c * a
+    }
+    /// Decode one unclamped `f64` component using the alpha mode.
+    fn decode_f64(c: f64, a: f64) -> f64 {
+        if a > 0.0 {
+            c / a
+        } else {
+            0.0
+        }
+    }
 }
@@ -0,0 +1,343 @@
+// argb.rs      ARGB color model.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{
+    self, AChannel, Mode as _, Opaque, Premultiplied, Straight, Translucent,
+};
+use crate::format::Model;
+use crate::gamma::{self, Linear, Srgb};
+use crate::gamut;
+use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel, Rgb};
+use std::marker::PhantomData;
+
+/// RGB additive [color model] with *alpha* first.
+///
+/// The *[alpha]* component is stored first, followed by *red*, *green* and
+/// *blue*, as used by Java, Flash and many capture APIs.  Buffers in this
+/// order can be loaded without copying with [with_u8_buffer].  Converting
+/// to or from [Rgb] with the same channel, alpha mode and gamma mode is a
+/// swizzle.
+///
+/// [alpha]: alpha/trait.AChannel.html
+/// [color model]: trait.ColorModel.html
+/// [Rgb]: struct.Rgb.html
+/// [with_u8_buffer]: struct.RasterBuilder.html#method.with_u8_buffer
+///
+/// ```
+/// # use pix::*;
+/// let r = RasterBuilder::<SArgb8>::new()
+///     .with_u8_buffer(1, 1, vec![0xFF, 0x10, 0x20, 0x30]);
+/// assert_eq!(r.pixel(0, 0).red(), Ch8::new(0x10));
+/// let bgra = RasterBuilder::<SBgra8>::new().with_raster(&r);
+/// assert_eq!(bgra.as_u8_slice(), &[0x30, 0x20, 0x10, 0xFF]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Argb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    alpha: A,
+    components: [C; 3],
+    mode: PhantomData<M>,
+    gamma: PhantomData<G>,
+}
+
+impl<C, A, M, G> ColorModel for Argb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Chan = C;
+    const MODEL: Model = Model::Argb;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
+
+    /// Get all non-alpha components
+    fn components(&self) -> &[Self::Chan] {
+        &self.components
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.alpha.value()
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        [self.red(), self.green(), self.blue(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        Argb::with_alpha(rgba[0], rgba[1], rgba[2], rgba[3])
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let r = if self.red() > rhs.red() {
+            self.red() - rhs.red()
+        } else {
+            rhs.red() - self.red()
+        };
+        let g = if self.green() > rhs.green() {
+            self.green() - rhs.green()
+        } else {
+            rhs.green() - self.green()
+        };
+        let b = if self.blue() > rhs.blue() {
+            self.blue() - rhs.blue()
+        } else {
+            rhs.blue() - self.blue()
+        };
+        let a = if self.alpha() > rhs.alpha() {
+            self.alpha() - rhs.alpha()
+        } else {
+            rhs.alpha() - self.alpha()
+        };
+        Argb::with_alpha(r, g, b, a)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        self.red() <= rhs.red()
+            && self.green() <= rhs.green()
+            && self.blue() <= rhs.blue()
+            && self.alpha() <= rhs.alpha()
+    }
+}
+
+impl<C, A, M, G> Pixel for Argb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Alpha = M;
+    type Gamma = G;
+    type Primaries = gamut::Srgb;
+}
+
+impl<C, A, M, G> Iterator for Argb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<C, M, G> From<Argb<C, Translucent<C>, M, G>> for Argb<C, Opaque<C>, M, G>
+where
+    C: Channel,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn from(c: Argb<C, Translucent<C>, M, G>) -> Self {
+        Argb::new(c.red(), c.green(), c.blue())
+    }
+}
+
+impl<C, M, G> From<Argb<C, Opaque<C>, M, G>> for Argb<C, Translucent<C>, M, G>
+where
+    C: Channel,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn from(c: Argb<C, Opaque<C>, M, G>) -> Self {
+        Argb::with_alpha(c.red(), c.green(), c.blue(), C::MAX)
+    }
+}
+
+impl<C, A, G> From<Argb<C, A, Straight, G>> for Argb<C, A, Premultiplied, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    G: gamma::Mode,
+{
+    fn from(c: Argb<C, A, Straight, G>) -> Self {
+        let red = Premultiplied::encode(c.red(), c.alpha());
+        let green = Premultiplied::encode(c.green(), c.alpha());
+        let blue = Premultiplied::encode(c.blue(), c.alpha());
+        Argb::with_alpha(red, green, blue, c.alpha())
+    }
+}
+
+impl<C, A, G> From<Argb<C, A, Premultiplied, G>> for Argb<C, A, Straight, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    G: gamma::Mode,
+{
+    fn from(c: Argb<C, A, Premultiplied, G>) -> Self {
+        let red = Premultiplied::decode(c.red(), c.alpha());
+        let green = Premultiplied::decode(c.green(), c.alpha());
+        let blue = Premultiplied::decode(c.blue(), c.alpha());
+        Argb::with_alpha(red, green, blue, c.alpha)
+    }
+}
+
+impl<C, A, M, G> From<Rgb<C, A, M, G>> for Argb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    /// Swizzle an `Rgb` into *ARGB* order
+    fn from(c: Rgb<C, A, M, G>) -> Self {
+        Argb::with_alpha(c.red(), c.green(), c.blue(), c.alpha())
+    }
+}
+
+impl<C, A, M, G> From<Argb<C, A, M, G>> for Rgb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    /// Swizzle into an `Rgb`
+    fn from(c: Argb<C, A, M, G>) -> Self {
+        Rgb::with_alpha(c.red(), c.green(), c.blue(), c.alpha())
+    }
+}
+
+impl<C, A, M, G> Argb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying *red*,
+    /// *green* and *blue* values.
+    pub fn new<H>(red: H, green: H, blue: H) -> Self
+    where
+        C: From<H>,
+        A: From<Opaque<C>>,
+    {
+        Self::with_alpha(red, green, blue, Opaque::default())
+    }
+    /// Create a [Translucent](alpha/struct.Translucent.html) color by
+    /// specifying *red*, *green*, *blue* and *alpha* values.
+    pub fn with_alpha<H, B>(red: H, green: H, blue: H, alpha: B) -> Self
+    where
+        C: From<H>,
+        A: From<B>,
+    {
+        let red = C::from(red);
+        let green = C::from(green);
+        let blue = C::from(blue);
+        let components = [red, green, blue];
+        let alpha = A::from(alpha);
+        Argb {
+            alpha,
+            components,
+            mode: PhantomData,
+            gamma: PhantomData,
+        }
+    }
+    /// Get the red component.
+    pub fn red(self) -> C {
+        self.components[0]
+    }
+    /// Get the green component.
+    pub fn green(self) -> C {
+        self.components[1]
+    }
+    /// Get the blue component.
+    pub fn blue(self) -> C {
+        self.components[2]
+    }
+}
+
+/// [Argb](struct.Argb.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Argb8 = Argb<Ch8, Translucent<Ch8>, Straight, Linear>;
+/// [Argb](struct.Argb.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Argb16 = Argb<Ch16, Translucent<Ch16>, Straight, Linear>;
+/// [Argb](struct.Argb.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Argb32 = Argb<Ch32, Translucent<Ch32>, Straight, Linear>;
+
+/// [Argb](struct.Argb.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Argb8p = Argb<Ch8, Translucent<Ch8>, Premultiplied, Linear>;
+/// [Argb](struct.Argb.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Argb16p = Argb<Ch16, Translucent<Ch16>, Premultiplied, Linear>;
+/// [Argb](struct.Argb.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Argb32p = Argb<Ch32, Translucent<Ch32>, Premultiplied, Linear>;
+
+/// [Argb](struct.Argb.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SArgb8 = Argb<Ch8, Translucent<Ch8>, Straight, Srgb>;
+/// [Argb](struct.Argb.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SArgb16 = Argb<Ch16, Translucent<Ch16>, Straight, Srgb>;
+/// [Argb](struct.Argb.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SArgb32 = Argb<Ch32, Translucent<Ch32>, Straight, Srgb>;
+
+/// [Argb](struct.Argb.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SArgb8p = Argb<Ch8, Translucent<Ch8>, Premultiplied, Srgb>;
+/// [Argb](struct.Argb.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SArgb16p = Argb<Ch16, Translucent<Ch16>, Premultiplied, Srgb>;
+/// [Argb](struct.Argb.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SArgb32p = Argb<Ch32, Translucent<Ch32>, Premultiplied, Srgb>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<SArgb8>(), 4);
+        assert_eq!(std::mem::size_of::<SArgb16p>(), 8);
+        assert_eq!(std::mem::size_of::<SArgb32>(), 16);
+    }
+
+    #[test]
+    fn swizzle() {
+        let b = vec![0x80, 0x10, 0x20, 0x30, 0x00, 0x40, 0x50, 0x60];
+        let r = RasterBuilder::<SArgb8p>::new().with_u8_buffer(2, 1, b);
+        let r = RasterBuilder::<SRgba8p>::new().with_raster(&r);
+        let v = [0x10, 0x20, 0x30, 0x80, 0x40, 0x50, 0x60, 0x00];
+        assert_eq!(r.as_u8_slice(), &v[..]);
+        let rgb = SRgba8::with_alpha(0x12, 0x34, 0x56, 0x78);
+        let argb: SArgb8 = rgb.into();
+        assert_eq!(argb, rgb.convert());
+        assert_eq!(argb.alpha(), Ch8::new(0x78));
+    }
+
+    #[test]
+    fn round_trip() {
+        let rgb = SRgb16::new(0x1234, 0x5678, 0x9ABC);
+        let argb: SArgb32 = rgb.convert();
+        let back: SRgb16 = argb.convert();
+        assert_eq!(rgb, back);
+    }
+}
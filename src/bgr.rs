@@ -0,0 +1,364 @@
+// bgr.rs       BGR color model.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{
+    self, AChannel, Mode as _, Opaque, Premultiplied, Straight, Translucent,
+};
+use crate::format::Model;
+use crate::gamma::{self, Linear, Srgb};
+use crate::gamut;
+use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel, Rgb};
+use std::marker::PhantomData;
+
+/// RGB additive [color model] in *blue*, *green*, *red* order.
+///
+/// The components are stored as *blue*, *green* and *red*, followed by an
+/// optional *[alpha]*, as used by Windows bitmaps, Cairo and Skia on
+/// little-endian platforms.  Buffers in this order can be loaded without
+/// copying with [with_u8_buffer].  Converting to or from [Rgb] with the
+/// same channel, alpha mode and gamma mode is a swizzle.
+///
+/// [alpha]: alpha/trait.AChannel.html
+/// [color model]: trait.ColorModel.html
+/// [Rgb]: struct.Rgb.html
+/// [with_u8_buffer]: struct.RasterBuilder.html#method.with_u8_buffer
+///
+/// ```
+/// # use pix::*;
+/// let r = RasterBuilder::<SBgra8>::new()
+///     .with_u8_buffer(1, 1, vec![0x10, 0x20, 0x30, 0xFF]);
+/// assert_eq!(r.pixel(0, 0).red(), Ch8::new(0x30));
+/// let rgba = RasterBuilder::<SRgba8>::new().with_raster(&r);
+/// assert_eq!(rgba.as_u8_slice(), &[0x30, 0x20, 0x10, 0xFF]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Bgr<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    components: [C; 3],
+    alpha: A,
+    mode: PhantomData<M>,
+    gamma: PhantomData<G>,
+}
+
+impl<C, A, M, G> ColorModel for Bgr<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Chan = C;
+    const MODEL: Model = Model::Bgr;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
+
+    /// Get all non-alpha components
+    fn components(&self) -> &[Self::Chan] {
+        &self.components
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.alpha.value()
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        [self.red(), self.green(), self.blue(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        Bgr::with_alpha(rgba[0], rgba[1], rgba[2], rgba[3])
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let r = if self.red() > rhs.red() {
+            self.red() - rhs.red()
+        } else {
+            rhs.red() - self.red()
+        };
+        let g = if self.green() > rhs.green() {
+            self.green() - rhs.green()
+        } else {
+            rhs.green() - self.green()
+        };
+        let b = if self.blue() > rhs.blue() {
+            self.blue() - rhs.blue()
+        } else {
+            rhs.blue() - self.blue()
+        };
+        let a = if self.alpha() > rhs.alpha() {
+            self.alpha() - rhs.alpha()
+        } else {
+            rhs.alpha() - self.alpha()
+        };
+        Bgr::with_alpha(r, g, b, a)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        self.red() <= rhs.red()
+            && self.green() <= rhs.green()
+            && self.blue() <= rhs.blue()
+            && self.alpha() <= rhs.alpha()
+    }
+}
+
+impl<C, A, M, G> Pixel for Bgr<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Alpha = M;
+    type Gamma = G;
+    type Primaries = gamut::Srgb;
+}
+
+impl<C, A, M, G> Iterator for Bgr<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<C, M, G> From<Bgr<C, Translucent<C>, M, G>> for Bgr<C, Opaque<C>, M, G>
+where
+    C: Channel,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn from(c: Bgr<C, Translucent<C>, M, G>) -> Self {
+        Bgr::new(c.red(), c.green(), c.blue())
+    }
+}
+
+impl<C, M, G> From<Bgr<C, Opaque<C>, M, G>> for Bgr<C, Translucent<C>, M, G>
+where
+    C: Channel,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn from(c: Bgr<C, Opaque<C>, M, G>) -> Self {
+        Bgr::with_alpha(c.red(), c.green(), c.blue(), C::MAX)
+    }
+}
+
+impl<C, A, G> From<Bgr<C, A, Straight, G>> for Bgr<C, A, Premultiplied, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    G: gamma::Mode,
+{
+    fn from(c: Bgr<C, A, Straight, G>) -> Self {
+        let red = Premultiplied::encode(c.red(), c.alpha());
+        let green = Premultiplied::encode(c.green(), c.alpha());
+        let blue = Premultiplied::encode(c.blue(), c.alpha());
+        Bgr::with_alpha(red, green, blue, c.alpha())
+    }
+}
+
+impl<C, A, G> From<Bgr<C, A, Premultiplied, G>> for Bgr<C, A, Straight, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    G: gamma::Mode,
+{
+    fn from(c: Bgr<C, A, Premultiplied, G>) -> Self {
+        let red = Premultiplied::decode(c.red(), c.alpha());
+        let green = Premultiplied::decode(c.green(), c.alpha());
+        let blue = Premultiplied::decode(c.blue(), c.alpha());
+        Bgr::with_alpha(red, green, blue, c.alpha)
+    }
+}
+
+impl<C, A, M, G> From<Rgb<C, A, M, G>> for Bgr<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    /// Swizzle an `Rgb` into *BGR* order
+    fn from(c: Rgb<C, A, M, G>) -> Self {
+        Bgr::with_alpha(c.red(), c.green(), c.blue(), c.alpha())
+    }
+}
+
+impl<C, A, M, G> From<Bgr<C, A, M, G>> for Rgb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    /// Swizzle into an `Rgb`
+    fn from(c: Bgr<C, A, M, G>) -> Self {
+        Rgb::with_alpha(c.red(), c.green(), c.blue(), c.alpha())
+    }
+}
+
+impl<C, A, M, G> Bgr<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying *red*,
+    /// *green* and *blue* values.
+    pub fn new<H>(red: H, green: H, blue: H) -> Self
+    where
+        C: From<H>,
+        A: From<Opaque<C>>,
+    {
+        Self::with_alpha(red, green, blue, Opaque::default())
+    }
+    /// Create a [Translucent](alpha/struct.Translucent.html) color by
+    /// specifying *red*, *green*, *blue* and *alpha* values.
+    pub fn with_alpha<H, B>(red: H, green: H, blue: H, alpha: B) -> Self
+    where
+        C: From<H>,
+        A: From<B>,
+    {
+        let red = C::from(red);
+        let green = C::from(green);
+        let blue = C::from(blue);
+        let components = [blue, green, red];
+        let alpha = A::from(alpha);
+        Bgr {
+            components,
+            alpha,
+            mode: PhantomData,
+            gamma: PhantomData,
+        }
+    }
+    /// Get the red component.
+    pub fn red(self) -> C {
+        self.components[2]
+    }
+    /// Get the green component.
+    pub fn green(self) -> C {
+        self.components[1]
+    }
+    /// Get the blue component.
+    pub fn blue(self) -> C {
+        self.components[0]
+    }
+}
+
+/// [Bgr](struct.Bgr.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Bgr8 = Bgr<Ch8, Opaque<Ch8>, Straight, Linear>;
+/// [Bgr](struct.Bgr.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Bgr16 = Bgr<Ch16, Opaque<Ch16>, Straight, Linear>;
+/// [Bgr](struct.Bgr.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Bgr32 = Bgr<Ch32, Opaque<Ch32>, Straight, Linear>;
+
+/// [Bgr](struct.Bgr.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Bgra8 = Bgr<Ch8, Translucent<Ch8>, Straight, Linear>;
+/// [Bgr](struct.Bgr.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Bgra16 = Bgr<Ch16, Translucent<Ch16>, Straight, Linear>;
+/// [Bgr](struct.Bgr.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Bgra32 = Bgr<Ch32, Translucent<Ch32>, Straight, Linear>;
+
+/// [Bgr](struct.Bgr.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Bgra8p = Bgr<Ch8, Translucent<Ch8>, Premultiplied, Linear>;
+/// [Bgr](struct.Bgr.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Bgra16p = Bgr<Ch16, Translucent<Ch16>, Premultiplied, Linear>;
+/// [Bgr](struct.Bgr.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Bgra32p = Bgr<Ch32, Translucent<Ch32>, Premultiplied, Linear>;
+
+/// [Bgr](struct.Bgr.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SBgr8 = Bgr<Ch8, Opaque<Ch8>, Straight, Srgb>;
+/// [Bgr](struct.Bgr.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SBgr16 = Bgr<Ch16, Opaque<Ch16>, Straight, Srgb>;
+/// [Bgr](struct.Bgr.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SBgr32 = Bgr<Ch32, Opaque<Ch32>, Straight, Srgb>;
+
+/// [Bgr](struct.Bgr.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SBgra8 = Bgr<Ch8, Translucent<Ch8>, Straight, Srgb>;
+/// [Bgr](struct.Bgr.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SBgra16 = Bgr<Ch16, Translucent<Ch16>, Straight, Srgb>;
+/// [Bgr](struct.Bgr.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SBgra32 = Bgr<Ch32, Translucent<Ch32>, Straight, Srgb>;
+
+/// [Bgr](struct.Bgr.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SBgra8p = Bgr<Ch8, Translucent<Ch8>, Premultiplied, Srgb>;
+/// [Bgr](struct.Bgr.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SBgra16p = Bgr<Ch16, Translucent<Ch16>, Premultiplied, Srgb>;
+/// [Bgr](struct.Bgr.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SBgra32p = Bgr<Ch32, Translucent<Ch32>, Premultiplied, Srgb>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<SBgr8>(), 3);
+        assert_eq!(std::mem::size_of::<SBgra8>(), 4);
+        assert_eq!(std::mem::size_of::<SBgra16p>(), 8);
+        assert_eq!(std::mem::size_of::<SBgra32>(), 16);
+    }
+
+    #[test]
+    fn swizzle() {
+        let p = SBgra8p::with_alpha(0x10, 0x20, 0x30, 0x00);
+        let r = RasterBuilder::<SBgra8p>::new().with_color(1, 1, p);
+        assert_eq!(r.as_u8_slice(), &[0x30, 0x20, 0x10, 0x00]);
+        let r = RasterBuilder::<SRgba8p>::new().with_raster(&r);
+        assert_eq!(r.as_u8_slice(), &[0x10, 0x20, 0x30, 0x00]);
+        let rgb = SRgb16::new(0x1234, 0x5678, 0x9ABC);
+        let bgr: SBgr16 = rgb.into();
+        assert_eq!(bgr, rgb.convert());
+        assert_eq!(rgb, SRgb16::from(bgr));
+    }
+
+    #[test]
+    fn round_trip() {
+        let rgb = SRgb16::new(0x1234, 0x5678, 0x9ABC);
+        let bgr: SBgra32 = rgb.convert();
+        let back: SRgb16 = bgr.convert();
+        assert_eq!(rgb, back);
+    }
+}
@@ -11,13 +11,17 @@ use std::ops::{Add, Div, Mul, Sub};
 
 /// A [color model] *component* which determines *bit depth*.
 ///
-/// Existing `Channel`s are [Ch8], [Ch16] and [Ch32].
+/// Existing `Channel`s are [Ch8], [Ch16], [Ch16f], [Ch32], [Ch32h] and
+/// [Ch64].
 ///
 /// This trait is *sealed*, and cannot be implemented outside of this crate.
 ///
 /// [Ch8]: struct.Ch8.html
 /// [Ch16]: struct.Ch16.html
+/// [Ch16f]: struct.Ch16f.html
 /// [Ch32]: struct.Ch32.html
+/// [Ch32h]: struct.Ch32h.html
+/// [Ch64]: struct.Ch64.html
 /// [color model]: trait.ColorModel.html
 pub trait Channel:
     Any
@@ -25,11 +29,16 @@ pub trait Channel:
     + Debug
     + Default
     + From<f32>
+    + Into<f32>
+    + From<f64>
+    + Into<f64>
     + Ord
     + Add<Output = Self>
     + Div<Output = Self>
     + Mul<Output = Self>
     + Sub<Output = Self>
+    + Send
+    + Sync
     + SrgbValue
 {
     /// Minimum intensity (*zero*)
@@ -59,6 +68,7 @@ pub trait Channel:
 /// assert_eq!(c, Ch32::MAX);
 /// ```
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
 pub struct Ch8(u8);
 
 /// 16-bit color [Channel](trait.Channel.html).
@@ -78,6 +88,7 @@ pub struct Ch8(u8);
 /// assert_eq!(c, Ch32::MAX);
 /// ```
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
 pub struct Ch16(u16);
 
 /// 32-bit color [Channel](trait.Channel.html).
@@ -96,9 +107,94 @@ pub struct Ch16(u16);
 /// let c: Ch16 = c.into();
 /// assert_eq!(c, Ch16::MAX);
 /// ```
-#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
 pub struct Ch32(f32);
 
+/// 16-bit floating-point high dynamic range color
+/// [Channel](trait.Channel.html).
+///
+/// The `Channel` is represented by an IEEE 754 *binary16* (half precision)
+/// value, which is not clamped.  Like [Ch32h], values above 1 and below 0
+/// are kept, so HDR images can be stored as `RGBA16F` without widening to 32
+/// bits.  Arithmetic does not saturate, and values too large for *binary16*
+/// become infinite.
+///
+/// [MAX] is nominal white.  As an [alpha] value, `MAX` is fully opaque, and
+/// alpha is always clamped between [MIN] and [MAX].  Converting to an
+/// integer `Channel`, [Ch32] or [Ch64] clamps between 0 and 1.
+///
+/// [alpha]: alpha/struct.Translucent.html
+/// [Ch32]: struct.Ch32.html
+/// [Ch32h]: struct.Ch32h.html
+/// [Ch64]: struct.Ch64.html
+/// [MAX]: trait.Channel.html#associatedconstant.MAX
+/// [MIN]: trait.Channel.html#associatedconstant.MIN
+///
+/// ```
+/// # use pix::*;
+/// let c: Ch16f = 0.5.into();
+/// assert_eq!(c.to_bits(), 0x3800);
+/// let c: Ch8 = c.into();
+/// assert_eq!(c, Ch8::new(128));
+/// let c: Ch16f = 4.0.into();
+/// assert_eq!(f32::from(c * 0.5), 2.0);
+/// let c: Ch32 = c.into();
+/// assert_eq!(c, Ch32::MAX);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
+pub struct Ch16f(u16);
+
+/// 32-bit floating-point high dynamic range color
+/// [Channel](trait.Channel.html).
+///
+/// The `Channel` is represented by an `f32`, which is not clamped.  Values
+/// above 1 (brighter than nominal white) and below 0 are kept, for
+/// scene-referred linear light.  Arithmetic does not saturate.
+///
+/// [MAX] is nominal white.  As an [alpha] value, `MAX` is fully opaque, and
+/// alpha is always clamped between [MIN] and [MAX].  Converting to any other
+/// `Channel` except [Ch16f] clamps between 0 and 1.
+///
+/// [alpha]: alpha/struct.Translucent.html
+/// [Ch16f]: struct.Ch16f.html
+/// [MAX]: trait.Channel.html#associatedconstant.MAX
+/// [MIN]: trait.Channel.html#associatedconstant.MIN
+///
+/// ```
+/// # use pix::*;
+/// let c: Ch32h = 4.0.into();
+/// assert_eq!(f32::from(c * 0.5), 2.0);
+/// let c: Ch8 = c.into();
+/// assert_eq!(c, Ch8::MAX);
+/// let c: Ch32h = Ch32::new(0.25).into();
+/// assert_eq!(f32::from(c - 0.5), -0.25);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
+pub struct Ch32h(f32);
+
+/// 64-bit color [Channel](trait.Channel.html).
+///
+/// The `Channel` is represented by an `f64`, but values are guaranteed to be
+/// between 0 and 1, inclusive.
+///
+/// ```
+/// # use pix::*;
+/// let c: Ch64 = 0.0.into();
+/// assert_eq!(c, Ch64::MIN);
+/// let c: Ch8 = c.into();
+/// assert_eq!(c, Ch8::MIN);
+/// let c: Ch64 = 1.0.into();
+/// assert_eq!(c, Ch64::MAX);
+/// let c: Ch16 = c.into();
+/// assert_eq!(c, Ch16::MAX);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
+pub struct Ch64(f64);
+
 impl Ch8 {
     /// Create a new 8-bit `Channel` value.
     pub fn new(value: u8) -> Self {
@@ -138,6 +234,24 @@ impl From<Ch8> for u8 {
     }
 }
 
+impl From<Ch8> for f32 {
+    fn from(c: Ch8) -> f32 {
+        Ch32::from(c).into()
+    }
+}
+
+impl From<f64> for Ch8 {
+    fn from(value: f64) -> Self {
+        Ch64::new(value).into()
+    }
+}
+
+impl From<Ch8> for f64 {
+    fn from(c: Ch8) -> f64 {
+        f64::from(c.0) / 255.0
+    }
+}
+
 impl<R> Add<R> for Ch8
 where
     Self: From<R>,
@@ -241,6 +355,24 @@ impl From<Ch16> for u16 {
     }
 }
 
+impl From<Ch16> for f32 {
+    fn from(c: Ch16) -> f32 {
+        Ch32::from(c).into()
+    }
+}
+
+impl From<f64> for Ch16 {
+    fn from(value: f64) -> Self {
+        Ch64::new(value).into()
+    }
+}
+
+impl From<Ch16> for f64 {
+    fn from(c: Ch16) -> f64 {
+        f64::from(c.0) / 65535.0
+    }
+}
+
 impl From<Ch16> for Ch8 {
     fn from(c: Ch16) -> Self {
         Ch8::new((c.0 >> 8) as u8)
@@ -354,10 +486,22 @@ impl From<Ch32> for f32 {
     }
 }
 
+impl From<f64> for Ch32 {
+    fn from(value: f64) -> Self {
+        Ch32::new(value as f32)
+    }
+}
+
+impl From<Ch32> for f64 {
+    fn from(c: Ch32) -> f64 {
+        f64::from(c.0)
+    }
+}
+
 impl From<Ch32> for Ch8 {
     fn from(c: Ch32) -> Self {
         let value = c.0;
-        debug_assert!(value >= 0.0 && value <= 1.0);
+        debug_assert!((0.0..=1.0).contains(&value));
         // this cast is not UB since the value is guaranteed
         // to be between 0.0 and 1.0 (see bug #10184)
         Ch8::new((value * 255.0).round() as u8)
@@ -367,7 +511,7 @@ impl From<Ch32> for Ch8 {
 impl From<Ch32> for Ch16 {
     fn from(c: Ch32) -> Self {
         let value = c.0;
-        debug_assert!(value >= 0.0 && value <= 1.0);
+        debug_assert!((0.0..=1.0).contains(&value));
         // this cast is not UB since the value is guaranteed
         // to be between 0.0 and 1.0 (see bug #10184)
         Ch16::new((value * 65535.0).round() as u16)
@@ -380,11 +524,25 @@ impl From<Ch16> for Ch32 {
     }
 }
 
+impl PartialEq for Ch32 {
+    fn eq(&self, other: &Ch32) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
 impl Eq for Ch32 {}
 
+impl PartialOrd for Ch32 {
+    fn partial_cmp(&self, other: &Ch32) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Ord for Ch32 {
     fn cmp(&self, other: &Ch32) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        // NaN can only come from raw bytes; order it totally
+        let (a, b) = (self.0, other.0);
+        a.partial_cmp(&b).unwrap_or_else(|| a.total_cmp(&b))
     }
 }
 
@@ -435,6 +593,591 @@ where
     }
 }
 
+/// Convert an `f32` to IEEE 754 *binary16* bits, rounding to nearest even.
+fn f32_to_f16_bits(value: f32) -> u16 {
+    let x = value.to_bits();
+    let sign = ((x >> 16) & 0x8000) as u16;
+    let exp = ((x >> 23) & 0xFF) as i32;
+    let man = x & 0x7F_FFFF;
+    if exp == 0xFF {
+        // infinity or NaN
+        let nan = if man != 0 { 0x0200 } else { 0 };
+        return sign | 0x7C00 | nan;
+    }
+    let exp = exp - 127 + 15;
+    if exp >= 0x1F {
+        // overflow to infinity
+        return sign | 0x7C00;
+    }
+    if exp <= 0 {
+        if exp < -10 {
+            // underflow to zero
+            return sign;
+        }
+        // subnormal
+        let man = man | 0x80_0000;
+        let shift = (14 - exp) as u32;
+        let half = 1 << (shift - 1);
+        let rem = man & ((1 << shift) - 1);
+        let mut h = man >> shift;
+        if rem > half || (rem == half && (h & 1) != 0) {
+            h += 1;
+        }
+        return sign | h as u16;
+    }
+    let mut h = ((exp as u32) << 10) | (man >> 13);
+    let rem = man & 0x1FFF;
+    // rounding may carry into the exponent, which is still correct
+    if rem > 0x1000 || (rem == 0x1000 && (h & 1) != 0) {
+        h += 1;
+    }
+    sign | h as u16
+}
+
+/// Convert IEEE 754 *binary16* bits to an `f32`.
+fn f16_bits_to_f32(bits: u16) -> f32 {
+    let sign = u32::from(bits & 0x8000) << 16;
+    let exp = u32::from((bits >> 10) & 0x1F);
+    let man = u32::from(bits & 0x03FF);
+    let x = match exp {
+        0 => {
+            // zero or subnormal
+            let value = man as f32 / 16_777_216.0;
+            return if sign != 0 { -value } else { value };
+        }
+        0x1F => sign | 0x7F80_0000 | (man << 13),
+        _ => sign | ((exp + 112) << 23) | (man << 13),
+    };
+    f32::from_bits(x)
+}
+
+impl Ch16f {
+    /// Create a new 16-bit floating-point `Channel` value.
+    ///
+    /// Returns [MIN](trait.Channel.html#associatedconstant.MIN) if value is
+    ///         `NaN`.
+    pub fn new(value: f32) -> Self {
+        Ch16f::from_bits(f32_to_f16_bits(value))
+    }
+    /// Create a `Channel` from raw *binary16* bits.
+    ///
+    /// Returns [MIN](trait.Channel.html#associatedconstant.MIN) if the bits
+    /// are `NaN`.  Negative zero is stored as positive zero.
+    pub fn from_bits(bits: u16) -> Self {
+        if bits == 0x8000 || bits & 0x7FFF > 0x7C00 {
+            Ch16f::MIN
+        } else {
+            Ch16f(bits)
+        }
+    }
+    /// Get the raw *binary16* bits.
+    pub fn to_bits(self) -> u16 {
+        self.0
+    }
+}
+
+impl Channel for Ch16f {
+    /// Minimum intensity (*zero*)
+    const MIN: Ch16f = Ch16f(0x0000);
+
+    /// Maximum intensity (*one*)
+    const MAX: Ch16f = Ch16f(0x3C00);
+
+    /// Raise to given power
+    ///
+    /// Negative values are mirrored, keeping their sign.
+    fn powf(self, g: f32) -> Self {
+        let v = f32::from(self);
+        Ch16f::new(v.abs().powf(g).copysign(v))
+    }
+}
+
+impl From<f32> for Ch16f {
+    fn from(value: f32) -> Self {
+        Ch16f::new(value)
+    }
+}
+
+impl From<Ch16f> for f32 {
+    fn from(c: Ch16f) -> f32 {
+        f16_bits_to_f32(c.0)
+    }
+}
+
+impl From<f64> for Ch16f {
+    fn from(value: f64) -> Self {
+        Ch16f::new(value as f32)
+    }
+}
+
+impl From<Ch16f> for f64 {
+    fn from(c: Ch16f) -> f64 {
+        f64::from(f16_bits_to_f32(c.0))
+    }
+}
+
+impl From<Ch8> for Ch16f {
+    fn from(c: Ch8) -> Self {
+        Ch16f::new(c.into())
+    }
+}
+
+impl From<Ch16> for Ch16f {
+    fn from(c: Ch16) -> Self {
+        Ch16f::new(c.into())
+    }
+}
+
+impl From<Ch32> for Ch16f {
+    fn from(c: Ch32) -> Self {
+        Ch16f::new(c.0)
+    }
+}
+
+impl From<Ch16f> for Ch8 {
+    fn from(c: Ch16f) -> Self {
+        Ch32::new(f32::from(c)).into()
+    }
+}
+
+impl From<Ch16f> for Ch16 {
+    fn from(c: Ch16f) -> Self {
+        Ch32::new(f32::from(c)).into()
+    }
+}
+
+impl From<Ch16f> for Ch32 {
+    fn from(c: Ch16f) -> Self {
+        Ch32::new(f32::from(c))
+    }
+}
+
+impl PartialEq for Ch16f {
+    fn eq(&self, other: &Ch16f) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ch16f {}
+
+impl PartialOrd for Ch16f {
+    fn partial_cmp(&self, other: &Ch16f) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ch16f {
+    fn cmp(&self, other: &Ch16f) -> Ordering {
+        // NaN bits can only come from raw bytes; order them totally
+        let a = f16_bits_to_f32(self.0);
+        let b = f16_bits_to_f32(other.0);
+        a.partial_cmp(&b).unwrap_or_else(|| a.total_cmp(&b))
+    }
+}
+
+impl<R> Add<R> for Ch16f
+where
+    f32: From<R>,
+{
+    type Output = Self;
+    fn add(self, rhs: R) -> Self {
+        Ch16f::new(f16_bits_to_f32(self.0) + f32::from(rhs))
+    }
+}
+
+impl<R> Sub<R> for Ch16f
+where
+    f32: From<R>,
+{
+    type Output = Self;
+    fn sub(self, rhs: R) -> Self {
+        Ch16f::new(f16_bits_to_f32(self.0) - f32::from(rhs))
+    }
+}
+
+impl<R> Mul<R> for Ch16f
+where
+    f32: From<R>,
+{
+    type Output = Self;
+    fn mul(self, rhs: R) -> Self {
+        Ch16f::new(f16_bits_to_f32(self.0) * f32::from(rhs))
+    }
+}
+
+impl<R> Div<R> for Ch16f
+where
+    f32: From<R>,
+{
+    type Output = Self;
+    fn div(self, rhs: R) -> Self {
+        let v = f32::from(rhs);
+        if v != 0.0 {
+            Ch16f::new(f16_bits_to_f32(self.0) / v)
+        } else {
+            Ch16f::MIN
+        }
+    }
+}
+
+impl Ch32h {
+    /// Create a new 32-bit high dynamic range `Channel` value.
+    ///
+    /// Returns [MIN](trait.Channel.html#associatedconstant.MIN) if value is
+    ///         `NaN`.
+    pub fn new(value: f32) -> Self {
+        let v = if value.is_nan() { 0.0 } else { value };
+        Ch32h(v)
+    }
+}
+
+impl Channel for Ch32h {
+    /// Minimum intensity (*zero*)
+    const MIN: Ch32h = Ch32h(0.0);
+
+    /// Maximum intensity (*one*, nominal white)
+    const MAX: Ch32h = Ch32h(1.0);
+
+    /// Raise to given power
+    ///
+    /// Negative values are mirrored, keeping their sign.
+    fn powf(self, g: f32) -> Self {
+        Ch32h::new(self.0.abs().powf(g).copysign(self.0))
+    }
+}
+
+impl From<f32> for Ch32h {
+    fn from(value: f32) -> Self {
+        Ch32h::new(value)
+    }
+}
+
+impl From<Ch32h> for f32 {
+    fn from(c: Ch32h) -> f32 {
+        c.0
+    }
+}
+
+impl From<f64> for Ch32h {
+    fn from(value: f64) -> Self {
+        Ch32h::new(value as f32)
+    }
+}
+
+impl From<Ch32h> for f64 {
+    fn from(c: Ch32h) -> f64 {
+        f64::from(c.0)
+    }
+}
+
+impl From<Ch8> for Ch32h {
+    fn from(c: Ch8) -> Self {
+        Ch32h(c.into())
+    }
+}
+
+impl From<Ch16> for Ch32h {
+    fn from(c: Ch16) -> Self {
+        Ch32h(c.into())
+    }
+}
+
+impl From<Ch16f> for Ch32h {
+    fn from(c: Ch16f) -> Self {
+        Ch32h(c.into())
+    }
+}
+
+impl From<Ch32> for Ch32h {
+    fn from(c: Ch32) -> Self {
+        Ch32h(c.0)
+    }
+}
+
+impl From<Ch32h> for Ch8 {
+    fn from(c: Ch32h) -> Self {
+        Ch32::new(c.0).into()
+    }
+}
+
+impl From<Ch32h> for Ch16 {
+    fn from(c: Ch32h) -> Self {
+        Ch32::new(c.0).into()
+    }
+}
+
+impl From<Ch32h> for Ch16f {
+    fn from(c: Ch32h) -> Self {
+        Ch16f::new(c.0)
+    }
+}
+
+impl From<Ch32h> for Ch32 {
+    fn from(c: Ch32h) -> Self {
+        Ch32::new(c.0)
+    }
+}
+
+impl PartialEq for Ch32h {
+    fn eq(&self, other: &Ch32h) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ch32h {}
+
+impl PartialOrd for Ch32h {
+    fn partial_cmp(&self, other: &Ch32h) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ch32h {
+    fn cmp(&self, other: &Ch32h) -> Ordering {
+        // NaN can only come from raw bytes; order it totally
+        let (a, b) = (self.0, other.0);
+        a.partial_cmp(&b).unwrap_or_else(|| a.total_cmp(&b))
+    }
+}
+
+impl<R> Add<R> for Ch32h
+where
+    f32: From<R>,
+{
+    type Output = Self;
+    fn add(self, rhs: R) -> Self {
+        Ch32h::new(self.0 + f32::from(rhs))
+    }
+}
+
+impl<R> Sub<R> for Ch32h
+where
+    f32: From<R>,
+{
+    type Output = Self;
+    fn sub(self, rhs: R) -> Self {
+        Ch32h::new(self.0 - f32::from(rhs))
+    }
+}
+
+impl<R> Mul<R> for Ch32h
+where
+    f32: From<R>,
+{
+    type Output = Self;
+    fn mul(self, rhs: R) -> Self {
+        Ch32h::new(self.0 * f32::from(rhs))
+    }
+}
+
+impl<R> Div<R> for Ch32h
+where
+    f32: From<R>,
+{
+    type Output = Self;
+    fn div(self, rhs: R) -> Self {
+        let v = f32::from(rhs);
+        if v != 0.0 {
+            Ch32h::new(self.0 / v)
+        } else {
+            Ch32h(0.0)
+        }
+    }
+}
+
+impl Ch64 {
+    /// Create a new 64-bit `Channel` value.
+    ///
+    /// Returns [MIN](trait.Channel.html#associatedconstant.MIN) if value is
+    ///         less than 0.0, or `NaN`.
+    /// Returns [MAX](trait.Channel.html#associatedconstant.MAX) if value is
+    ///         greater than 1.0.
+    pub fn new(value: f64) -> Self {
+        let v = if value.is_nan() || value < 0.0 {
+            0.0
+        } else if value > 1.0 {
+            1.0
+        } else {
+            value
+        };
+        Ch64(v)
+    }
+}
+
+impl Channel for Ch64 {
+    /// Minimum intensity (*zero*)
+    const MIN: Ch64 = Ch64(0.0);
+
+    /// Maximum intensity (*one*)
+    const MAX: Ch64 = Ch64(1.0);
+
+    /// Raise to given power
+    fn powf(self, g: f32) -> Self {
+        Ch64::new(self.0.powf(f64::from(g)))
+    }
+}
+
+impl From<f64> for Ch64 {
+    fn from(value: f64) -> Self {
+        Ch64::new(value)
+    }
+}
+
+impl From<Ch64> for f64 {
+    fn from(c: Ch64) -> f64 {
+        c.0
+    }
+}
+
+impl From<f32> for Ch64 {
+    fn from(value: f32) -> Self {
+        Ch64::new(f64::from(value))
+    }
+}
+
+impl From<Ch64> for f32 {
+    fn from(c: Ch64) -> f32 {
+        c.0 as f32
+    }
+}
+
+impl From<Ch8> for Ch64 {
+    fn from(c: Ch8) -> Self {
+        Ch64(f64::from(c.0) / 255.0)
+    }
+}
+
+impl From<Ch16> for Ch64 {
+    fn from(c: Ch16) -> Self {
+        Ch64(f64::from(c.0) / 65535.0)
+    }
+}
+
+impl From<Ch16f> for Ch64 {
+    fn from(c: Ch16f) -> Self {
+        Ch64::new(f64::from(f16_bits_to_f32(c.0)))
+    }
+}
+
+impl From<Ch32> for Ch64 {
+    fn from(c: Ch32) -> Self {
+        Ch64(f64::from(c.0))
+    }
+}
+
+impl From<Ch32h> for Ch64 {
+    fn from(c: Ch32h) -> Self {
+        Ch64::new(f64::from(c.0))
+    }
+}
+
+impl From<Ch64> for Ch8 {
+    fn from(c: Ch64) -> Self {
+        let value = c.0;
+        debug_assert!((0.0..=1.0).contains(&value));
+        // this cast is not UB since the value is guaranteed
+        // to be between 0.0 and 1.0 (see bug #10184)
+        Ch8::new((value * 255.0).round() as u8)
+    }
+}
+
+impl From<Ch64> for Ch16 {
+    fn from(c: Ch64) -> Self {
+        let value = c.0;
+        debug_assert!((0.0..=1.0).contains(&value));
+        // this cast is not UB since the value is guaranteed
+        // to be between 0.0 and 1.0 (see bug #10184)
+        Ch16::new((value * 65535.0).round() as u16)
+    }
+}
+
+impl From<Ch64> for Ch16f {
+    fn from(c: Ch64) -> Self {
+        Ch16f::new(c.0 as f32)
+    }
+}
+
+impl From<Ch64> for Ch32 {
+    fn from(c: Ch64) -> Self {
+        Ch32::new(c.0 as f32)
+    }
+}
+
+impl From<Ch64> for Ch32h {
+    fn from(c: Ch64) -> Self {
+        Ch32h::new(c.0 as f32)
+    }
+}
+
+impl PartialEq for Ch64 {
+    fn eq(&self, other: &Ch64) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ch64 {}
+
+impl PartialOrd for Ch64 {
+    fn partial_cmp(&self, other: &Ch64) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ch64 {
+    fn cmp(&self, other: &Ch64) -> Ordering {
+        // NaN can only come from raw bytes; order it totally
+        let (a, b) = (self.0, other.0);
+        a.partial_cmp(&b).unwrap_or_else(|| a.total_cmp(&b))
+    }
+}
+
+impl<R> Add<R> for Ch64
+where
+    f64: From<R>,
+{
+    type Output = Self;
+    fn add(self, rhs: R) -> Self {
+        let value = self.0 + f64::from(rhs);
+        Ch64(value.min(1.0))
+    }
+}
+
+impl<R> Sub<R> for Ch64
+where
+    f64: From<R>,
+{
+    type Output = Self;
+    fn sub(self, rhs: R) -> Self {
+        let value = self.0 - f64::from(rhs);
+        Ch64(value.max(0.0))
+    }
+}
+
+impl<R> Mul<R> for Ch64
+where
+    f64: From<R>,
+{
+    type Output = Self;
+    fn mul(self, rhs: R) -> Self {
+        Ch64(self.0 * f64::from(rhs))
+    }
+}
+
+impl<R> Div<R> for Ch64
+where
+    f64: From<R>,
+{
+    type Output = Self;
+    fn div(self, rhs: R) -> Self {
+        let v = f64::from(rhs);
+        if v > 0.0 {
+            Ch64((self.0 / v).min(1.0))
+        } else {
+            Ch64(0.0)
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -536,4 +1279,111 @@ mod test {
         assert_eq!(Ch32::new(0.0625), Ch32::new(0.5) * 0.125);
         assert_eq!(Ch32::new(0.03125), Ch32::new(0.5) * 0.0625);
     }
+    #[test]
+    fn ch32_nan_bits() {
+        let a = Ch32(f32::NAN);
+        assert!(a > Ch32::MAX);
+        assert!(Ch32(-f32::NAN) < Ch32::MIN);
+        assert!(a >= a);
+        assert_eq!(a, a);
+        assert_eq!(Ch32(-0.0), Ch32::MIN);
+    }
+    #[test]
+    fn ch16f_into() {
+        assert_eq!(Ch16f::new(1.0).to_bits(), 0x3C00);
+        assert_eq!(Ch16f::new(0.5).to_bits(), 0x3800);
+        assert_eq!(Ch16f::new(0.333_333_34).to_bits(), 0x3555);
+        assert_eq!(Ch16f::new(1e-7).to_bits(), 0x0002);
+        assert_eq!(Ch16f::new(2.0).to_bits(), 0x4000);
+        assert_eq!(Ch16f::new(-1.0).to_bits(), 0xBC00);
+        assert_eq!(Ch16f::new(1e6).to_bits(), 0x7C00);
+        assert_eq!(Ch16f::new(f32::NAN), Ch16f::MIN);
+        assert_eq!(Ch16f::from_bits(0x7E00), Ch16f::MIN);
+        assert!(Ch16f::new(2.0) > Ch16f::MAX);
+        assert_eq!(Ch8::MAX, Ch16f::new(2.0).into());
+        assert_eq!(Ch32::MIN, Ch16f::new(-1.0).into());
+        assert_eq!(Ch16f::new(-0.0).to_bits(), 0x0000);
+        assert_eq!(Ch16f::from_bits(0x8000), Ch16f::MIN);
+        assert_eq!(Ch16f::from_bits(0x8000).to_bits(), 0x0000);
+        assert!(Ch16f::new(-0.0) <= Ch16f::MAX);
+        assert!(Ch16f::new(0.25) < Ch16f::new(0.5));
+        assert_eq!(f32::from(Ch16f::from_bits(0x0001)), 5.960_464_5e-8);
+        for i in 0..=255 {
+            let c8 = Ch8::new(i);
+            let c16f: Ch16f = c8.into();
+            assert_eq!(c8, c16f.into());
+        }
+        assert_eq!(Ch16::new(65535), Ch16f::MAX.into());
+        assert_eq!(Ch32::new(0.25), Ch16f::new(0.25).into());
+    }
+    #[test]
+    fn ch16f_mul() {
+        assert_eq!(Ch16f::new(0.5), Ch16f::new(1.0) * 0.5);
+        assert_eq!(Ch16f::new(0.25), Ch16f::new(0.5) * 0.5);
+        assert_eq!(Ch16f::new(1.0), Ch16f::new(0.25) / 0.25);
+        assert_eq!(Ch16f::new(1.25), Ch16f::new(0.75) + 0.5);
+        assert_eq!(Ch16f::new(-0.25), Ch16f::new(0.25) - 0.5);
+        assert_eq!(Ch16f::new(3.0), Ch16f::new(1.5) * 2.0);
+        assert_eq!(Ch16f::new(-4.0), Ch16f::new(-2.0).powf(2.0));
+    }
+    #[test]
+    fn ch16f_nan_bits() {
+        let a = Ch16f(0x7E00);
+        let b = Ch16f(0xFE00);
+        assert_ne!(a, Ch16f::MIN);
+        assert!(a > Ch16f::MAX);
+        assert!(b < Ch16f::MIN);
+        assert_eq!(a, a);
+    }
+    #[test]
+    fn ch32h_nan_bits() {
+        let a = Ch32h(f32::NAN);
+        assert!(a > Ch32h::new(f32::MAX));
+        assert!(Ch32h(-f32::NAN) < Ch32h::new(f32::MIN));
+        assert_eq!(a.cmp(&a), Ordering::Equal);
+        assert_eq!(a, a);
+    }
+    #[test]
+    fn ch32h_unclamped() {
+        assert_eq!(Ch32h::new(2.5), 2.5.into());
+        assert_eq!(Ch32h::new(-0.5), (-0.5).into());
+        assert_eq!(Ch32h::new(0.0), f32::NAN.into());
+        assert_eq!(Ch32h::new(3.0), Ch32h::new(1.5) * 2.0);
+        assert_eq!(Ch32h::new(3.0), Ch32h::new(1.5) / 0.5);
+        assert_eq!(Ch32h::new(4.0), Ch32h::new(2.5) + 1.5);
+        assert_eq!(Ch32h::new(-1.0), Ch32h::new(0.5) - 1.5);
+        assert_eq!(Ch32h::new(-4.0), Ch32h::new(-2.0).powf(2.0));
+        assert!(Ch32h::new(2.0) > Ch32h::MAX);
+        assert_eq!(Ch8::MAX, Ch32h::new(2.0).into());
+        assert_eq!(Ch16::MIN, Ch32h::new(-1.0).into());
+        assert_eq!(Ch32::new(0.5), Ch32h::new(0.5).into());
+        assert_eq!(Ch32h::new(1.0), Ch8::new(255).into());
+    }
+    #[test]
+    fn ch64_into() {
+        assert_eq!(Ch8::new(128), Ch64::new(0.5).into());
+        assert_eq!(Ch16::new(32768), Ch64::new(0.5).into());
+        assert_eq!(Ch32::new(0.5), Ch64::new(0.5).into());
+        assert_eq!(Ch64::new(1.0), Ch8::new(255).into());
+        assert_eq!(Ch64::new(1.0), Ch32h::new(2.0).into());
+        assert_eq!(Ch64::new(0.0), (-1.0).into());
+        assert_eq!(f64::from(Ch64::from(Ch16::new(1))), 1.0 / 65535.0);
+    }
+    #[test]
+    fn ch64_mul() {
+        assert_eq!(Ch64::new(0.25), Ch64::new(0.5) * Ch64::new(0.5));
+        assert_eq!(Ch64::new(1.0), Ch64::new(0.75) + 0.5);
+        assert_eq!(Ch64::new(0.0), Ch64::new(0.25) - 0.5);
+        assert_eq!(Ch64::new(0.5), Ch64::new(0.25) / 0.5);
+        let v = Ch64::new(1.0) * 1e-12;
+        assert_eq!(f64::from(v * 1e12), 1.0);
+    }
+    #[test]
+    fn ch64_nan_bits() {
+        let a = Ch64(f64::NAN);
+        assert!(a > Ch64::MAX);
+        assert!(Ch64(-f64::NAN) < Ch64::MIN);
+        assert_eq!(a.cmp(&a), Ordering::Equal);
+        assert_eq!(a, a);
+    }
 }
@@ -0,0 +1,519 @@
+// cmyk.rs      CMYK color model.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{
+    self, AChannel, Opaque, Premultiplied, Straight, Translucent,
+};
+use crate::format::Model;
+use crate::gamma::{self, Linear, Srgb};
+use crate::gamut;
+use crate::hue::rgb_to_f32;
+use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel};
+use crate::{Raster, RasterBuilder, Rgb};
+use std::marker::PhantomData;
+
+/// CMYK subtractive [color model].
+///
+/// The components are *cyan*, *magenta*, *yellow* and *key* (black), with
+/// optional *[alpha]*.  Each component is an amount of ink, from
+/// [Channel::MIN] (none) to [Channel::MAX] (full coverage).
+///
+/// Converting from RGB uses the default [Separation] (full undercolor removal
+/// and no ink limit).  Use a custom [Separation] for other behavior.
+///
+/// [alpha]: alpha/trait.AChannel.html
+/// [Channel::MAX]: trait.Channel.html#associatedconstant.MAX
+/// [Channel::MIN]: trait.Channel.html#associatedconstant.MIN
+/// [color model]: trait.ColorModel.html
+/// [Separation]: struct.Separation.html
+///
+/// ### Convert from `Rgb`
+/// ```
+/// # use pix::*;
+/// let cmyk: SCmyk8 = SRgb8::new(0xFF, 0x80, 0x00).convert();
+/// assert_eq!(cmyk, SCmyk8::new(0x00, 0x7F, 0xFF, 0x00));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Cmyk<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    components: [C; 4],
+    alpha: A,
+    mode: PhantomData<M>,
+    gamma: PhantomData<G>,
+}
+
+/// Separation settings for converting RGB to [Cmyk].
+///
+/// * *Undercolor removal* is the fraction of the common gray component of
+///   *cyan*, *magenta* and *yellow* which is replaced with *key* ink, from 0
+///   (none) to 1 (all).
+/// * *Ink limit* is the maximum total ink coverage, from 0 to 4 (400%).
+///   When exceeded, *cyan*, *magenta* and *yellow* are reduced.
+///
+/// [Cmyk]: struct.Cmyk.html
+///
+/// ### Separate with 200% ink limit
+/// ```
+/// # use pix::*;
+/// let sep = Separation::default()
+///     .with_undercolor_removal(0.0)
+///     .with_ink_limit(2.0);
+/// let cmyk: SCmyk8 = sep.separate(SRgb32::new(0.0, 0.0, 0.5));
+/// assert_eq!(cmyk, SCmyk8::new(0xCC, 0xCC, 0x66, 0x00));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Separation {
+    undercolor_removal: f32,
+    ink_limit: f32,
+}
+
+impl<C, A, M, G> Cmyk<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying
+    /// *cyan*, *magenta*, *yellow* and *key*.
+    pub fn new<H>(cyan: H, magenta: H, yellow: H, key: H) -> Self
+    where
+        C: From<H>,
+        A: From<Opaque<C>>,
+    {
+        Self::with_alpha(cyan, magenta, yellow, key, Opaque::default())
+    }
+    /// Create a [Translucent](alpha/struct.Translucent.html) color by
+    /// specifying *cyan*, *magenta*, *yellow*, *key* and *alpha*.
+    pub fn with_alpha<H, B>(
+        cyan: H,
+        magenta: H,
+        yellow: H,
+        key: H,
+        alpha: B,
+    ) -> Self
+    where
+        C: From<H>,
+        A: From<B>,
+    {
+        let cyan = C::from(cyan);
+        let magenta = C::from(magenta);
+        let yellow = C::from(yellow);
+        let key = C::from(key);
+        let components = [cyan, magenta, yellow, key];
+        let alpha = A::from(alpha);
+        Cmyk {
+            components,
+            alpha,
+            mode: PhantomData,
+            gamma: PhantomData,
+        }
+    }
+    /// Get the *cyan* component.
+    pub fn cyan(self) -> C {
+        self.components[0]
+    }
+    /// Get the *magenta* component.
+    pub fn magenta(self) -> C {
+        self.components[1]
+    }
+    /// Get the *yellow* component.
+    pub fn yellow(self) -> C {
+        self.components[2]
+    }
+    /// Get the *key* (black) component.
+    pub fn key(self) -> C {
+        self.components[3]
+    }
+}
+
+impl<C, A, M, G> ColorModel for Cmyk<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Chan = C;
+    const MODEL: Model = Model::Cmyk;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
+
+    /// Get all non-alpha components
+    fn components(&self) -> &[Self::Chan] {
+        &self.components
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.alpha.value()
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        let cyan: f32 = self.cyan().into();
+        let magenta: f32 = self.magenta().into();
+        let yellow: f32 = self.yellow().into();
+        let key: f32 = self.key().into();
+        let red = (1.0 - cyan) * (1.0 - key);
+        let green = (1.0 - magenta) * (1.0 - key);
+        let blue = (1.0 - yellow) * (1.0 - key);
+        [red.into(), green.into(), blue.into(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        Separation::default().separate_rgba(rgba)
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let c = if self.cyan() > rhs.cyan() {
+            self.cyan() - rhs.cyan()
+        } else {
+            rhs.cyan() - self.cyan()
+        };
+        let m = if self.magenta() > rhs.magenta() {
+            self.magenta() - rhs.magenta()
+        } else {
+            rhs.magenta() - self.magenta()
+        };
+        let y = if self.yellow() > rhs.yellow() {
+            self.yellow() - rhs.yellow()
+        } else {
+            rhs.yellow() - self.yellow()
+        };
+        let k = if self.key() > rhs.key() {
+            self.key() - rhs.key()
+        } else {
+            rhs.key() - self.key()
+        };
+        let a = if self.alpha() > rhs.alpha() {
+            self.alpha() - rhs.alpha()
+        } else {
+            rhs.alpha() - self.alpha()
+        };
+        Cmyk::with_alpha(c, m, y, k, a)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        self.cyan() <= rhs.cyan()
+            && self.magenta() <= rhs.magenta()
+            && self.yellow() <= rhs.yellow()
+            && self.key() <= rhs.key()
+            && self.alpha() <= rhs.alpha()
+    }
+}
+
+impl<C, A, M, G> Pixel for Cmyk<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Alpha = M;
+    type Gamma = G;
+    type Primaries = gamut::Srgb;
+}
+
+impl<C, A, M, G> Iterator for Cmyk<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<C, M, G> From<Cmyk<C, Translucent<C>, M, G>> for Cmyk<C, Opaque<C>, M, G>
+where
+    C: Channel,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn from(c: Cmyk<C, Translucent<C>, M, G>) -> Self {
+        Cmyk::new(c.cyan(), c.magenta(), c.yellow(), c.key())
+    }
+}
+
+impl<C, M, G> From<Cmyk<C, Opaque<C>, M, G>> for Cmyk<C, Translucent<C>, M, G>
+where
+    C: Channel,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn from(c: Cmyk<C, Opaque<C>, M, G>) -> Self {
+        Cmyk::with_alpha(c.cyan(), c.magenta(), c.yellow(), c.key(), C::MAX)
+    }
+}
+
+impl Default for Separation {
+    fn default() -> Self {
+        Separation {
+            undercolor_removal: 1.0,
+            ink_limit: 4.0,
+        }
+    }
+}
+
+impl Separation {
+    /// Set the *undercolor removal* fraction (0 to 1).
+    pub fn with_undercolor_removal(mut self, undercolor_removal: f32) -> Self {
+        self.undercolor_removal = undercolor_removal.clamp(0.0, 1.0);
+        self
+    }
+    /// Set the total *ink limit* (0 to 4).
+    pub fn with_ink_limit(mut self, ink_limit: f32) -> Self {
+        self.ink_limit = ink_limit.clamp(0.0, 4.0);
+        self
+    }
+    /// Get the *undercolor removal* fraction.
+    pub fn undercolor_removal(&self) -> f32 {
+        self.undercolor_removal
+    }
+    /// Get the total *ink limit*.
+    pub fn ink_limit(&self) -> f32 {
+        self.ink_limit
+    }
+    /// Separate a pixel into [Cmyk](struct.Cmyk.html) components.
+    ///
+    /// * `S` `Pixel` format of source.
+    pub fn separate<S, C, A, M, G>(&self, src: S) -> Cmyk<C, A, M, G>
+    where
+        S: Pixel,
+        C: Channel + From<S::Chan>,
+        A: AChannel<Chan = C> + From<C>,
+        M: alpha::Mode,
+        G: gamma::Mode,
+    {
+        let rgb: Rgb<C, A, M, G> = src.convert();
+        self.separate_rgba(rgb.to_rgba())
+    }
+    /// Separate a `Raster` into [Cmyk](struct.Cmyk.html) components.
+    ///
+    /// * `S` `Pixel` format of source `Raster`.
+    ///
+    /// ### Separate an `SRgb8` raster
+    /// ```
+    /// # use pix::*;
+    /// let r0 = RasterBuilder::<SRgb8>::new().with_clear(50, 50);
+    /// let sep = Separation::default().with_ink_limit(2.8);
+    /// let r1: Raster<SCmyk8> = sep.separate_raster(&r0);
+    /// ```
+    pub fn separate_raster<S, C, A, M, G>(
+        &self,
+        src: &Raster<S>,
+    ) -> Raster<Cmyk<C, A, M, G>>
+    where
+        S: Pixel,
+        C: Channel + From<S::Chan>,
+        A: AChannel<Chan = C> + From<C>,
+        M: alpha::Mode,
+        G: gamma::Mode,
+    {
+        RasterBuilder::new().with_map(src, |p| self.separate(p))
+    }
+    /// Separate *red*, *green*, *blue* and *alpha* components.
+    fn separate_rgba<C, A, M, G>(&self, rgba: [C; 4]) -> Cmyk<C, A, M, G>
+    where
+        C: Channel,
+        A: AChannel<Chan = C> + From<C>,
+        M: alpha::Mode,
+        G: gamma::Mode,
+    {
+        let [red, green, blue] = rgb_to_f32(rgba);
+        let (cyan, magenta, yellow) = (1.0 - red, 1.0 - green, 1.0 - blue);
+        let key = cyan.min(magenta).min(yellow) * self.undercolor_removal;
+        let (mut cyan, mut magenta, mut yellow) = if key < 1.0 {
+            (
+                (cyan - key) / (1.0 - key),
+                (magenta - key) / (1.0 - key),
+                (yellow - key) / (1.0 - key),
+            )
+        } else {
+            (0.0, 0.0, 0.0)
+        };
+        let total = cyan + magenta + yellow;
+        if total + key > self.ink_limit && total > 0.0 {
+            let scale = (self.ink_limit - key).max(0.0) / total;
+            cyan *= scale;
+            magenta *= scale;
+            yellow *= scale;
+        }
+        Cmyk::with_alpha(cyan, magenta, yellow, key, rgba[3])
+    }
+}
+
+/// [Cmyk](struct.Cmyk.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Cmyk8 = Cmyk<Ch8, Opaque<Ch8>, Straight, Linear>;
+/// [Cmyk](struct.Cmyk.html) 16-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Cmyk16 = Cmyk<Ch16, Opaque<Ch16>, Straight, Linear>;
+/// [Cmyk](struct.Cmyk.html) 32-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Cmyk32 = Cmyk<Ch32, Opaque<Ch32>, Straight, Linear>;
+
+/// [Cmyk](struct.Cmyk.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Cmyka8 = Cmyk<Ch8, Translucent<Ch8>, Straight, Linear>;
+/// [Cmyk](struct.Cmyk.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Cmyka16 = Cmyk<Ch16, Translucent<Ch16>, Straight, Linear>;
+/// [Cmyk](struct.Cmyk.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Cmyka32 = Cmyk<Ch32, Translucent<Ch32>, Straight, Linear>;
+
+/// [Cmyk](struct.Cmyk.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Cmyka8p = Cmyk<Ch8, Translucent<Ch8>, Premultiplied, Linear>;
+/// [Cmyk](struct.Cmyk.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Cmyka16p = Cmyk<Ch16, Translucent<Ch16>, Premultiplied, Linear>;
+/// [Cmyk](struct.Cmyk.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Cmyka32p = Cmyk<Ch32, Translucent<Ch32>, Premultiplied, Linear>;
+
+/// [Cmyk](struct.Cmyk.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SCmyk8 = Cmyk<Ch8, Opaque<Ch8>, Straight, Srgb>;
+/// [Cmyk](struct.Cmyk.html) 16-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SCmyk16 = Cmyk<Ch16, Opaque<Ch16>, Straight, Srgb>;
+/// [Cmyk](struct.Cmyk.html) 32-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SCmyk32 = Cmyk<Ch32, Opaque<Ch32>, Straight, Srgb>;
+
+/// [Cmyk](struct.Cmyk.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SCmyka8 = Cmyk<Ch8, Translucent<Ch8>, Straight, Srgb>;
+/// [Cmyk](struct.Cmyk.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SCmyka16 = Cmyk<Ch16, Translucent<Ch16>, Straight, Srgb>;
+/// [Cmyk](struct.Cmyk.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SCmyka32 = Cmyk<Ch32, Translucent<Ch32>, Straight, Srgb>;
+
+/// [Cmyk](struct.Cmyk.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SCmyka8p = Cmyk<Ch8, Translucent<Ch8>, Premultiplied, Srgb>;
+/// [Cmyk](struct.Cmyk.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SCmyka16p = Cmyk<Ch16, Translucent<Ch16>, Premultiplied, Srgb>;
+/// [Cmyk](struct.Cmyk.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SCmyka32p = Cmyk<Ch32, Translucent<Ch32>, Premultiplied, Srgb>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<SCmyk8>(), 4);
+        assert_eq!(std::mem::size_of::<SCmyk16>(), 8);
+        assert_eq!(std::mem::size_of::<SCmyk32>(), 16);
+        assert_eq!(std::mem::size_of::<SCmyka8>(), 5);
+        assert_eq!(std::mem::size_of::<SCmyka16>(), 10);
+        assert_eq!(std::mem::size_of::<SCmyka32>(), 20);
+    }
+
+    #[test]
+    fn cmyk_to_rgb() {
+        assert_eq!(
+            SRgb8::new(0xFF, 0xFF, 0xFF),
+            SCmyk8::new(0x00, 0x00, 0x00, 0x00).convert(),
+        );
+        assert_eq!(
+            SRgb8::new(0x00, 0x00, 0x00),
+            SCmyk8::new(0x00, 0x00, 0x00, 0xFF).convert(),
+        );
+        assert_eq!(
+            SRgb32::new(0.25, 0.5, 0.125),
+            SCmyk32::new(0.5, 0.0, 0.75, 0.5).convert(),
+        );
+    }
+
+    #[test]
+    fn rgb_to_cmyk() {
+        assert_eq!(
+            SCmyk32::new(0.0, 1.0, 1.0, 0.0),
+            SRgb32::new(1.0, 0.0, 0.0).convert(),
+        );
+        assert_eq!(
+            SCmyk32::new(0.0, 0.0, 0.0, 0.75),
+            SRgb32::new(0.25, 0.25, 0.25).convert(),
+        );
+        assert_eq!(
+            SCmyk32::new(0.5, 0.0, 0.75, 0.5),
+            SRgb32::new(0.25, 0.5, 0.125).convert(),
+        );
+        assert_eq!(
+            SCmyka16::with_alpha(0x0000, 0xFFFF, 0x0000, 0x0000, 0x8000),
+            SRgba16::with_alpha(0xFFFF, 0x0000, 0xFFFF, 0x8000).convert(),
+        );
+    }
+
+    #[test]
+    fn undercolor_removal() {
+        let sep = Separation::default().with_undercolor_removal(0.0);
+        assert_eq!(
+            SCmyk32::new(0.75, 0.5, 0.875, 0.0),
+            sep.separate(SRgb32::new(0.25, 0.5, 0.125)),
+        );
+        let sep = Separation::default().with_undercolor_removal(0.5);
+        assert_eq!(
+            SCmyk32::new(1.0, 1.0, 1.0, 0.5),
+            sep.separate(SRgb32::new(0.0, 0.0, 0.0)),
+        );
+    }
+
+    #[test]
+    fn ink_limit() {
+        let sep = Separation::default().with_ink_limit(2.5);
+        let cmyk: SCmyk32 = sep.separate(SRgb32::new(0.0, 0.0, 0.5));
+        assert_eq!(cmyk, SCmyk32::new(1.0, 1.0, 0.0, 0.5));
+        let sep = sep.with_undercolor_removal(0.0);
+        let cmyk: SCmyk32 = sep.separate(SRgb32::new(0.0, 0.0, 0.0));
+        let total: f32 = cmyk.components().iter().map(|c| f32::from(*c)).sum();
+        assert!((total - 2.5).abs() < 1e-6);
+        assert_eq!(cmyk.key(), Ch32::new(0.0));
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = SRgb8::new(r, g, b);
+                    let cmyk: SCmyk32 = rgb.convert();
+                    let back: SRgb16 = cmyk.convert();
+                    assert_eq!(rgb, back.convert());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,256 @@
+// dyn_raster.rs    Dynamically typed raster images.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::format::PixelFormat;
+use crate::{
+    Ch16, Ch32, Ch8, Error, Gray16, Gray32, Gray8, GrayAlpha16, GrayAlpha16p,
+    GrayAlpha32, GrayAlpha32p, GrayAlpha8, GrayAlpha8p, Mask16, Mask32, Mask8,
+    Pixel, Raster, RasterBuilder, Rgb16, Rgb32, Rgb8, Rgba16, Rgba16p, Rgba32,
+    Rgba32p, Rgba8, Rgba8p, SGray16, SGray32, SGray8, SGrayAlpha16,
+    SGrayAlpha16p, SGrayAlpha32, SGrayAlpha32p, SGrayAlpha8, SGrayAlpha8p,
+    SRgb16, SRgb32, SRgb8, SRgba16, SRgba16p, SRgba32, SRgba32p, SRgba8,
+    SRgba8p,
+};
+use std::any::Any;
+
+macro_rules! dyn_raster {
+    ($($p:ident),* $(,)?) => {
+        /// [Raster] with a [pixel format] known only at runtime.
+        ///
+        /// Each variant wraps a `Raster` of one supported [Pixel] type:
+        /// [Gray], `GrayAlpha`, [Rgb], `Rgba` (straight or premultiplied)
+        /// and [Mask] with 8-, 16- and 32-bit channels, in linear or sRGB
+        /// gamma.
+        ///
+        /// [Gray]: struct.Gray.html
+        /// [Mask]: struct.Mask.html
+        /// [Pixel]: trait.Pixel.html
+        /// [pixel format]: format/struct.PixelFormat.html
+        /// [Raster]: struct.Raster.html
+        /// [Rgb]: struct.Rgb.html
+        ///
+        /// ### Load a `Raster` with a runtime format
+        /// ```
+        /// # use pix::*;
+        /// # use pix::format::{Alpha, Depth, Gamma, Model, PixelFormat};
+        /// # use pix::format::{Primaries, White};
+        /// let fmt = PixelFormat::new(
+        ///     Model::Rgb,
+        ///     Depth::Ch8,
+        ///     Alpha::Opaque,
+        ///     Gamma::Srgb,
+        ///     Primaries::Srgb,
+        ///     White::D65,
+        /// );
+        /// let mut r = DynRaster::new(fmt, 2, 1)?.expect("unsupported");
+        /// r.as_u8_slice_mut().copy_from_slice(&[0xFF, 0, 0, 0, 0xFF, 0]);
+        /// let r: Raster<SRgba8> = r.into_raster();
+        /// assert_eq!(r.pixel(1, 0), SRgba8::new(0, 0xFF, 0));
+        /// # Ok::<(), Error>(())
+        /// ```
+        pub enum DynRaster {
+            $(
+                #[doc = concat!(
+                    "`Raster` of [", stringify!($p), "](type.",
+                    stringify!($p), ".html) pixels"
+                )]
+                $p(Raster<$p>),
+            )*
+        }
+
+        $(
+            impl From<Raster<$p>> for DynRaster {
+                fn from(raster: Raster<$p>) -> Self {
+                    DynRaster::$p(raster)
+                }
+            }
+        )*
+
+        impl DynRaster {
+            /// Create a cleared `DynRaster`.
+            ///
+            /// * `format` Pixel format.
+            /// * `width` Width of `Raster`.
+            /// * `height` Height of `Raster`.
+            ///
+            /// Returns `Ok(None)` if the pixel format is not supported, or
+            /// [Error::SizeOverflow] if `width` * `height` overflows.
+            ///
+            /// [Error::SizeOverflow]: enum.Error.html#variant.SizeOverflow
+            pub fn new(
+                format: PixelFormat,
+                width: u32,
+                height: u32,
+            ) -> Result<Option<Self>, Error> {
+                $(
+                    if format == PixelFormat::of::<$p>() {
+                        let r = RasterBuilder::<$p>::new()
+                            .try_with_clear(width, height)?;
+                        return Ok(Some(DynRaster::$p(r)));
+                    }
+                )*
+                Ok(None)
+            }
+            /// Get the pixel format.
+            pub fn format(&self) -> PixelFormat {
+                match self {
+                    $(DynRaster::$p(_) => PixelFormat::of::<$p>(),)*
+                }
+            }
+            /// Get width of `Raster`.
+            pub fn width(&self) -> u32 {
+                match self {
+                    $(DynRaster::$p(r) => r.width(),)*
+                }
+            }
+            /// Get height of `Raster`.
+            pub fn height(&self) -> u32 {
+                match self {
+                    $(DynRaster::$p(r) => r.height(),)*
+                }
+            }
+            /// Get view of pixels as a `u8` slice.
+            pub fn as_u8_slice(&self) -> &[u8] {
+                match self {
+                    $(DynRaster::$p(r) => r.as_u8_slice(),)*
+                }
+            }
+            /// Get view of pixels as a mutable `u8` slice.
+            ///
+            /// Channels wider than 8 bits are in native-endian byte order.
+            pub fn as_u8_slice_mut(&mut self) -> &mut [u8] {
+                match self {
+                    $(DynRaster::$p(r) => r.as_u8_slice_mut(),)*
+                }
+            }
+            /// Convert to a `Raster` of another format.
+            ///
+            /// * `P` `Pixel` format of destination `Raster`.
+            ///
+            /// This has the same semantics as `RasterBuilder`::[with_raster],
+            /// but the `Raster` is moved without conversion if its format
+            /// already matches.
+            ///
+            /// [with_raster]: struct.RasterBuilder.html#method.with_raster
+            pub fn into_raster<P>(self) -> Raster<P>
+            where
+                P: Pixel,
+                P::Chan: From<Ch8> + From<Ch16> + From<Ch32>,
+            {
+                match self {
+                    $(DynRaster::$p(r) => convert_raster(r),)*
+                }
+            }
+        }
+    };
+}
+
+dyn_raster!(
+    Gray8,
+    Gray16,
+    Gray32,
+    SGray8,
+    SGray16,
+    SGray32,
+    GrayAlpha8,
+    GrayAlpha16,
+    GrayAlpha32,
+    GrayAlpha8p,
+    GrayAlpha16p,
+    GrayAlpha32p,
+    SGrayAlpha8,
+    SGrayAlpha16,
+    SGrayAlpha32,
+    SGrayAlpha8p,
+    SGrayAlpha16p,
+    SGrayAlpha32p,
+    Rgb8,
+    Rgb16,
+    Rgb32,
+    SRgb8,
+    SRgb16,
+    SRgb32,
+    Rgba8,
+    Rgba16,
+    Rgba32,
+    Rgba8p,
+    Rgba16p,
+    Rgba32p,
+    SRgba8,
+    SRgba16,
+    SRgba32,
+    SRgba8p,
+    SRgba16p,
+    SRgba32p,
+    Mask8,
+    Mask16,
+    Mask32,
+);
+
+/// Convert a `Raster`, moving it if the format matches
+fn convert_raster<S, D>(src: Raster<S>) -> Raster<D>
+where
+    S: Pixel,
+    D: Pixel,
+    D::Chan: From<S::Chan>,
+{
+    let src: Box<dyn Any> = Box::new(src);
+    match src.downcast::<Raster<D>>() {
+        Ok(r) => *r,
+        Err(src) => match src.downcast::<Raster<S>>() {
+            Ok(r) => RasterBuilder::new().with_raster(&r),
+            Err(_) => unreachable!(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::format::{Alpha, Depth, Gamma, Model, Primaries, White};
+    use crate::{P3Rgb8, SRgb565};
+
+    #[test]
+    fn dyn_raster() {
+        let fmt = PixelFormat::new(
+            Model::Gray,
+            Depth::Ch16,
+            Alpha::Straight,
+            Gamma::Srgb,
+            Primaries::Srgb,
+            White::D65,
+        );
+        let mut r = DynRaster::new(fmt, 3, 2).unwrap().unwrap();
+        assert_eq!(r.format(), fmt);
+        assert_eq!(r.width(), 3);
+        assert_eq!(r.height(), 2);
+        assert_eq!(r.as_u8_slice().len(), 3 * 2 * 4);
+        r.as_u8_slice_mut()[..4].copy_from_slice(&[0xFF; 4]);
+        let r: Raster<SGrayAlpha8> = r.into_raster();
+        assert_eq!(r.pixel(0, 0), SGrayAlpha8::new(0xFF));
+        assert_eq!(r.pixel(1, 0), SGrayAlpha8::with_alpha(0, 0));
+        let r = DynRaster::new(fmt, 1 << 16, 1 << 16);
+        assert_eq!(r.err(), Some(Error::SizeOverflow));
+        let fmt = PixelFormat::of::<SRgb565>();
+        assert!(DynRaster::new(fmt, 1, 1).unwrap().is_none());
+        let fmt = PixelFormat::of::<P3Rgb8>();
+        assert!(DynRaster::new(fmt, 1, 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn into_raster() {
+        let r = RasterBuilder::<Rgba8p>::new()
+            .with_color(2, 2, Rgba8p::with_alpha(0x80, 0x40, 0x20, 0x80));
+        let d = DynRaster::from(r);
+        assert_eq!(d.format(), PixelFormat::of::<Rgba8p>());
+        let r: Raster<Rgba8p> = d.into_raster();
+        assert_eq!(r.pixel(1, 1), Rgba8p::with_alpha(0x80, 0x40, 0x20, 0x80));
+        let d = DynRaster::from(r);
+        let r: Raster<Rgba16p> = d.into_raster();
+        assert_eq!(
+            r.pixel(0, 0),
+            Rgba16p::with_alpha(0x8080, 0x4040, 0x2020, 0x8080)
+        );
+    }
+}
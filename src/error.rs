@@ -0,0 +1,59 @@
+// error.rs     Error type.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use std::fmt;
+
+/// Error building a [Raster], [PackedRaster], [PlanarRaster] or
+/// [RasterView].
+///
+/// [PackedRaster]: struct.PackedRaster.html
+/// [PlanarRaster]: struct.PlanarRaster.html
+/// [Raster]: struct.Raster.html
+/// [RasterView]: struct.RasterView.html
+///
+/// ### Reject an untrusted buffer
+/// ```
+/// # use pix::*;
+/// let r = RasterBuilder::<SRgb8>::new().try_with_u8_buffer(4, 4, vec![0; 47]);
+/// assert_eq!(
+///     r.err(),
+///     Some(Error::BufferLength {
+///         expected: 48,
+///         actual: 47
+///     })
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Size (`width` * `height`, or length in bytes) overflows
+    SizeOverflow,
+    /// Buffer length does not match the size, or is too small for a view
+    BufferLength {
+        /// Expected length, in buffer elements
+        expected: usize,
+        /// Actual length, in buffer elements
+        actual: usize,
+    },
+    /// Buffer or row stride is not aligned for the pixel type
+    Misaligned,
+    /// Row stride is less than the width, in bytes
+    InvalidStride,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::SizeOverflow => write!(f, "size overflow"),
+            Error::BufferLength { expected, actual } => write!(
+                f,
+                "buffer length mismatch: expected {}, actual {}",
+                expected, actual
+            ),
+            Error::Misaligned => write!(f, "misaligned buffer"),
+            Error::InvalidStride => write!(f, "stride too small"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
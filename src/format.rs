@@ -0,0 +1,296 @@
+// format.rs    Runtime pixel format descriptors.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+//! Module for runtime pixel format items
+use crate::alpha::Premultiplied;
+use crate::gamma::Mode as _;
+use crate::gamut::{self, Primaries as _};
+use crate::white::Point as _;
+use crate::{Ch16, Ch16f, Ch32, Ch32h, Ch64, Ch8, Channel, Pixel};
+use std::any::TypeId;
+
+/// Color model of a pixel format
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Model {
+    /// [Argb](../struct.Argb.html)
+    Argb,
+    /// [Bgr](../struct.Bgr.html)
+    Bgr,
+    /// [Cmyk](../struct.Cmyk.html)
+    Cmyk,
+    /// [Gray](../struct.Gray.html)
+    Gray,
+    /// [Hsl](../struct.Hsl.html)
+    Hsl,
+    /// [Hsv](../struct.Hsv.html)
+    Hsv,
+    /// [Hwb](../struct.Hwb.html)
+    Hwb,
+    /// [Lab](../struct.Lab.html)
+    Lab,
+    /// [Lch](../struct.Lch.html)
+    Lch,
+    /// [Mask](../struct.Mask.html)
+    Mask,
+    /// [Oklab](../struct.Oklab.html)
+    Oklab,
+    /// [Oklch](../struct.Oklch.html)
+    Oklch,
+    /// [PackedRgb](../struct.PackedRgb.html)
+    PackedRgb,
+    /// [Rgb](../struct.Rgb.html)
+    Rgb,
+    /// [Xyz](../struct.Xyz.html)
+    Xyz,
+    /// [YCbCr](../struct.YCbCr.html)
+    YCbCr,
+}
+
+/// Channel depth of a pixel format
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Depth {
+    /// 8-bit integer [Ch8](../struct.Ch8.html)
+    Ch8,
+    /// 16-bit integer [Ch16](../struct.Ch16.html)
+    Ch16,
+    /// 16-bit floating-point [Ch16f](../struct.Ch16f.html)
+    Ch16f,
+    /// 32-bit floating-point [Ch32](../struct.Ch32.html)
+    Ch32,
+    /// 32-bit floating-point high dynamic range
+    /// [Ch32h](../struct.Ch32h.html)
+    Ch32h,
+    /// 64-bit floating-point [Ch64](../struct.Ch64.html)
+    Ch64,
+}
+
+/// Alpha mode of a pixel format
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Alpha {
+    /// No *alpha* component ([Opaque](../alpha/struct.Opaque.html))
+    Opaque,
+    /// [Straight](../alpha/struct.Straight.html) alpha
+    Straight,
+    /// [Premultiplied](../alpha/struct.Premultiplied.html) alpha
+    Premultiplied,
+}
+
+/// Gamma mode of a pixel format
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Gamma {
+    /// [Linear](../gamma/struct.Linear.html) gamma
+    Linear,
+    /// [sRGB](../gamma/struct.Srgb.html) gamma
+    Srgb,
+    /// [Rec. 709](../gamma/struct.Rec709.html) gamma
+    Rec709,
+    /// [Power law](../gamma/struct.PowerLaw.html) gamma, with an exponent
+    /// of `N / D`
+    PowerLaw(u32, u32),
+    /// [PQ](../gamma/struct.Pq.html) gamma, with linear 1.0 equal to
+    /// `NITS`
+    Pq(u32),
+    /// [HLG](../gamma/struct.Hlg.html) gamma, with a `PEAK` display
+    /// luminance and linear 1.0 equal to `NITS`
+    Hlg(u32, u32),
+}
+
+/// RGB primaries of a pixel format
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Primaries {
+    /// [sRGB](../gamut/struct.Srgb.html) primaries
+    Srgb,
+    /// [Display P3](../gamut/struct.DisplayP3.html) primaries
+    DisplayP3,
+    /// [Rec. 2020](../gamut/struct.Rec2020.html) primaries
+    Rec2020,
+    /// [Adobe RGB](../gamut/struct.AdobeRgb.html) primaries
+    AdobeRgb,
+    /// [ProPhoto RGB](../gamut/struct.ProPhotoRgb.html) primaries
+    ProPhotoRgb,
+    /// [ACEScg](../gamut/struct.AcesCg.html) primaries
+    AcesCg,
+    /// [CIE XYZ](../gamut/struct.CieXyz.html) tristimulus values
+    CieXyz,
+}
+
+/// White point of a pixel format
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum White {
+    /// [D50](../white/struct.D50.html) white point
+    D50,
+    /// [D55](../white/struct.D55.html) white point
+    D55,
+    /// [D60](../white/struct.D60.html) white point
+    D60,
+    /// [D65](../white/struct.D65.html) white point
+    D65,
+    /// [D75](../white/struct.D75.html) white point
+    D75,
+    /// [E](../white/struct.E.html) white point
+    E,
+}
+
+/// Runtime descriptor of a [Pixel] format.
+///
+/// The descriptor covers [color model], channel depth, [alpha mode],
+/// [gamma mode], RGB [primaries] and [white point].  Video parameters
+/// ([YCbCr] matrix and range) are not included.
+///
+/// [alpha mode]: ../alpha/trait.Mode.html
+/// [color model]: ../trait.ColorModel.html
+/// [gamma mode]: ../gamma/trait.Mode.html
+/// [Pixel]: ../trait.Pixel.html
+/// [primaries]: ../gamut/trait.Primaries.html
+/// [white point]: ../white/trait.Point.html
+/// [YCbCr]: ../struct.YCbCr.html
+///
+/// ```
+/// # use pix::*;
+/// # use pix::format::{Alpha, Depth, Gamma, Model, PixelFormat};
+/// # use pix::format::{Primaries, White};
+/// let fmt = PixelFormat::of::<SRgba16p>();
+/// assert_eq!(fmt.model(), Model::Rgb);
+/// assert_eq!(fmt.depth(), Depth::Ch16);
+/// assert_eq!(fmt.alpha(), Alpha::Premultiplied);
+/// assert_eq!(fmt.gamma(), Gamma::Srgb);
+/// assert_eq!(fmt.primaries(), Primaries::Srgb);
+/// assert_eq!(fmt.white(), White::D65);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PixelFormat {
+    model: Model,
+    depth: Depth,
+    alpha: Alpha,
+    gamma: Gamma,
+    primaries: Primaries,
+    white: White,
+}
+
+impl Depth {
+    /// Get the depth of a `Channel` type
+    fn of<C: Channel>() -> Self {
+        let id = TypeId::of::<C>();
+        if id == TypeId::of::<Ch8>() {
+            Depth::Ch8
+        } else if id == TypeId::of::<Ch16>() {
+            Depth::Ch16
+        } else if id == TypeId::of::<Ch16f>() {
+            Depth::Ch16f
+        } else if id == TypeId::of::<Ch32>() {
+            Depth::Ch32
+        } else if id == TypeId::of::<Ch32h>() {
+            Depth::Ch32h
+        } else if id == TypeId::of::<Ch64>() {
+            Depth::Ch64
+        } else {
+            unreachable!("sealed Channel type")
+        }
+    }
+}
+
+impl PixelFormat {
+    /// Create a pixel format descriptor.
+    pub const fn new(
+        model: Model,
+        depth: Depth,
+        alpha: Alpha,
+        gamma: Gamma,
+        primaries: Primaries,
+        white: White,
+    ) -> Self {
+        PixelFormat {
+            model,
+            depth,
+            alpha,
+            gamma,
+            primaries,
+            white,
+        }
+    }
+    /// Get the descriptor of a `Pixel` format.
+    pub fn of<P: Pixel>() -> Self {
+        let alpha = if !P::HAS_ALPHA {
+            Alpha::Opaque
+        } else if TypeId::of::<P::Alpha>() == TypeId::of::<Premultiplied>() {
+            Alpha::Premultiplied
+        } else {
+            Alpha::Straight
+        };
+        PixelFormat {
+            model: P::MODEL,
+            depth: Depth::of::<P::Chan>(),
+            alpha,
+            gamma: P::Gamma::FORMAT,
+            primaries: P::Primaries::FORMAT,
+            white: <P::Primaries as gamut::Primaries>::White::FORMAT,
+        }
+    }
+    /// Get the color model.
+    pub fn model(&self) -> Model {
+        self.model
+    }
+    /// Get the channel depth.
+    pub fn depth(&self) -> Depth {
+        self.depth
+    }
+    /// Get the alpha mode.
+    pub fn alpha(&self) -> Alpha {
+        self.alpha
+    }
+    /// Get the gamma mode.
+    pub fn gamma(&self) -> Gamma {
+        self.gamma
+    }
+    /// Get the RGB primaries.
+    pub fn primaries(&self) -> Primaries {
+        self.primaries
+    }
+    /// Get the white point.
+    pub fn white(&self) -> White {
+        self.white
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn descriptors() {
+        let fmt = PixelFormat::of::<Gray8>();
+        let e = PixelFormat::new(
+            Model::Gray,
+            Depth::Ch8,
+            Alpha::Opaque,
+            Gamma::Linear,
+            Primaries::Srgb,
+            White::D65,
+        );
+        assert_eq!(fmt, e);
+        let fmt = PixelFormat::of::<SRgba32>();
+        assert_eq!(fmt.model(), Model::Rgb);
+        assert_eq!(fmt.depth(), Depth::Ch32);
+        assert_eq!(fmt.alpha(), Alpha::Straight);
+        assert_eq!(fmt.gamma(), Gamma::Srgb);
+        let fmt = PixelFormat::of::<Mask16>();
+        assert_eq!(fmt.model(), Model::Mask);
+        assert_eq!(fmt.alpha(), Alpha::Straight);
+        assert_eq!(PixelFormat::of::<Rgb565>().alpha(), Alpha::Opaque);
+        assert_eq!(PixelFormat::of::<SRgba4444>().alpha(), Alpha::Straight);
+        let fmt = PixelFormat::of::<G22Rgb8>();
+        assert_eq!(fmt.gamma(), Gamma::PowerLaw(11, 5));
+        assert_eq!(PixelFormat::of::<Rgb32h>().depth(), Depth::Ch32h);
+        assert_eq!(PixelFormat::of::<YCbCra8p>().model(), Model::YCbCr);
+        let fmt = PixelFormat::of::<P3Rgb8>();
+        assert_eq!(fmt.primaries(), Primaries::DisplayP3);
+        assert_eq!(fmt.white(), White::D65);
+        let fmt = PixelFormat::of::<Lab8>();
+        assert_eq!(fmt.primaries(), Primaries::CieXyz);
+        assert_eq!(fmt.white(), White::D65);
+        let fmt = PixelFormat::of::<AcesCgRgb32>();
+        assert_eq!(fmt.white(), White::D60);
+    }
+}
@@ -4,21 +4,34 @@
 // Copyright (c) 2019-2020  Jeron Aldaron Lau
 //
 //! Module for gamma encoding items
+use crate::format;
 use crate::private::Sealed;
-use crate::{Ch16, Ch32, Ch8, Channel};
-use std::any::Any;
+use crate::{Ch16, Ch16f, Ch32, Ch32h, Ch64, Ch8, Channel};
+use std::any::{Any, TypeId};
 use std::fmt::Debug;
+use std::sync::OnceLock;
 
 /// Trait for handling gamma mode conversions.
 ///
 /// This trait is *sealed*, and cannot be implemented outside of this crate.
 pub trait Mode:
-    Any + Copy + Clone + Debug + Default + PartialEq + Sealed
+    Any + Copy + Clone + Debug + Default + PartialEq + Send + Sync + Sealed
 {
     /// Convert a `Channel` value to linear.
     fn to_linear<C: Channel>(c: C) -> C;
     /// Convert a `Channel` value from linear.
     fn from_linear<C: Channel>(c: C) -> C;
+    /// Convert an unclamped `f64` value to linear.
+    ///
+    /// Values above 1 are extended, and negative values are mirrored.
+    fn to_linear_f64(v: f64) -> f64;
+    /// Convert an unclamped `f64` value from linear.
+    ///
+    /// Values above 1 are extended, and negative values are mirrored.
+    fn from_linear_f64(v: f64) -> f64;
+
+    /// Runtime descriptor of the gamma mode
+    const FORMAT: format::Gamma;
 }
 
 /// Linear gamma (no gamma correction)
@@ -29,7 +42,90 @@ pub struct Linear;
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct Srgb;
 
-// TODO: add PowerLawGamma when const generics feature is stable
+/// Gamma correction using the Rec. 709 (BT.709) formula
+///
+/// This is the camera transfer function (OETF) used by HD video, which is
+/// also used by BT.2020.  Its inverse is used for decoding.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Rec709;
+
+/// Gamma correction using a pure power law, with an exponent of `N / D`
+///
+/// Common exponents ([Gamma18], [Gamma22], [AdobeRgb] and [Bt1886]) use
+/// look-up tables for 8- and 16-bit channels.  Other exponents are
+/// calculated.
+///
+/// [AdobeRgb]: type.AdobeRgb.html
+/// [Bt1886]: type.Bt1886.html
+/// [Gamma18]: type.Gamma18.html
+/// [Gamma22]: type.Gamma22.html
+///
+/// ```
+/// # use pix::gamma::{Mode, PowerLaw};
+/// # use pix::Ch32;
+/// type Gamma25 = PowerLaw<5, 2>;
+/// let v = Gamma25::to_linear(Ch32::new(0.5));
+/// assert_eq!(v, Ch32::new(0.5_f32.powf(2.5)));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct PowerLaw<const N: u32, const D: u32>;
+
+/// Gamma 1.8 power law, as used by classic Mac OS and ProPhoto RGB
+pub type Gamma18 = PowerLaw<9, 5>;
+
+/// Gamma 2.2 power law
+pub type Gamma22 = PowerLaw<11, 5>;
+
+/// Adobe RGB (1998) power law, with an exponent of 563/256 (about 2.2)
+pub type AdobeRgb = PowerLaw<563, 256>;
+
+/// BT.1886 display transfer function (EOTF) with zero black level, a pure
+/// 2.4 power law
+pub type Bt1886 = PowerLaw<12, 5>;
+
+/// SMPTE ST 2084 perceptual quantizer (PQ), as used by HDR10
+///
+/// Decoding applies the PQ EOTF, with linear values in units of `NITS`
+/// candelas per square meter (cd/m²).  [PqNits] gives absolute luminance,
+/// `Pq<10000>` normalizes the full range to 1 and `Pq<203>` normalizes to
+/// reference white.  Linear values above 1 need an unclamped channel, such
+/// as [Ch32h].
+///
+/// [Ch32h]: ../struct.Ch32h.html
+/// [PqNits]: type.PqNits.html
+///
+/// ```
+/// # use pix::*;
+/// # use pix::alpha::{Opaque, Straight};
+/// type PqRgb16 = Rgb<Ch16, Opaque<Ch16>, Straight, gamma::PqNits>;
+/// let p: Rgb32h = PqRgb16::new(0x8000, 0xFFFF, 0).convert();
+/// assert!((f32::from(p.red()) - 92.25).abs() < 0.01);
+/// assert_eq!(f32::from(p.green()), 10_000.0);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Pq<const NITS: u32>;
+
+/// PQ with linear values in absolute luminance (cd/m²)
+pub type PqNits = Pq<1>;
+
+/// Hybrid Log-Gamma (HLG), as defined in ARIB STD-B67 and BT.2100
+///
+/// Decoding applies the HLG EOTF for a display with `PEAK` cd/m² nominal
+/// peak luminance, with linear values in units of `NITS` cd/m².  [HlgNits]
+/// gives absolute luminance, and `Hlg<1000, 1000>` normalizes to the
+/// display peak.
+///
+/// The system gamma is 1.2 for a 1000 cd/m² display, adjusted for other
+/// peaks as in BT.2100.  Since gamma modes work on one component at a time,
+/// the OOTF is applied to each component rather than to luminance.
+///
+/// [HlgNits]: type.HlgNits.html
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Hlg<const PEAK: u32, const NITS: u32>;
+
+/// HLG for a 1000 cd/m² display, with linear values in absolute luminance
+/// (cd/m²)
+pub type HlgNits = Hlg<1000, 1>;
 
 /// Trait to encode/decode sRGB values.
 ///
@@ -143,6 +239,61 @@ impl SrgbValue for Ch16 {
     }
 }
 
+/// Bits of `f32` square root of 1/2, for splitting exponent and mantissa
+pub(crate) const SQRT_HALF_BITS: i32 = 0x3F35_04F3;
+
+/// Coefficients of `log2((1 + t) / (1 - t))`, in odd powers of `t`
+pub(crate) const LOG2_COEF: [f64; 7] = [
+    2.885_390_081_777_926_8,
+    0.961_796_693_925_975_6,
+    0.577_078_016_355_585_3,
+    0.412_198_583_111_132_4,
+    0.320_598_897_975_325_2,
+    0.262_308_189_252_538_8,
+    0.221_953_083_213_686_67,
+];
+
+/// Coefficients of `2^f`, in powers of `f`
+pub(crate) const EXP2_COEF: [f64; 11] = [
+    1.0,
+    std::f64::consts::LN_2,
+    0.240_226_506_959_100_72,
+    0.055_504_108_664_821_58,
+    0.009_618_129_107_628_477,
+    0.001_333_355_814_642_844_3,
+    0.000_154_035_303_933_816_1,
+    1.525_273_380_405_984_1e-5,
+    1.321_548_679_014_431e-6,
+    1.017_808_600_923_97e-7,
+    7.054_911_620_801_123e-9,
+];
+
+/// Evaluate a polynomial with Horner's method
+fn horner(x: f64, coef: &[f64]) -> f64 {
+    let (last, rest) = coef.split_last().expect("no coefficients");
+    rest.iter().rev().fold(*last, |acc, c| acc * x + c)
+}
+
+/// Raise an `f32` value to a power, for sRGB gamma.
+///
+/// Unlike `powf`, this is computed with plain `f64` arithmetic, so SIMD
+/// kernels can get identical results.
+///
+/// * `v` Value, from 2^-9 to 1.
+/// * `g` Exponent.
+pub(crate) fn srgb_powf(v: f32, g: f64) -> f32 {
+    // Split into exponent and mantissa, from sqrt(1/2) to sqrt(2)
+    let bits = v.to_bits() as i32;
+    let e = (bits - SQRT_HALF_BITS) >> 23;
+    let m = f64::from(f32::from_bits((bits - (e << 23)) as u32));
+    let t = (m - 1.0) / (m + 1.0);
+    let y = (t * horner(t * t, &LOG2_COEF) + f64::from(e)) * g;
+    // Split into integer and fraction, from -1/2 to 1/2
+    let n = (y - 0.5) as i32;
+    let p = horner(y - f64::from(n), &EXP2_COEF) as f32;
+    f32::from_bits((p.to_bits() as i32 + (n << 23)) as u32)
+}
+
 impl SrgbValue for f32 {
     /// Encode an sRGB gamma value from linear intensity
     fn encode_srgb(self) -> Self {
@@ -151,7 +302,7 @@ impl SrgbValue for f32 {
         } else if self < 0.003_130_8 {
             self * 12.92
         } else if self < 1.0 {
-            self.powf(1.0 / 2.4) * 1.055 - 0.055
+            srgb_powf(self, 1.0 / 2.4) * 1.055 - 0.055
         } else {
             1.0
         }
@@ -163,7 +314,7 @@ impl SrgbValue for f32 {
         } else if self < 0.04045 {
             self / 12.92
         } else if self < 1.0 {
-            ((self + 0.055) / 1.055).powf(2.4)
+            srgb_powf((self + 0.055) / 1.055, 2.4)
         } else {
             1.0
         }
@@ -181,6 +332,47 @@ impl SrgbValue for Ch32 {
     }
 }
 
+impl SrgbValue for Ch16f {
+    /// Encode an sRGB gamma value from linear intensity
+    ///
+    /// The curve is extended past 1, and mirrored for negative values.
+    fn encode_srgb(self) -> Self {
+        Ch32h::from(self).encode_srgb().into()
+    }
+    /// Decode an sRGB gamma value into linear intensity
+    ///
+    /// The curve is extended past 1, and mirrored for negative values.
+    fn decode_srgb(self) -> Self {
+        Ch32h::from(self).decode_srgb().into()
+    }
+}
+
+impl SrgbValue for Ch64 {
+    /// Encode an sRGB gamma value from linear intensity
+    fn encode_srgb(self) -> Self {
+        Self::new(f64::from(self).encode_srgb())
+    }
+    /// Decode an sRGB gamma value into linear intensity
+    fn decode_srgb(self) -> Self {
+        Self::new(f64::from(self).decode_srgb())
+    }
+}
+
+impl SrgbValue for Ch32h {
+    /// Encode an sRGB gamma value from linear intensity
+    ///
+    /// The curve is extended past 1, and mirrored for negative values.
+    fn encode_srgb(self) -> Self {
+        Self::new(encode_srgb_extended(f64::from(self)) as f32)
+    }
+    /// Decode an sRGB gamma value into linear intensity
+    ///
+    /// The curve is extended past 1, and mirrored for negative values.
+    fn decode_srgb(self) -> Self {
+        Self::new(decode_srgb_extended(f64::from(self)) as f32)
+    }
+}
+
 impl SrgbValue for f64 {
     /// Encode an sRGB gamma value from linear intensity
     fn encode_srgb(self) -> Self {
@@ -208,7 +400,31 @@ impl SrgbValue for f64 {
     }
 }
 
+/// Encode an sRGB gamma value, extended past 1 and mirrored for negative
+/// values
+fn encode_srgb_extended(v: f64) -> f64 {
+    let s = if v.abs() < 0.003_130_8 {
+        v.abs() * 12.92
+    } else {
+        v.abs().powf(1.0 / 2.4) * 1.055 - 0.055
+    };
+    s.copysign(v)
+}
+
+/// Decode an sRGB gamma value, extended past 1 and mirrored for negative
+/// values
+fn decode_srgb_extended(v: f64) -> f64 {
+    let s = if v.abs() < 0.04045 {
+        v.abs() / 12.92
+    } else {
+        ((v.abs() + 0.055) / 1.055).powf(2.4)
+    };
+    s.copysign(v)
+}
+
 impl Mode for Linear {
+    const FORMAT: format::Gamma = format::Gamma::Linear;
+
     /// Convert a `Channel` value to linear.
     fn to_linear<C: Channel>(c: C) -> C {
         c
@@ -217,9 +433,19 @@ impl Mode for Linear {
     fn from_linear<C: Channel>(c: C) -> C {
         c
     }
+    /// Convert an unclamped `f64` value to linear.
+    fn to_linear_f64(v: f64) -> f64 {
+        v
+    }
+    /// Convert an unclamped `f64` value from linear.
+    fn from_linear_f64(v: f64) -> f64 {
+        v
+    }
 }
 
 impl Mode for Srgb {
+    const FORMAT: format::Gamma = format::Gamma::Srgb;
+
     /// Convert a `Channel` value to linear.
     fn to_linear<C: Channel>(c: C) -> C {
         c.decode_srgb()
@@ -228,6 +454,556 @@ impl Mode for Srgb {
     fn from_linear<C: Channel>(c: C) -> C {
         c.encode_srgb()
     }
+    /// Convert an unclamped `f64` value to linear.
+    fn to_linear_f64(v: f64) -> f64 {
+        decode_srgb_extended(v)
+    }
+    /// Convert an unclamped `f64` value from linear.
+    fn from_linear_f64(v: f64) -> f64 {
+        encode_srgb_extended(v)
+    }
+}
+
+/// Look-up table to encode 8-bit linear to Rec. 709
+const ENCODE_REC709_U8: &[u8] = &[
+    0x00, 0x05, 0x09, 0x0E, 0x12, 0x17, 0x1B, 0x1E, 0x22, 0x25, 0x28, 0x2B,
+    0x2E, 0x30, 0x33, 0x35, 0x37, 0x3A, 0x3C, 0x3E, 0x40, 0x42, 0x44, 0x46,
+    0x48, 0x49, 0x4B, 0x4D, 0x4E, 0x50, 0x52, 0x53, // 32
+    0x55, 0x56, 0x58, 0x59, 0x5B, 0x5C, 0x5E, 0x5F, 0x61, 0x62, 0x63, 0x65,
+    0x66, 0x67, 0x68, 0x6A, 0x6B, 0x6C, 0x6D, 0x6F, 0x70, 0x71, 0x72, 0x73,
+    0x74, 0x76, 0x77, 0x78, 0x79, 0x7A, 0x7B, 0x7C, // 64
+    0x7D, 0x7E, 0x7F, 0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88,
+    0x89, 0x8A, 0x8B, 0x8C, 0x8D, 0x8E, 0x8F, 0x90, 0x91, 0x92, 0x93, 0x93,
+    0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9A, 0x9A, // 96
+    0x9B, 0x9C, 0x9D, 0x9E, 0x9F, 0x9F, 0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA4,
+    0xA5, 0xA6, 0xA7, 0xA8, 0xA8, 0xA9, 0xAA, 0xAB, 0xAB, 0xAC, 0xAD, 0xAE,
+    0xAE, 0xAF, 0xB0, 0xB1, 0xB1, 0xB2, 0xB3, 0xB4, // 128
+    0xB4, 0xB5, 0xB6, 0xB6, 0xB7, 0xB8, 0xB9, 0xB9, 0xBA, 0xBB, 0xBB, 0xBC,
+    0xBD, 0xBD, 0xBE, 0xBF, 0xBF, 0xC0, 0xC1, 0xC1, 0xC2, 0xC3, 0xC3, 0xC4,
+    0xC5, 0xC5, 0xC6, 0xC7, 0xC7, 0xC8, 0xC9, 0xC9, // 160
+    0xCA, 0xCB, 0xCB, 0xCC, 0xCD, 0xCD, 0xCE, 0xCE, 0xCF, 0xD0, 0xD0, 0xD1,
+    0xD1, 0xD2, 0xD3, 0xD3, 0xD4, 0xD5, 0xD5, 0xD6, 0xD6, 0xD7, 0xD8, 0xD8,
+    0xD9, 0xD9, 0xDA, 0xDA, 0xDB, 0xDC, 0xDC, 0xDD, // 192
+    0xDD, 0xDE, 0xDF, 0xDF, 0xE0, 0xE0, 0xE1, 0xE1, 0xE2, 0xE3, 0xE3, 0xE4,
+    0xE4, 0xE5, 0xE5, 0xE6, 0xE6, 0xE7, 0xE8, 0xE8, 0xE9, 0xE9, 0xEA, 0xEA,
+    0xEB, 0xEB, 0xEC, 0xEC, 0xED, 0xEE, 0xEE, 0xEF, // 224
+    0xEF, 0xF0, 0xF0, 0xF1, 0xF1, 0xF2, 0xF2, 0xF3, 0xF3, 0xF4, 0xF4, 0xF5,
+    0xF5, 0xF6, 0xF6, 0xF7, 0xF7, 0xF8, 0xF8, 0xF9, 0xF9, 0xFA, 0xFB, 0xFB,
+    0xFC, 0xFC, 0xFD, 0xFD, 0xFE, 0xFE, 0xFF, 0xFF, // 256
+];
+
+/// Look-up table to decode 8-bit Rec. 709 to linear
+const DECODE_REC709_U8: &[u8] = &[
+    0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x01, 0x02, 0x02, 0x02, 0x02, 0x02,
+    0x03, 0x03, 0x03, 0x03, 0x04, 0x04, 0x04, 0x04, 0x04, 0x05, 0x05, 0x05,
+    0x05, 0x06, 0x06, 0x06, 0x06, 0x07, 0x07, 0x07, // 32
+    0x07, 0x08, 0x08, 0x08, 0x09, 0x09, 0x09, 0x0A, 0x0A, 0x0A, 0x0B, 0x0B,
+    0x0B, 0x0C, 0x0C, 0x0D, 0x0D, 0x0D, 0x0E, 0x0E, 0x0F, 0x0F, 0x0F, 0x10,
+    0x10, 0x11, 0x11, 0x12, 0x12, 0x13, 0x13, 0x14, // 64
+    0x14, 0x15, 0x15, 0x16, 0x16, 0x17, 0x17, 0x18, 0x18, 0x19, 0x19, 0x1A,
+    0x1B, 0x1B, 0x1C, 0x1C, 0x1D, 0x1E, 0x1E, 0x1F, 0x1F, 0x20, 0x21, 0x21,
+    0x22, 0x23, 0x23, 0x24, 0x25, 0x25, 0x26, 0x27, // 96
+    0x28, 0x28, 0x29, 0x2A, 0x2B, 0x2B, 0x2C, 0x2D, 0x2E, 0x2E, 0x2F, 0x30,
+    0x31, 0x32, 0x33, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x39, 0x3A,
+    0x3B, 0x3C, 0x3D, 0x3E, 0x3F, 0x40, 0x41, 0x42, // 128
+    0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E,
+    0x4F, 0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x58, 0x59, 0x5A, 0x5B,
+    0x5C, 0x5D, 0x5E, 0x60, 0x61, 0x62, 0x63, 0x64, // 160
+    0x66, 0x67, 0x68, 0x69, 0x6B, 0x6C, 0x6D, 0x6E, 0x70, 0x71, 0x72, 0x74,
+    0x75, 0x76, 0x77, 0x79, 0x7A, 0x7C, 0x7D, 0x7E, 0x80, 0x81, 0x82, 0x84,
+    0x85, 0x87, 0x88, 0x8A, 0x8B, 0x8C, 0x8E, 0x8F, // 192
+    0x91, 0x92, 0x94, 0x95, 0x97, 0x98, 0x9A, 0x9B, 0x9D, 0x9E, 0xA0, 0xA2,
+    0xA3, 0xA5, 0xA6, 0xA8, 0xAA, 0xAB, 0xAD, 0xAE, 0xB0, 0xB2, 0xB3, 0xB5,
+    0xB7, 0xB8, 0xBA, 0xBC, 0xBE, 0xBF, 0xC1, 0xC3, // 224
+    0xC5, 0xC6, 0xC8, 0xCA, 0xCC, 0xCD, 0xCF, 0xD1, 0xD3, 0xD5, 0xD6, 0xD8,
+    0xDA, 0xDC, 0xDE, 0xE0, 0xE2, 0xE4, 0xE5, 0xE7, 0xE9, 0xEB, 0xED, 0xEF,
+    0xF1, 0xF3, 0xF5, 0xF7, 0xF9, 0xFB, 0xFD, 0xFF, // 256
+];
+
+/// Look-up table to encode 8-bit linear to gamma 1.8
+const ENCODE_GAMMA18_U8: &[u8] = &[
+    0x00, 0x0C, 0x11, 0x16, 0x19, 0x1D, 0x20, 0x23, 0x25, 0x28, 0x2A, 0x2C,
+    0x2F, 0x31, 0x33, 0x35, 0x37, 0x39, 0x3A, 0x3C, 0x3E, 0x40, 0x41, 0x43,
+    0x45, 0x46, 0x48, 0x49, 0x4B, 0x4C, 0x4E, 0x4F, // 32
+    0x50, 0x52, 0x53, 0x55, 0x56, 0x57, 0x59, 0x5A, 0x5B, 0x5C, 0x5E, 0x5F,
+    0x60, 0x61, 0x62, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6B, 0x6C, 0x6D,
+    0x6E, 0x6F, 0x70, 0x71, 0x72, 0x73, 0x74, 0x75, // 64
+    0x76, 0x77, 0x78, 0x79, 0x7A, 0x7B, 0x7C, 0x7D, 0x7E, 0x7F, 0x80, 0x81,
+    0x82, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8A, 0x8B, 0x8B, 0x8C,
+    0x8D, 0x8E, 0x8F, 0x90, 0x91, 0x92, 0x92, 0x93, // 96
+    0x94, 0x95, 0x96, 0x97, 0x98, 0x98, 0x99, 0x9A, 0x9B, 0x9C, 0x9D, 0x9D,
+    0x9E, 0x9F, 0xA0, 0xA1, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5, 0xA5, 0xA6, 0xA7,
+    0xA8, 0xA9, 0xA9, 0xAA, 0xAB, 0xAC, 0xAC, 0xAD, // 128
+    0xAE, 0xAF, 0xAF, 0xB0, 0xB1, 0xB2, 0xB2, 0xB3, 0xB4, 0xB5, 0xB5, 0xB6,
+    0xB7, 0xB7, 0xB8, 0xB9, 0xBA, 0xBA, 0xBB, 0xBC, 0xBC, 0xBD, 0xBE, 0xBF,
+    0xBF, 0xC0, 0xC1, 0xC1, 0xC2, 0xC3, 0xC3, 0xC4, // 160
+    0xC5, 0xC6, 0xC6, 0xC7, 0xC8, 0xC8, 0xC9, 0xCA, 0xCA, 0xCB, 0xCC, 0xCC,
+    0xCD, 0xCE, 0xCE, 0xCF, 0xD0, 0xD0, 0xD1, 0xD1, 0xD2, 0xD3, 0xD3, 0xD4,
+    0xD5, 0xD5, 0xD6, 0xD7, 0xD7, 0xD8, 0xD9, 0xD9, // 192
+    0xDA, 0xDA, 0xDB, 0xDC, 0xDC, 0xDD, 0xDE, 0xDE, 0xDF, 0xDF, 0xE0, 0xE1,
+    0xE1, 0xE2, 0xE2, 0xE3, 0xE4, 0xE4, 0xE5, 0xE6, 0xE6, 0xE7, 0xE7, 0xE8,
+    0xE9, 0xE9, 0xEA, 0xEA, 0xEB, 0xEC, 0xEC, 0xED, // 224
+    0xED, 0xEE, 0xEE, 0xEF, 0xF0, 0xF0, 0xF1, 0xF1, 0xF2, 0xF3, 0xF3, 0xF4,
+    0xF4, 0xF5, 0xF5, 0xF6, 0xF7, 0xF7, 0xF8, 0xF8, 0xF9, 0xF9, 0xFA, 0xFB,
+    0xFB, 0xFC, 0xFC, 0xFD, 0xFD, 0xFE, 0xFE, 0xFF, // 256
+];
+
+/// Look-up table to decode 8-bit gamma 1.8 to linear
+const DECODE_GAMMA18_U8: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x01,
+    0x01, 0x01, 0x01, 0x02, 0x02, 0x02, 0x02, 0x02, 0x03, 0x03, 0x03, 0x03,
+    0x04, 0x04, 0x04, 0x04, 0x05, 0x05, 0x05, 0x06, // 32
+    0x06, 0x06, 0x07, 0x07, 0x08, 0x08, 0x08, 0x09, 0x09, 0x0A, 0x0A, 0x0A,
+    0x0B, 0x0B, 0x0C, 0x0C, 0x0D, 0x0D, 0x0E, 0x0E, 0x0F, 0x0F, 0x10, 0x10,
+    0x11, 0x11, 0x12, 0x12, 0x13, 0x13, 0x14, 0x15, // 64
+    0x15, 0x16, 0x16, 0x17, 0x18, 0x18, 0x19, 0x1A, 0x1A, 0x1B, 0x1C, 0x1C,
+    0x1D, 0x1E, 0x1E, 0x1F, 0x20, 0x20, 0x21, 0x22, 0x23, 0x23, 0x24, 0x25,
+    0x26, 0x26, 0x27, 0x28, 0x29, 0x29, 0x2A, 0x2B, // 96
+    0x2C, 0x2D, 0x2E, 0x2E, 0x2F, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x35,
+    0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B, 0x3C, 0x3D, 0x3E, 0x3F, 0x40, 0x41,
+    0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, // 128
+    0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F, 0x50, 0x51, 0x52, 0x53, 0x54, 0x56,
+    0x57, 0x58, 0x59, 0x5A, 0x5B, 0x5C, 0x5D, 0x5F, 0x60, 0x61, 0x62, 0x63,
+    0x64, 0x66, 0x67, 0x68, 0x69, 0x6B, 0x6C, 0x6D, // 160
+    0x6E, 0x6F, 0x71, 0x72, 0x73, 0x74, 0x76, 0x77, 0x78, 0x7A, 0x7B, 0x7C,
+    0x7E, 0x7F, 0x80, 0x81, 0x83, 0x84, 0x86, 0x87, 0x88, 0x8A, 0x8B, 0x8C,
+    0x8E, 0x8F, 0x91, 0x92, 0x93, 0x95, 0x96, 0x98, // 192
+    0x99, 0x9A, 0x9C, 0x9D, 0x9F, 0xA0, 0xA2, 0xA3, 0xA5, 0xA6, 0xA8, 0xA9,
+    0xAB, 0xAC, 0xAE, 0xAF, 0xB1, 0xB2, 0xB4, 0xB5, 0xB7, 0xB8, 0xBA, 0xBC,
+    0xBD, 0xBF, 0xC0, 0xC2, 0xC3, 0xC5, 0xC7, 0xC8, // 224
+    0xCA, 0xCC, 0xCD, 0xCF, 0xD0, 0xD2, 0xD4, 0xD5, 0xD7, 0xD9, 0xDA, 0xDC,
+    0xDE, 0xE0, 0xE1, 0xE3, 0xE5, 0xE6, 0xE8, 0xEA, 0xEC, 0xED, 0xEF, 0xF1,
+    0xF3, 0xF4, 0xF6, 0xF8, 0xFA, 0xFB, 0xFD, 0xFF, // 256
+];
+
+/// Look-up table to encode 8-bit linear to gamma 2.2
+const ENCODE_GAMMA22_U8: &[u8] = &[
+    0x00, 0x15, 0x1C, 0x22, 0x27, 0x2B, 0x2E, 0x32, 0x35, 0x38, 0x3B, 0x3D,
+    0x40, 0x42, 0x44, 0x46, 0x48, 0x4A, 0x4C, 0x4E, 0x50, 0x52, 0x54, 0x55,
+    0x57, 0x59, 0x5A, 0x5C, 0x5D, 0x5F, 0x60, 0x62, // 32
+    0x63, 0x65, 0x66, 0x67, 0x69, 0x6A, 0x6B, 0x6D, 0x6E, 0x6F, 0x70, 0x72,
+    0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x7A, 0x7B, 0x7C, 0x7D, 0x7E, 0x7F,
+    0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87, // 64
+    0x88, 0x89, 0x8A, 0x8B, 0x8C, 0x8D, 0x8E, 0x8F, 0x90, 0x90, 0x91, 0x92,
+    0x93, 0x94, 0x95, 0x96, 0x97, 0x97, 0x98, 0x99, 0x9A, 0x9B, 0x9C, 0x9C,
+    0x9D, 0x9E, 0x9F, 0xA0, 0xA0, 0xA1, 0xA2, 0xA3, // 96
+    0xA4, 0xA4, 0xA5, 0xA6, 0xA7, 0xA7, 0xA8, 0xA9, 0xAA, 0xAA, 0xAB, 0xAC,
+    0xAD, 0xAD, 0xAE, 0xAF, 0xAF, 0xB0, 0xB1, 0xB2, 0xB2, 0xB3, 0xB4, 0xB4,
+    0xB5, 0xB6, 0xB6, 0xB7, 0xB8, 0xB8, 0xB9, 0xBA, // 128
+    0xBA, 0xBB, 0xBC, 0xBC, 0xBD, 0xBE, 0xBE, 0xBF, 0xC0, 0xC0, 0xC1, 0xC2,
+    0xC2, 0xC3, 0xC3, 0xC4, 0xC5, 0xC5, 0xC6, 0xC7, 0xC7, 0xC8, 0xC8, 0xC9,
+    0xCA, 0xCA, 0xCB, 0xCB, 0xCC, 0xCD, 0xCD, 0xCE, // 160
+    0xCE, 0xCF, 0xCF, 0xD0, 0xD1, 0xD1, 0xD2, 0xD2, 0xD3, 0xD4, 0xD4, 0xD5,
+    0xD5, 0xD6, 0xD6, 0xD7, 0xD7, 0xD8, 0xD9, 0xD9, 0xDA, 0xDA, 0xDB, 0xDB,
+    0xDC, 0xDC, 0xDD, 0xDD, 0xDE, 0xDF, 0xDF, 0xE0, // 192
+    0xE0, 0xE1, 0xE1, 0xE2, 0xE2, 0xE3, 0xE3, 0xE4, 0xE4, 0xE5, 0xE5, 0xE6,
+    0xE6, 0xE7, 0xE7, 0xE8, 0xE8, 0xE9, 0xE9, 0xEA, 0xEA, 0xEB, 0xEB, 0xEC,
+    0xEC, 0xED, 0xED, 0xEE, 0xEE, 0xEF, 0xEF, 0xF0, // 224
+    0xF0, 0xF1, 0xF1, 0xF2, 0xF2, 0xF3, 0xF3, 0xF4, 0xF4, 0xF5, 0xF5, 0xF6,
+    0xF6, 0xF7, 0xF7, 0xF8, 0xF8, 0xF9, 0xF9, 0xF9, 0xFA, 0xFA, 0xFB, 0xFB,
+    0xFC, 0xFC, 0xFD, 0xFD, 0xFE, 0xFE, 0xFF, 0xFF, // 256
+];
+
+/// Look-up table to decode 8-bit gamma 2.2 to linear
+const DECODE_GAMMA22_U8: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+    0x01, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, // 32
+    0x03, 0x03, 0x03, 0x03, 0x03, 0x04, 0x04, 0x04, 0x04, 0x05, 0x05, 0x05,
+    0x05, 0x06, 0x06, 0x06, 0x06, 0x07, 0x07, 0x07, 0x08, 0x08, 0x08, 0x09,
+    0x09, 0x09, 0x0A, 0x0A, 0x0B, 0x0B, 0x0B, 0x0C, // 64
+    0x0C, 0x0D, 0x0D, 0x0D, 0x0E, 0x0E, 0x0F, 0x0F, 0x10, 0x10, 0x11, 0x11,
+    0x12, 0x12, 0x13, 0x13, 0x14, 0x14, 0x15, 0x16, 0x16, 0x17, 0x17, 0x18,
+    0x19, 0x19, 0x1A, 0x1A, 0x1B, 0x1C, 0x1C, 0x1D, // 96
+    0x1E, 0x1E, 0x1F, 0x20, 0x21, 0x21, 0x22, 0x23, 0x23, 0x24, 0x25, 0x26,
+    0x27, 0x27, 0x28, 0x29, 0x2A, 0x2B, 0x2B, 0x2C, 0x2D, 0x2E, 0x2F, 0x30,
+    0x31, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, // 128
+    0x38, 0x39, 0x3A, 0x3B, 0x3C, 0x3D, 0x3E, 0x3F, 0x40, 0x41, 0x42, 0x43,
+    0x44, 0x45, 0x46, 0x47, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F, 0x51,
+    0x52, 0x53, 0x54, 0x55, 0x57, 0x58, 0x59, 0x5A, // 160
+    0x5B, 0x5D, 0x5E, 0x5F, 0x61, 0x62, 0x63, 0x64, 0x66, 0x67, 0x69, 0x6A,
+    0x6B, 0x6D, 0x6E, 0x6F, 0x71, 0x72, 0x74, 0x75, 0x77, 0x78, 0x79, 0x7B,
+    0x7C, 0x7E, 0x7F, 0x81, 0x82, 0x84, 0x85, 0x87, // 192
+    0x89, 0x8A, 0x8C, 0x8D, 0x8F, 0x91, 0x92, 0x94, 0x95, 0x97, 0x99, 0x9A,
+    0x9C, 0x9E, 0x9F, 0xA1, 0xA3, 0xA5, 0xA6, 0xA8, 0xAA, 0xAC, 0xAD, 0xAF,
+    0xB1, 0xB3, 0xB5, 0xB6, 0xB8, 0xBA, 0xBC, 0xBE, // 224
+    0xC0, 0xC2, 0xC4, 0xC5, 0xC7, 0xC9, 0xCB, 0xCD, 0xCF, 0xD1, 0xD3, 0xD5,
+    0xD7, 0xD9, 0xDB, 0xDD, 0xDF, 0xE1, 0xE3, 0xE5, 0xE7, 0xEA, 0xEC, 0xEE,
+    0xF0, 0xF2, 0xF4, 0xF6, 0xF8, 0xFB, 0xFD, 0xFF, // 256
+];
+
+/// Look-up table to encode 8-bit linear to Adobe RGB gamma
+const ENCODE_ADOBE_U8: &[u8] = &[
+    0x00, 0x15, 0x1C, 0x22, 0x27, 0x2B, 0x2E, 0x32, 0x35, 0x38, 0x3A, 0x3D,
+    0x40, 0x42, 0x44, 0x46, 0x48, 0x4A, 0x4C, 0x4E, 0x50, 0x52, 0x54, 0x55,
+    0x57, 0x59, 0x5A, 0x5C, 0x5D, 0x5F, 0x60, 0x62, // 32
+    0x63, 0x65, 0x66, 0x67, 0x69, 0x6A, 0x6B, 0x6D, 0x6E, 0x6F, 0x70, 0x72,
+    0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x7A, 0x7B, 0x7C, 0x7D, 0x7E, 0x7F,
+    0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87, // 64
+    0x88, 0x89, 0x8A, 0x8B, 0x8C, 0x8D, 0x8E, 0x8F, 0x8F, 0x90, 0x91, 0x92,
+    0x93, 0x94, 0x95, 0x96, 0x97, 0x97, 0x98, 0x99, 0x9A, 0x9B, 0x9C, 0x9C,
+    0x9D, 0x9E, 0x9F, 0xA0, 0xA0, 0xA1, 0xA2, 0xA3, // 96
+    0xA4, 0xA4, 0xA5, 0xA6, 0xA7, 0xA7, 0xA8, 0xA9, 0xAA, 0xAA, 0xAB, 0xAC,
+    0xAD, 0xAD, 0xAE, 0xAF, 0xAF, 0xB0, 0xB1, 0xB2, 0xB2, 0xB3, 0xB4, 0xB4,
+    0xB5, 0xB6, 0xB6, 0xB7, 0xB8, 0xB8, 0xB9, 0xBA, // 128
+    0xBA, 0xBB, 0xBC, 0xBC, 0xBD, 0xBE, 0xBE, 0xBF, 0xC0, 0xC0, 0xC1, 0xC2,
+    0xC2, 0xC3, 0xC3, 0xC4, 0xC5, 0xC5, 0xC6, 0xC7, 0xC7, 0xC8, 0xC8, 0xC9,
+    0xCA, 0xCA, 0xCB, 0xCB, 0xCC, 0xCD, 0xCD, 0xCE, // 160
+    0xCE, 0xCF, 0xCF, 0xD0, 0xD1, 0xD1, 0xD2, 0xD2, 0xD3, 0xD3, 0xD4, 0xD5,
+    0xD5, 0xD6, 0xD6, 0xD7, 0xD7, 0xD8, 0xD9, 0xD9, 0xDA, 0xDA, 0xDB, 0xDB,
+    0xDC, 0xDC, 0xDD, 0xDD, 0xDE, 0xDF, 0xDF, 0xE0, // 192
+    0xE0, 0xE1, 0xE1, 0xE2, 0xE2, 0xE3, 0xE3, 0xE4, 0xE4, 0xE5, 0xE5, 0xE6,
+    0xE6, 0xE7, 0xE7, 0xE8, 0xE8, 0xE9, 0xE9, 0xEA, 0xEA, 0xEB, 0xEB, 0xEC,
+    0xEC, 0xED, 0xED, 0xEE, 0xEE, 0xEF, 0xEF, 0xF0, // 224
+    0xF0, 0xF1, 0xF1, 0xF2, 0xF2, 0xF3, 0xF3, 0xF4, 0xF4, 0xF5, 0xF5, 0xF6,
+    0xF6, 0xF7, 0xF7, 0xF8, 0xF8, 0xF9, 0xF9, 0xF9, 0xFA, 0xFA, 0xFB, 0xFB,
+    0xFC, 0xFC, 0xFD, 0xFD, 0xFE, 0xFE, 0xFF, 0xFF, // 256
+];
+
+/// Look-up table to decode 8-bit Adobe RGB gamma to linear
+const DECODE_ADOBE_U8: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+    0x01, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, // 32
+    0x03, 0x03, 0x03, 0x03, 0x03, 0x04, 0x04, 0x04, 0x04, 0x05, 0x05, 0x05,
+    0x05, 0x06, 0x06, 0x06, 0x06, 0x07, 0x07, 0x07, 0x08, 0x08, 0x08, 0x09,
+    0x09, 0x09, 0x0A, 0x0A, 0x0B, 0x0B, 0x0B, 0x0C, // 64
+    0x0C, 0x0D, 0x0D, 0x0D, 0x0E, 0x0E, 0x0F, 0x0F, 0x10, 0x10, 0x11, 0x11,
+    0x12, 0x12, 0x13, 0x13, 0x14, 0x14, 0x15, 0x16, 0x16, 0x17, 0x17, 0x18,
+    0x19, 0x19, 0x1A, 0x1A, 0x1B, 0x1C, 0x1C, 0x1D, // 96
+    0x1E, 0x1E, 0x1F, 0x20, 0x21, 0x21, 0x22, 0x23, 0x23, 0x24, 0x25, 0x26,
+    0x27, 0x27, 0x28, 0x29, 0x2A, 0x2B, 0x2B, 0x2C, 0x2D, 0x2E, 0x2F, 0x30,
+    0x31, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, // 128
+    0x38, 0x39, 0x3A, 0x3B, 0x3C, 0x3D, 0x3E, 0x3F, 0x40, 0x41, 0x42, 0x43,
+    0x44, 0x45, 0x46, 0x47, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F, 0x51,
+    0x52, 0x53, 0x54, 0x55, 0x57, 0x58, 0x59, 0x5A, // 160
+    0x5B, 0x5D, 0x5E, 0x5F, 0x61, 0x62, 0x63, 0x65, 0x66, 0x67, 0x69, 0x6A,
+    0x6B, 0x6D, 0x6E, 0x6F, 0x71, 0x72, 0x74, 0x75, 0x77, 0x78, 0x79, 0x7B,
+    0x7C, 0x7E, 0x7F, 0x81, 0x82, 0x84, 0x86, 0x87, // 192
+    0x89, 0x8A, 0x8C, 0x8D, 0x8F, 0x91, 0x92, 0x94, 0x95, 0x97, 0x99, 0x9A,
+    0x9C, 0x9E, 0x9F, 0xA1, 0xA3, 0xA5, 0xA6, 0xA8, 0xAA, 0xAC, 0xAD, 0xAF,
+    0xB1, 0xB3, 0xB5, 0xB6, 0xB8, 0xBA, 0xBC, 0xBE, // 224
+    0xC0, 0xC2, 0xC4, 0xC5, 0xC7, 0xC9, 0xCB, 0xCD, 0xCF, 0xD1, 0xD3, 0xD5,
+    0xD7, 0xD9, 0xDB, 0xDD, 0xDF, 0xE1, 0xE3, 0xE5, 0xE7, 0xEA, 0xEC, 0xEE,
+    0xF0, 0xF2, 0xF4, 0xF6, 0xF8, 0xFB, 0xFD, 0xFF, // 256
+];
+
+/// Look-up table to encode 8-bit linear to BT.1886 gamma
+const ENCODE_BT1886_U8: &[u8] = &[
+    0x00, 0x19, 0x22, 0x28, 0x2D, 0x32, 0x35, 0x39, 0x3C, 0x3F, 0x42, 0x45,
+    0x47, 0x4A, 0x4C, 0x4E, 0x50, 0x53, 0x54, 0x56, 0x58, 0x5A, 0x5C, 0x5E,
+    0x5F, 0x61, 0x62, 0x64, 0x66, 0x67, 0x69, 0x6A, // 32
+    0x6B, 0x6D, 0x6E, 0x6F, 0x71, 0x72, 0x73, 0x75, 0x76, 0x77, 0x78, 0x79,
+    0x7B, 0x7C, 0x7D, 0x7E, 0x7F, 0x80, 0x81, 0x82, 0x83, 0x85, 0x86, 0x87,
+    0x88, 0x89, 0x8A, 0x8B, 0x8C, 0x8D, 0x8D, 0x8E, // 64
+    0x8F, 0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x97, 0x98, 0x99,
+    0x9A, 0x9B, 0x9C, 0x9C, 0x9D, 0x9E, 0x9F, 0xA0, 0xA1, 0xA1, 0xA2, 0xA3,
+    0xA4, 0xA4, 0xA5, 0xA6, 0xA7, 0xA8, 0xA8, 0xA9, // 96
+    0xAA, 0xAA, 0xAB, 0xAC, 0xAD, 0xAD, 0xAE, 0xAF, 0xAF, 0xB0, 0xB1, 0xB2,
+    0xB2, 0xB3, 0xB4, 0xB4, 0xB5, 0xB6, 0xB6, 0xB7, 0xB8, 0xB8, 0xB9, 0xBA,
+    0xBA, 0xBB, 0xBC, 0xBC, 0xBD, 0xBD, 0xBE, 0xBF, // 128
+    0xBF, 0xC0, 0xC1, 0xC1, 0xC2, 0xC2, 0xC3, 0xC4, 0xC4, 0xC5, 0xC5, 0xC6,
+    0xC7, 0xC7, 0xC8, 0xC8, 0xC9, 0xCA, 0xCA, 0xCB, 0xCB, 0xCC, 0xCC, 0xCD,
+    0xCE, 0xCE, 0xCF, 0xCF, 0xD0, 0xD0, 0xD1, 0xD1, // 160
+    0xD2, 0xD3, 0xD3, 0xD4, 0xD4, 0xD5, 0xD5, 0xD6, 0xD6, 0xD7, 0xD7, 0xD8,
+    0xD8, 0xD9, 0xD9, 0xDA, 0xDA, 0xDB, 0xDC, 0xDC, 0xDD, 0xDD, 0xDE, 0xDE,
+    0xDF, 0xDF, 0xE0, 0xE0, 0xE1, 0xE1, 0xE2, 0xE2, // 192
+    0xE3, 0xE3, 0xE4, 0xE4, 0xE5, 0xE5, 0xE5, 0xE6, 0xE6, 0xE7, 0xE7, 0xE8,
+    0xE8, 0xE9, 0xE9, 0xEA, 0xEA, 0xEB, 0xEB, 0xEC, 0xEC, 0xED, 0xED, 0xEE,
+    0xEE, 0xEE, 0xEF, 0xEF, 0xF0, 0xF0, 0xF1, 0xF1, // 224
+    0xF2, 0xF2, 0xF2, 0xF3, 0xF3, 0xF4, 0xF4, 0xF5, 0xF5, 0xF6, 0xF6, 0xF6,
+    0xF7, 0xF7, 0xF8, 0xF8, 0xF9, 0xF9, 0xFA, 0xFA, 0xFA, 0xFB, 0xFB, 0xFC,
+    0xFC, 0xFC, 0xFD, 0xFD, 0xFE, 0xFE, 0xFF, 0xFF, // 256
+];
+
+/// Look-up table to decode 8-bit BT.1886 gamma to linear
+const DECODE_BT1886_U8: &[u8] = &[
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01,
+    0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x02, // 32
+    0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x03, 0x03, 0x03, 0x03, 0x03, 0x04,
+    0x04, 0x04, 0x04, 0x04, 0x05, 0x05, 0x05, 0x05, 0x06, 0x06, 0x06, 0x06,
+    0x07, 0x07, 0x07, 0x08, 0x08, 0x08, 0x09, 0x09, // 64
+    0x09, 0x0A, 0x0A, 0x0A, 0x0B, 0x0B, 0x0B, 0x0C, 0x0C, 0x0D, 0x0D, 0x0E,
+    0x0E, 0x0E, 0x0F, 0x0F, 0x10, 0x10, 0x11, 0x11, 0x12, 0x12, 0x13, 0x13,
+    0x14, 0x14, 0x15, 0x16, 0x16, 0x17, 0x17, 0x18, // 96
+    0x18, 0x19, 0x1A, 0x1A, 0x1B, 0x1C, 0x1C, 0x1D, 0x1E, 0x1E, 0x1F, 0x20,
+    0x20, 0x21, 0x22, 0x23, 0x23, 0x24, 0x25, 0x26, 0x27, 0x27, 0x28, 0x29,
+    0x2A, 0x2B, 0x2B, 0x2C, 0x2D, 0x2E, 0x2F, 0x30, // 128
+    0x31, 0x32, 0x33, 0x34, 0x35, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B,
+    0x3C, 0x3E, 0x3F, 0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x49,
+    0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x50, 0x51, 0x52, // 160
+    0x53, 0x55, 0x56, 0x57, 0x58, 0x5A, 0x5B, 0x5C, 0x5E, 0x5F, 0x60, 0x62,
+    0x63, 0x64, 0x66, 0x67, 0x69, 0x6A, 0x6C, 0x6D, 0x6F, 0x70, 0x72, 0x73,
+    0x75, 0x76, 0x78, 0x79, 0x7B, 0x7C, 0x7E, 0x7F, // 192
+    0x81, 0x83, 0x84, 0x86, 0x88, 0x89, 0x8B, 0x8D, 0x8E, 0x90, 0x92, 0x94,
+    0x95, 0x97, 0x99, 0x9B, 0x9C, 0x9E, 0xA0, 0xA2, 0xA4, 0xA6, 0xA7, 0xA9,
+    0xAB, 0xAD, 0xAF, 0xB1, 0xB3, 0xB5, 0xB7, 0xB9, // 224
+    0xBB, 0xBD, 0xBF, 0xC1, 0xC3, 0xC5, 0xC7, 0xC9, 0xCB, 0xCD, 0xCF, 0xD2,
+    0xD4, 0xD6, 0xD8, 0xDA, 0xDC, 0xDF, 0xE1, 0xE3, 0xE5, 0xE8, 0xEA, 0xEC,
+    0xEF, 0xF1, 0xF3, 0xF6, 0xF8, 0xFA, 0xFD, 0xFF, // 256
+];
+
+/// Transfer function with look-up tables for 8- and 16-bit channels
+struct Lut {
+    /// Transfer function, for values from 0 to 1
+    func: fn(f64) -> f64,
+    /// 8-bit look-up table
+    table_u8: &'static [u8],
+    /// 16-bit look-up table, built on first use
+    table_u16: OnceLock<Box<[u16]>>,
+}
+
+impl Lut {
+    /// Create a new transfer function look-up table
+    const fn new(func: fn(f64) -> f64, table_u8: &'static [u8]) -> Self {
+        Lut {
+            func,
+            table_u8,
+            table_u16: OnceLock::new(),
+        }
+    }
+
+    /// Get the 16-bit look-up table
+    fn table_u16(&self) -> &[u16] {
+        self.table_u16.get_or_init(|| {
+            (0..=u16::MAX)
+                .map(|i| {
+                    let v = (self.func)(f64::from(i) / 65535.0);
+                    (v * 65535.0).round() as u16
+                })
+                .collect()
+        })
+    }
+
+    /// Look up a `Ch8` or `Ch16` value
+    fn lookup<C: Channel>(&self, c: C) -> Option<C> {
+        let v: f64 = c.into();
+        let t = TypeId::of::<C>();
+        if t == TypeId::of::<Ch8>() {
+            let i = usize::from(u8::from(Ch8::from(v)));
+            let v = f64::from(Ch8::from(self.table_u8[i]));
+            Some(<C as From<f64>>::from(v))
+        } else if t == TypeId::of::<Ch16>() {
+            let i = usize::from(u16::from(Ch16::from(v)));
+            let v = f64::from(Ch16::from(self.table_u16()[i]));
+            Some(<C as From<f64>>::from(v))
+        } else {
+            None
+        }
+    }
+
+    /// Apply the transfer function to a `Channel` value.
+    ///
+    /// The function is mirrored for negative values.
+    fn apply<C: Channel>(&self, c: C) -> C {
+        self.lookup(c)
+            .unwrap_or_else(|| <C as From<f64>>::from(self.apply_f64(c.into())))
+    }
+
+    /// Apply the transfer function to an unclamped `f64` value.
+    ///
+    /// The function is mirrored for negative values.
+    fn apply_f64(&self, v: f64) -> f64 {
+        (self.func)(v.abs()).copysign(v)
+    }
+}
+
+/// Encode a Rec. 709 gamma value from linear intensity
+fn encode_rec709(v: f64) -> f64 {
+    if v < 0.018 {
+        v * 4.5
+    } else {
+        v.powf(0.45) * 1.099 - 0.099
+    }
+}
+
+/// Decode a Rec. 709 gamma value into linear intensity
+fn decode_rec709(v: f64) -> f64 {
+    if v < 0.081 {
+        v / 4.5
+    } else {
+        ((v + 0.099) / 1.099).powf(1.0 / 0.45)
+    }
+}
+
+static ENCODE_REC709: Lut = Lut::new(encode_rec709, ENCODE_REC709_U8);
+static DECODE_REC709: Lut = Lut::new(decode_rec709, DECODE_REC709_U8);
+static ENCODE_GAMMA18: Lut = Lut::new(|v| v.powf(5.0 / 9.0), ENCODE_GAMMA18_U8);
+static DECODE_GAMMA18: Lut = Lut::new(|v| v.powf(1.8), DECODE_GAMMA18_U8);
+static ENCODE_GAMMA22: Lut =
+    Lut::new(|v| v.powf(5.0 / 11.0), ENCODE_GAMMA22_U8);
+static DECODE_GAMMA22: Lut = Lut::new(|v| v.powf(2.2), DECODE_GAMMA22_U8);
+static ENCODE_ADOBE: Lut = Lut::new(|v| v.powf(256.0 / 563.0), ENCODE_ADOBE_U8);
+static DECODE_ADOBE: Lut = Lut::new(|v| v.powf(563.0 / 256.0), DECODE_ADOBE_U8);
+static ENCODE_BT1886: Lut = Lut::new(|v| v.powf(5.0 / 12.0), ENCODE_BT1886_U8);
+static DECODE_BT1886: Lut = Lut::new(|v| v.powf(2.4), DECODE_BT1886_U8);
+
+impl Mode for Rec709 {
+    const FORMAT: format::Gamma = format::Gamma::Rec709;
+
+    /// Convert a `Channel` value to linear.
+    fn to_linear<C: Channel>(c: C) -> C {
+        DECODE_REC709.apply(c)
+    }
+    /// Convert a `Channel` value from linear.
+    fn from_linear<C: Channel>(c: C) -> C {
+        ENCODE_REC709.apply(c)
+    }
+    /// Convert an unclamped `f64` value to linear.
+    fn to_linear_f64(v: f64) -> f64 {
+        DECODE_REC709.apply_f64(v)
+    }
+    /// Convert an unclamped `f64` value from linear.
+    fn from_linear_f64(v: f64) -> f64 {
+        ENCODE_REC709.apply_f64(v)
+    }
+}
+
+impl<const N: u32, const D: u32> PowerLaw<N, D> {
+    /// Get encode and decode look-up tables for common exponents
+    fn luts() -> Option<(&'static Lut, &'static Lut)> {
+        match (N, D) {
+            (9, 5) => Some((&ENCODE_GAMMA18, &DECODE_GAMMA18)),
+            (11, 5) => Some((&ENCODE_GAMMA22, &DECODE_GAMMA22)),
+            (563, 256) => Some((&ENCODE_ADOBE, &DECODE_ADOBE)),
+            (12, 5) => Some((&ENCODE_BT1886, &DECODE_BT1886)),
+            _ => None,
+        }
+    }
+}
+
+impl<const N: u32, const D: u32> Mode for PowerLaw<N, D> {
+    const FORMAT: format::Gamma = format::Gamma::PowerLaw(N, D);
+
+    /// Convert a `Channel` value to linear.
+    fn to_linear<C: Channel>(c: C) -> C {
+        Self::luts()
+            .and_then(|(_, decode)| decode.lookup(c))
+            .unwrap_or_else(|| {
+                <C as From<f64>>::from(Self::to_linear_f64(c.into()))
+            })
+    }
+    /// Convert a `Channel` value from linear.
+    fn from_linear<C: Channel>(c: C) -> C {
+        Self::luts()
+            .and_then(|(encode, _)| encode.lookup(c))
+            .unwrap_or_else(|| {
+                <C as From<f64>>::from(Self::from_linear_f64(c.into()))
+            })
+    }
+    /// Convert an unclamped `f64` value to linear.
+    fn to_linear_f64(v: f64) -> f64 {
+        let g = f64::from(N) / f64::from(D);
+        v.abs().powf(g).copysign(v)
+    }
+    /// Convert an unclamped `f64` value from linear.
+    fn from_linear_f64(v: f64) -> f64 {
+        let g = f64::from(D) / f64::from(N);
+        v.abs().powf(g).copysign(v)
+    }
+}
+
+/// PQ constant *m1*
+const PQ_M1: f64 = 2610.0 / 16384.0;
+/// PQ constant *m2*
+const PQ_M2: f64 = 2523.0 / 4096.0 * 128.0;
+/// PQ constant *c1*
+const PQ_C1: f64 = 3424.0 / 4096.0;
+/// PQ constant *c2*
+const PQ_C2: f64 = 2413.0 / 4096.0 * 32.0;
+/// PQ constant *c3*
+const PQ_C3: f64 = 2392.0 / 4096.0 * 32.0;
+
+/// Encode luminance (cd/m²) into a PQ value
+fn encode_pq(nits: f64) -> f64 {
+    let y = (nits / 10_000.0).clamp(0.0, 1.0).powf(PQ_M1);
+    ((PQ_C1 + PQ_C2 * y) / (1.0 + PQ_C3 * y)).powf(PQ_M2)
+}
+
+/// Decode a PQ value into luminance (cd/m²)
+fn decode_pq(v: f64) -> f64 {
+    let p = v.clamp(0.0, 1.0).powf(1.0 / PQ_M2);
+    let y = ((p - PQ_C1).max(0.0) / (PQ_C2 - PQ_C3 * p)).powf(1.0 / PQ_M1);
+    y * 10_000.0
+}
+
+impl<const NITS: u32> Mode for Pq<NITS> {
+    const FORMAT: format::Gamma = format::Gamma::Pq(NITS);
+
+    /// Convert a `Channel` value to linear.
+    fn to_linear<C: Channel>(c: C) -> C {
+        <C as From<f64>>::from(Self::to_linear_f64(c.into()))
+    }
+    /// Convert a `Channel` value from linear.
+    fn from_linear<C: Channel>(c: C) -> C {
+        <C as From<f64>>::from(Self::from_linear_f64(c.into()))
+    }
+    /// Convert an unclamped `f64` value to linear.
+    fn to_linear_f64(v: f64) -> f64 {
+        decode_pq(v) / f64::from(NITS)
+    }
+    /// Convert an unclamped `f64` value from linear.
+    fn from_linear_f64(v: f64) -> f64 {
+        encode_pq(v * f64::from(NITS))
+    }
+}
+
+/// HLG constant *a*
+const HLG_A: f64 = 0.178_832_77;
+/// HLG constant *b*
+const HLG_B: f64 = 0.284_668_92;
+/// HLG constant *c*
+const HLG_C: f64 = 0.559_910_73;
+
+/// Encode relative scene light with the HLG OETF
+fn encode_hlg(e: f64) -> f64 {
+    let e = e.max(0.0);
+    if e <= 1.0 / 12.0 {
+        (3.0 * e).sqrt()
+    } else {
+        HLG_A * (12.0 * e - HLG_B).ln() + HLG_C
+    }
+}
+
+/// Decode an HLG value into relative scene light (inverse OETF)
+fn decode_hlg(v: f64) -> f64 {
+    let v = v.max(0.0);
+    if v <= 0.5 {
+        v * v / 3.0
+    } else {
+        (((v - HLG_C) / HLG_A).exp() + HLG_B) / 12.0
+    }
+}
+
+/// Get the HLG system gamma for a display peak luminance (cd/m²)
+fn hlg_gamma(peak: f64) -> f64 {
+    1.2 + 0.42 * (peak / 1000.0).log10()
+}
+
+impl<const PEAK: u32, const NITS: u32> Mode for Hlg<PEAK, NITS> {
+    const FORMAT: format::Gamma = format::Gamma::Hlg(PEAK, NITS);
+
+    /// Convert a `Channel` value to linear.
+    fn to_linear<C: Channel>(c: C) -> C {
+        <C as From<f64>>::from(Self::to_linear_f64(c.into()))
+    }
+    /// Convert a `Channel` value from linear.
+    fn from_linear<C: Channel>(c: C) -> C {
+        <C as From<f64>>::from(Self::from_linear_f64(c.into()))
+    }
+    /// Convert an unclamped `f64` value to linear.
+    fn to_linear_f64(v: f64) -> f64 {
+        let peak = f64::from(PEAK);
+        let e = decode_hlg(v);
+        peak * e.powf(hlg_gamma(peak)) / f64::from(NITS)
+    }
+    /// Convert an unclamped `f64` value from linear.
+    fn from_linear_f64(v: f64) -> f64 {
+        let peak = f64::from(PEAK);
+        let d = (v * f64::from(NITS) / peak).max(0.0);
+        encode_hlg(d.powf(1.0 / hlg_gamma(peak)))
+    }
 }
 
 #[cfg(test)]
@@ -235,18 +1011,133 @@ mod test {
     use super::*;
     #[test]
     fn lut_decode_u8() {
-        for i in 0..256 {
+        for (i, d) in DECODE_SRGB_U8.iter().enumerate() {
             let s = i as f64 / 255.0;
             let v = (s.decode_srgb() * 255.0).round() as u8;
-            assert_eq!(v, DECODE_SRGB_U8[i]);
+            assert_eq!(v, *d);
         }
     }
     #[test]
     fn lut_encode_u8() {
-        for i in 0..256 {
+        for (i, e) in ENCODE_SRGB_U8.iter().enumerate() {
             let s = i as f64 / 255.0;
             let v = (s.encode_srgb() * 255.0).round() as u8;
-            assert_eq!(v, ENCODE_SRGB_U8[i]);
+            assert_eq!(v, *e);
         }
     }
+    #[test]
+    fn lut_transfer_u8() {
+        let luts = [
+            &ENCODE_REC709,
+            &DECODE_REC709,
+            &ENCODE_GAMMA18,
+            &DECODE_GAMMA18,
+            &ENCODE_GAMMA22,
+            &DECODE_GAMMA22,
+            &ENCODE_ADOBE,
+            &DECODE_ADOBE,
+            &ENCODE_BT1886,
+            &DECODE_BT1886,
+        ];
+        for lut in luts.iter() {
+            assert_eq!(lut.table_u8.len(), 256);
+            for (i, e) in lut.table_u8.iter().enumerate() {
+                let s = i as f64 / 255.0;
+                let v = ((lut.func)(s) * 255.0).round() as u8;
+                assert_eq!(v, *e);
+            }
+        }
+    }
+    #[test]
+    fn lut_transfer_u16() {
+        let v = Rec709::from_linear(Ch16::new(0x8000));
+        assert_eq!(v, Ch16::new(0xB49C));
+        assert_eq!(Rec709::to_linear(v), Ch16::new(0x8000));
+        let v = Gamma22::to_linear(Ch16::new(0x8000));
+        assert_eq!(v, Ch16::new(0x37B7));
+        assert_eq!(Gamma22::from_linear(v), Ch16::new(0x8000));
+    }
+    #[test]
+    fn power_law() {
+        let v = Gamma18::to_linear(Ch32::new(0.5));
+        assert!((f32::from(v) - 0.287_175).abs() < 1e-5);
+        let v = AdobeRgb::from_linear(Ch8::new(0x80));
+        assert_eq!(v, Ch8::new(0xBA));
+        let v = PowerLaw::<5, 2>::from_linear(Ch8::new(0x80));
+        assert_eq!(v, Ch8::new(0xC2));
+        let v = Bt1886::to_linear(Ch32h::new(-0.5));
+        assert!((f32::from(v) + 0.189_465).abs() < 1e-5);
+    }
+    #[test]
+    fn rec709_extended() {
+        let v = Rec709::from_linear(Ch32h::new(2.0));
+        assert!((f32::from(v) - 1.402_278).abs() < 1e-5);
+        assert!((f32::from(Rec709::to_linear(v)) - 2.0).abs() < 1e-5);
+        let v = Rec709::from_linear(Ch32h::new(-0.01));
+        assert!((f32::from(v) + 0.045).abs() < 1e-6);
+    }
+    #[test]
+    fn ch64_precision() {
+        let v = Rec709::to_linear(Ch64::new(0.5));
+        assert_eq!(f64::from(v), decode_rec709(0.5));
+        let v = Bt1886::from_linear(Ch64::new(0.5));
+        assert_eq!(f64::from(v), 0.5_f64.powf(5.0 / 12.0));
+        let v = PqNits::from_linear(Ch64::new(0.01));
+        assert_eq!(f64::from(v), encode_pq(0.01));
+    }
+    #[test]
+    fn pq() {
+        let v = PqNits::to_linear(Ch32h::new(0.5));
+        assert!((f32::from(v) - 92.245_71).abs() < 1e-3);
+        let v = PqNits::from_linear(Ch32h::new(100.0));
+        assert!((f32::from(v) - 0.508_078).abs() < 1e-5);
+        let v = Pq::<10_000>::to_linear(Ch16::new(0xFFFF));
+        assert_eq!(v, Ch16::new(0xFFFF));
+        let v = Pq::<203>::from_linear(Ch32h::new(1.0));
+        assert!((f32::from(v) - 0.580_689).abs() < 1e-5);
+        for i in 0..=100 {
+            let v = Ch32h::new(i as f32 / 100.0);
+            let l = PqNits::to_linear(v);
+            let e = PqNits::from_linear(l);
+            assert!((f32::from(e) - f32::from(v)).abs() < 1e-4);
+        }
+    }
+    #[test]
+    fn hlg() {
+        let v = HlgNits::to_linear(Ch32h::new(0.75));
+        assert!((f32::from(v) - 203.152_1).abs() < 1e-2);
+        let v = HlgNits::to_linear(Ch32h::new(1.0));
+        assert!((f32::from(v) - 1000.0).abs() < 1e-2);
+        let v = Hlg::<1000, 1000>::to_linear(Ch32::new(1.0));
+        assert_eq!(v, Ch32::new(1.0));
+        let v = Hlg::<1000, 1000>::from_linear(Ch32::new(0.0));
+        assert_eq!(v, Ch32::new(0.0));
+        for i in 0..=100 {
+            let v = Ch32h::new(i as f32 / 100.0);
+            let l = Hlg::<400, 1>::to_linear(v);
+            let e = Hlg::<400, 1>::from_linear(l);
+            assert!((f32::from(e) - f32::from(v)).abs() < 1e-4);
+        }
+    }
+    #[test]
+    fn srgb_powf_ulp() {
+        for (lo, g) in [(0.003_130_8_f32, 1.0 / 2.4), (0.04_f32, 2.4)] {
+            for b in (lo.to_bits()..1.0_f32.to_bits()).step_by(97) {
+                let v = f32::from_bits(b);
+                let p = srgb_powf(v, g).to_bits();
+                let e = (f64::from(v).powf(g) as f32).to_bits();
+                assert!(p.abs_diff(e) <= 1, "{}", v);
+            }
+        }
+    }
+    #[test]
+    fn srgb_extended() {
+        let v = Ch32h::new(2.0).encode_srgb();
+        assert!((f32::from(v) - 1.353_256).abs() < 1e-5);
+        assert!((f32::from(v.decode_srgb()) - 2.0).abs() < 1e-5);
+        let v = Ch32h::new(-0.5).encode_srgb();
+        assert!((f32::from(v) + 0.735_357).abs() < 1e-5);
+        let v = Ch32h::new(1.0).encode_srgb();
+        assert!((f32::from(v) - 1.0).abs() < 1e-5);
+    }
 }
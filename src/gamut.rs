@@ -0,0 +1,322 @@
+// gamut.rs     RGB primaries and gamut mapping.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+//! Module for RGB gamut items
+use crate::format;
+use crate::matrix::{invert, mul_vec, rgb_to_xyz};
+use crate::private::Sealed;
+use crate::white::{self, Adaptation, Point, D50, D60, D65};
+use std::any::Any;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// RGB color primaries and reference white.
+///
+/// When converting between [Rgb] pixels with different primaries, the
+/// components are transformed in linear light through *XYZ*.  Colors
+/// outside of the destination gamut are adjusted with a [Mapping].
+///
+/// This trait is *sealed*, and cannot be implemented outside of this crate.
+///
+/// [Mapping]: enum.Mapping.html
+/// [Rgb]: ../struct.Rgb.html
+///
+/// ```
+/// # use pix::*;
+/// let p: SRgb8 = P3Rgb8::new(0xFF, 0x00, 0x00).convert();
+/// assert_eq!(p, SRgb8::new(0xFF, 0x00, 0x00));
+/// let p: P3Rgb8 = SRgb8::new(0xFF, 0x00, 0x00).convert();
+/// assert_eq!(p, P3Rgb8::new(0xEA, 0x33, 0x23));
+/// ```
+pub trait Primaries:
+    Any + Copy + Clone + Debug + Default + PartialEq + Send + Sync + Sealed
+{
+    /// Reference white point
+    type White: white::Point;
+
+    /// CIE 1931 *xy* chromaticity of the *red* primary
+    const RED: [f64; 2];
+
+    /// CIE 1931 *xy* chromaticity of the *green* primary
+    const GREEN: [f64; 2];
+
+    /// CIE 1931 *xy* chromaticity of the *blue* primary
+    const BLUE: [f64; 2];
+
+    /// Matrix to convert linear *red*, *green* and *blue* to *XYZ*
+    const TO_XYZ: [[f64; 3]; 3] = rgb_to_xyz(
+        Self::RED,
+        Self::GREEN,
+        Self::BLUE,
+        <Self::White as Point>::XYZ,
+    );
+
+    /// Matrix to convert *XYZ* to linear *red*, *green* and *blue*
+    const FROM_XYZ: [[f64; 3]; 3] = invert(&Self::TO_XYZ);
+
+    /// Runtime descriptor of the primaries
+    const FORMAT: format::Primaries;
+}
+
+/// sRGB / BT.709 primaries with a [D65](../white/struct.D65.html) white
+/// point
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Srgb;
+
+/// Display P3 (DCI-P3) primaries with a [D65](../white/struct.D65.html)
+/// white point
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct DisplayP3;
+
+/// Rec. 2020 (BT.2020 / BT.2100) primaries with a
+/// [D65](../white/struct.D65.html) white point
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Rec2020;
+
+/// Adobe RGB (1998) primaries with a [D65](../white/struct.D65.html) white
+/// point
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct AdobeRgb;
+
+/// ProPhoto RGB (ROMM RGB) primaries with a [D50](../white/struct.D50.html)
+/// white point
+///
+/// ```
+/// # use pix::*;
+/// # use pix::alpha::{Opaque, Straight};
+/// # use pix::gamma::Gamma18;
+/// # use pix::gamut::ProPhotoRgb;
+/// # use pix::white::Adaptation;
+/// type ProPhoto16 = Rgb<Ch16, Opaque<Ch16>, Straight, Gamma18, ProPhotoRgb>;
+/// let p = ProPhoto16::new(0xFFFF, 0xFFFF, 0xFFFF);
+/// let conv = Conversion::default().with_adaptation(Adaptation::VonKries);
+/// let c: SRgb16 = conv.convert(p);
+/// assert_eq!(c, SRgb16::new(0xFFFF, 0xFFFF, 0xFFFF));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ProPhotoRgb;
+
+/// ACEScg (ACES AP1) primaries with an [ACES](../white/struct.D60.html)
+/// white point
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct AcesCg;
+
+/// CIE 1931 *XYZ* tristimulus values relative to a
+/// [white point](../white/trait.Point.html)
+///
+/// These are the primaries of [Xyz], [Lab], [Lch], [Oklab] and [Oklch]
+/// colors.  Their *red*, *green* and *blue* components are *X*, *Y* and *Z*,
+/// each divided by the white point, so colors are converted through *XYZ*
+/// directly, without being limited to the gamut of any RGB primaries.
+///
+/// [Lab]: ../struct.Lab.html
+/// [Lch]: ../struct.Lch.html
+/// [Oklab]: ../struct.Oklab.html
+/// [Oklch]: ../struct.Oklch.html
+/// [Xyz]: ../struct.Xyz.html
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CieXyz<W: Point>(PhantomData<W>);
+
+impl Primaries for Srgb {
+    type White = D65;
+    const FORMAT: format::Primaries = format::Primaries::Srgb;
+    const RED: [f64; 2] = [0.64, 0.33];
+    const GREEN: [f64; 2] = [0.30, 0.60];
+    const BLUE: [f64; 2] = [0.15, 0.06];
+}
+
+impl Primaries for DisplayP3 {
+    type White = D65;
+    const FORMAT: format::Primaries = format::Primaries::DisplayP3;
+    const RED: [f64; 2] = [0.680, 0.320];
+    const GREEN: [f64; 2] = [0.265, 0.690];
+    const BLUE: [f64; 2] = [0.150, 0.060];
+}
+
+impl Primaries for Rec2020 {
+    type White = D65;
+    const FORMAT: format::Primaries = format::Primaries::Rec2020;
+    const RED: [f64; 2] = [0.708, 0.292];
+    const GREEN: [f64; 2] = [0.170, 0.797];
+    const BLUE: [f64; 2] = [0.131, 0.046];
+}
+
+impl Primaries for AdobeRgb {
+    type White = D65;
+    const FORMAT: format::Primaries = format::Primaries::AdobeRgb;
+    const RED: [f64; 2] = [0.64, 0.33];
+    const GREEN: [f64; 2] = [0.21, 0.71];
+    const BLUE: [f64; 2] = [0.15, 0.06];
+}
+
+impl Primaries for ProPhotoRgb {
+    type White = D50;
+    const FORMAT: format::Primaries = format::Primaries::ProPhotoRgb;
+    const RED: [f64; 2] = [0.734_7, 0.265_3];
+    const GREEN: [f64; 2] = [0.159_6, 0.840_4];
+    const BLUE: [f64; 2] = [0.036_6, 0.000_1];
+}
+
+impl Primaries for AcesCg {
+    type White = D60;
+    const FORMAT: format::Primaries = format::Primaries::AcesCg;
+    const RED: [f64; 2] = [0.713, 0.293];
+    const GREEN: [f64; 2] = [0.165, 0.830];
+    const BLUE: [f64; 2] = [0.128, 0.044];
+}
+
+impl<W: Point> Primaries for CieXyz<W> {
+    type White = W;
+    const FORMAT: format::Primaries = format::Primaries::CieXyz;
+    const RED: [f64; 2] = [1.0, 0.0];
+    const GREEN: [f64; 2] = [0.0, 1.0];
+    const BLUE: [f64; 2] = [0.0, 0.0];
+    const TO_XYZ: [[f64; 3]; 3] = [
+        [W::XYZ[0], 0.0, 0.0],
+        [0.0, W::XYZ[1], 0.0],
+        [0.0, 0.0, W::XYZ[2]],
+    ];
+}
+
+/// Gamut mapping for colors outside of the destination gamut.
+///
+/// The mapping is selected with [Conversion] settings.  Only negative
+/// components are out of gamut; components above [Channel::MAX] are clamped
+/// by the destination [Channel], except for unclamped (HDR) channels.
+///
+/// [Channel]: ../trait.Channel.html
+/// [Channel::MAX]: ../trait.Channel.html#associatedconstant.MAX
+/// [Conversion]: ../struct.Conversion.html
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mapping {
+    /// Clip each negative component to zero, which can shift hue
+    #[default]
+    Clip,
+    /// Desaturate toward gray of the same luminance until every component
+    /// is in gamut, preserving hue and luminance
+    Compress,
+}
+
+impl Mapping {
+    /// Map linear RGB components into gamut.
+    ///
+    /// * `rgb` Linear *red*, *green* and *blue*.
+    /// * `lum` Luminance (*Y*) of each primary.
+    fn map(self, rgb: [f64; 3], lum: [f64; 3]) -> [f64; 3] {
+        match self {
+            Mapping::Clip => {
+                [rgb[0].max(0.0), rgb[1].max(0.0), rgb[2].max(0.0)]
+            }
+            Mapping::Compress => {
+                let y = rgb[0] * lum[0] + rgb[1] * lum[1] + rgb[2] * lum[2];
+                if y <= 0.0 {
+                    return [0.0; 3];
+                }
+                let t = rgb
+                    .iter()
+                    .filter(|c| **c < 0.0)
+                    .fold(1.0_f64, |t, c| t.min(y / (y - c)));
+                [
+                    y + t * (rgb[0] - y),
+                    y + t * (rgb[1] - y),
+                    y + t * (rgb[2] - y),
+                ]
+            }
+        }
+    }
+}
+
+/// Convert linear RGB components from one set of primaries to another.
+pub(crate) fn convert<S, D>(
+    rgb: [f64; 3],
+    mapping: Mapping,
+    adaptation: Adaptation,
+) -> [f64; 3]
+where
+    S: Primaries,
+    D: Primaries,
+{
+    let xyz = mul_vec(&S::TO_XYZ, rgb);
+    from_xyz::<D>(xyz, S::White::XYZ, mapping, adaptation)
+}
+
+/// Convert *XYZ* to linear RGB components, mapped into gamut.
+///
+/// * `xyz` Tristimulus values relative to the `white` point.
+pub(crate) fn from_xyz<D: Primaries>(
+    xyz: [f64; 3],
+    white: [f64; 3],
+    mapping: Mapping,
+    adaptation: Adaptation,
+) -> [f64; 3] {
+    let xyz = adaptation.adapt(xyz, white, D::White::XYZ);
+    mapping.map(mul_vec(&D::FROM_XYZ, xyz), D::TO_XYZ[1])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn srgb_matrix() {
+        let m = Srgb::TO_XYZ;
+        let e = [
+            [0.412_4, 0.357_6, 0.180_5],
+            [0.212_6, 0.715_2, 0.072_2],
+            [0.019_3, 0.119_2, 0.950_5],
+        ];
+        for (r, re) in m.iter().zip(e.iter()) {
+            for (v, ev) in r.iter().zip(re.iter()) {
+                assert!((v - ev).abs() < 1e-3);
+            }
+        }
+    }
+    #[test]
+    fn white_maps_to_white() {
+        let a = Adaptation::Bradford;
+        let rgb = convert::<DisplayP3, Srgb>([1.0; 3], Mapping::Clip, a);
+        for v in rgb.iter() {
+            assert!((v - 1.0).abs() < 1e-4);
+        }
+        let a = Adaptation::Cat02;
+        let rgb = convert::<AcesCg, Rec2020>([1.0; 3], Mapping::Clip, a);
+        for v in rgb.iter() {
+            assert!((v - 1.0).abs() < 1e-3);
+        }
+        let a = Adaptation::VonKries;
+        let rgb = convert::<ProPhotoRgb, Srgb>([1.0; 3], Mapping::Clip, a);
+        for v in rgb.iter() {
+            assert!((v - 1.0).abs() < 1e-3);
+        }
+    }
+    #[test]
+    fn xyz_white() {
+        let a = Adaptation::Bradford;
+        let xyz = convert::<Srgb, CieXyz<D50>>([1.0; 3], Mapping::Clip, a);
+        for v in xyz.iter() {
+            assert!((v - 1.0).abs() < 1e-3);
+        }
+    }
+    #[test]
+    fn xyz_wide_gamut() {
+        // Rec. 2020 green is outside of the sRGB gamut
+        let a = Adaptation::Bradford;
+        let rgb = [0.0, 1.0, 0.0];
+        let xyz = convert::<Rec2020, CieXyz<D65>>(rgb, Mapping::Clip, a);
+        let rgb = convert::<CieXyz<D65>, Rec2020>(xyz, Mapping::Clip, a);
+        assert!(rgb[0].abs() < 1e-12);
+        assert!((rgb[1] - 1.0).abs() < 1e-12);
+        assert!(rgb[2].abs() < 1e-12);
+    }
+    #[test]
+    fn compress() {
+        let lum = Srgb::TO_XYZ[1];
+        let rgb = Mapping::Compress.map([1.2, -0.1, 0.0], lum);
+        assert!(rgb.iter().all(|c| *c >= -1e-12));
+        let y0 = 1.2 * lum[0] - 0.1 * lum[1];
+        let y1 = rgb[0] * lum[0] + rgb[1] * lum[1] + rgb[2] * lum[2];
+        assert!((y0 - y1).abs() < 1e-12);
+        let rgb = Mapping::Clip.map([1.2, -0.1, 0.0], lum);
+        assert_eq!(rgb, [1.2, 0.0, 0.0]);
+    }
+}
@@ -6,8 +6,10 @@
 use crate::alpha::{
     self, AChannel, Mode as _, Opaque, Premultiplied, Straight, Translucent,
 };
+use crate::format::Model;
 use crate::gamma::{self, Linear, Srgb};
-use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel};
+use crate::gamut;
+use crate::{Ch16, Ch16f, Ch32, Ch32h, Ch64, Ch8, Channel, ColorModel, Pixel};
 use std::marker::PhantomData;
 use std::ops::Mul;
 
@@ -95,6 +97,8 @@ where
     G: gamma::Mode,
 {
     type Chan = C;
+    const MODEL: Model = Model::Gray;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
 
     /// Get all non-alpha components
     fn components(&self) -> &[Self::Chan] {
@@ -157,6 +161,7 @@ where
 {
     type Alpha = M;
     type Gamma = G;
+    type Primaries = gamut::Srgb;
 }
 
 impl<C, A, M, G> Iterator for Gray<C, A, M, G>
@@ -259,6 +264,19 @@ pub type Gray16 = Gray<Ch16, Opaque<Ch16>, Straight, Linear>;
 /// alpha) [linear](gamma/struct.Linear.html) gamma
 /// [pixel](trait.Pixel.html) format.
 pub type Gray32 = Gray<Ch32, Opaque<Ch32>, Straight, Linear>;
+/// [Gray](struct.Gray.html) 16-bit floating-point
+/// [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Gray16f = Gray<Ch16f, Opaque<Ch16f>, Straight, Linear>;
+/// [Gray](struct.Gray.html) 64-bit floating-point
+/// [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Gray64 = Gray<Ch64, Opaque<Ch64>, Straight, Linear>;
+/// [Gray](struct.Gray.html) 32-bit floating-point
+/// [high dynamic range](struct.Ch32h.html) [opaque](alpha/struct.Opaque.html)
+/// (no alpha) [linear](gamma/struct.Linear.html) gamma
+/// [pixel](trait.Pixel.html) format.
+pub type Gray32h = Gray<Ch32h, Opaque<Ch32h>, Straight, Linear>;
 
 type GrayAlpha<C, A> = Gray<C, A, Straight, Linear>;
 /// [Gray](struct.Gray.html) 8-bit [straight](alpha/struct.Straight.html) alpha
@@ -270,6 +288,19 @@ pub type GrayAlpha16 = GrayAlpha<Ch16, Translucent<Ch16>>;
 /// [Gray](struct.Gray.html) 32-bit [straight](alpha/struct.Straight.html) alpha
 /// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
 pub type GrayAlpha32 = GrayAlpha<Ch32, Translucent<Ch32>>;
+/// [Gray](struct.Gray.html) 16-bit floating-point
+/// [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type GrayAlpha16f = GrayAlpha<Ch16f, Translucent<Ch16f>>;
+/// [Gray](struct.Gray.html) 64-bit floating-point
+/// [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type GrayAlpha64 = GrayAlpha<Ch64, Translucent<Ch64>>;
+/// [Gray](struct.Gray.html) 32-bit floating-point
+/// [high dynamic range](struct.Ch32h.html)
+/// [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type GrayAlpha32h = GrayAlpha<Ch32h, Translucent<Ch32h>>;
 
 type GrayAlphap<C, A> = Gray<C, A, Premultiplied, Linear>;
 /// [Gray](struct.Gray.html) 8-bit
@@ -284,6 +315,19 @@ pub type GrayAlpha16p = GrayAlphap<Ch16, Translucent<Ch16>>;
 /// [premultiplied](alpha/struct.Premultiplied.html) alpha
 /// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
 pub type GrayAlpha32p = GrayAlphap<Ch32, Translucent<Ch32>>;
+/// [Gray](struct.Gray.html) 16-bit floating-point
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type GrayAlpha16fp = GrayAlphap<Ch16f, Translucent<Ch16f>>;
+/// [Gray](struct.Gray.html) 64-bit floating-point
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type GrayAlpha64p = GrayAlphap<Ch64, Translucent<Ch64>>;
+/// [Gray](struct.Gray.html) 32-bit floating-point
+/// [high dynamic range](struct.Ch32h.html)
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type GrayAlpha32hp = GrayAlphap<Ch32h, Translucent<Ch32h>>;
 
 type SGray<C, A> = Gray<C, A, Straight, Srgb>;
 /// [Gray](struct.Gray.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
@@ -297,6 +341,14 @@ pub type SGray16 = SGray<Ch16, Opaque<Ch16>>;
 /// alpha) [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html)
 /// format.
 pub type SGray32 = SGray<Ch32, Opaque<Ch32>>;
+/// [Gray](struct.Gray.html) 16-bit floating-point
+/// [opaque](alpha/struct.Opaque.html) (no alpha) [sRGB](gamma/struct.Srgb.html)
+/// gamma [pixel](trait.Pixel.html) format.
+pub type SGray16f = SGray<Ch16f, Opaque<Ch16f>>;
+/// [Gray](struct.Gray.html) 64-bit floating-point
+/// [opaque](alpha/struct.Opaque.html) (no alpha) [sRGB](gamma/struct.Srgb.html)
+/// gamma [pixel](trait.Pixel.html) format.
+pub type SGray64 = SGray<Ch64, Opaque<Ch64>>;
 
 type SGrayAlpha<C, A> = Gray<C, A, Straight, Srgb>;
 /// [Gray](struct.Gray.html) 8-bit [straight](alpha/struct.Straight.html) alpha
@@ -308,6 +360,14 @@ pub type SGrayAlpha16 = SGrayAlpha<Ch16, Translucent<Ch16>>;
 /// [Gray](struct.Gray.html) 32-bit [straight](alpha/struct.Straight.html) alpha
 /// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
 pub type SGrayAlpha32 = SGrayAlpha<Ch32, Translucent<Ch32>>;
+/// [Gray](struct.Gray.html) 16-bit floating-point
+/// [straight](alpha/struct.Straight.html) alpha [sRGB](gamma/struct.Srgb.html)
+/// gamma [pixel](trait.Pixel.html) format.
+pub type SGrayAlpha16f = SGrayAlpha<Ch16f, Translucent<Ch16f>>;
+/// [Gray](struct.Gray.html) 64-bit floating-point
+/// [straight](alpha/struct.Straight.html) alpha [sRGB](gamma/struct.Srgb.html)
+/// gamma [pixel](trait.Pixel.html) format.
+pub type SGrayAlpha64 = SGrayAlpha<Ch64, Translucent<Ch64>>;
 
 type SGrayAlphap<C, A> = Gray<C, A, Premultiplied, Srgb>;
 /// [Gray](struct.Gray.html) 8-bit
@@ -322,6 +382,107 @@ pub type SGrayAlpha16p = SGrayAlphap<Ch16, Translucent<Ch16>>;
 /// [premultiplied](alpha/struct.Premultiplied.html) alpha
 /// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
 pub type SGrayAlpha32p = SGrayAlphap<Ch32, Translucent<Ch32>>;
+/// [Gray](struct.Gray.html) 16-bit floating-point
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SGrayAlpha16fp = SGrayAlphap<Ch16f, Translucent<Ch16f>>;
+/// [Gray](struct.Gray.html) 64-bit floating-point
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SGrayAlpha64p = SGrayAlphap<Ch64, Translucent<Ch64>>;
+
+type Rec709Gray<C, A> = Gray<C, A, Straight, gamma::Rec709>;
+/// [Gray](struct.Gray.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [Rec. 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec709Gray8 = Rec709Gray<Ch8, Opaque<Ch8>>;
+/// [Gray](struct.Gray.html) 16-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha)
+/// [Rec. 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec709Gray16 = Rec709Gray<Ch16, Opaque<Ch16>>;
+
+type Rec709GrayAlpha<C, A> = Gray<C, A, Straight, gamma::Rec709>;
+/// [Gray](struct.Gray.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [Rec. 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec709GrayAlpha8 = Rec709GrayAlpha<Ch8, Translucent<Ch8>>;
+/// [Gray](struct.Gray.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [Rec. 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec709GrayAlpha16 = Rec709GrayAlpha<Ch16, Translucent<Ch16>>;
+
+type Rec709GrayAlphap<C, A> = Gray<C, A, Premultiplied, gamma::Rec709>;
+/// [Gray](struct.Gray.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [Rec. 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec709GrayAlpha8p = Rec709GrayAlphap<Ch8, Translucent<Ch8>>;
+/// [Gray](struct.Gray.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [Rec. 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec709GrayAlpha16p = Rec709GrayAlphap<Ch16, Translucent<Ch16>>;
+
+type G18Gray<C, A> = Gray<C, A, Straight, gamma::Gamma18>;
+/// [Gray](struct.Gray.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [1.8 power law](gamma/type.Gamma18.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G18Gray8 = G18Gray<Ch8, Opaque<Ch8>>;
+/// [Gray](struct.Gray.html) 16-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha)
+/// [1.8 power law](gamma/type.Gamma18.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G18Gray16 = G18Gray<Ch16, Opaque<Ch16>>;
+
+type G18GrayAlpha<C, A> = Gray<C, A, Straight, gamma::Gamma18>;
+/// [Gray](struct.Gray.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [1.8 power law](gamma/type.Gamma18.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G18GrayAlpha8 = G18GrayAlpha<Ch8, Translucent<Ch8>>;
+/// [Gray](struct.Gray.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [1.8 power law](gamma/type.Gamma18.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G18GrayAlpha16 = G18GrayAlpha<Ch16, Translucent<Ch16>>;
+
+type G18GrayAlphap<C, A> = Gray<C, A, Premultiplied, gamma::Gamma18>;
+/// [Gray](struct.Gray.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [1.8 power law](gamma/type.Gamma18.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G18GrayAlpha8p = G18GrayAlphap<Ch8, Translucent<Ch8>>;
+/// [Gray](struct.Gray.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [1.8 power law](gamma/type.Gamma18.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G18GrayAlpha16p = G18GrayAlphap<Ch16, Translucent<Ch16>>;
+
+type G22Gray<C, A> = Gray<C, A, Straight, gamma::Gamma22>;
+/// [Gray](struct.Gray.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [2.2 power law](gamma/type.Gamma22.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G22Gray8 = G22Gray<Ch8, Opaque<Ch8>>;
+/// [Gray](struct.Gray.html) 16-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha)
+/// [2.2 power law](gamma/type.Gamma22.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G22Gray16 = G22Gray<Ch16, Opaque<Ch16>>;
+
+type G22GrayAlpha<C, A> = Gray<C, A, Straight, gamma::Gamma22>;
+/// [Gray](struct.Gray.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [2.2 power law](gamma/type.Gamma22.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G22GrayAlpha8 = G22GrayAlpha<Ch8, Translucent<Ch8>>;
+/// [Gray](struct.Gray.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [2.2 power law](gamma/type.Gamma22.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G22GrayAlpha16 = G22GrayAlpha<Ch16, Translucent<Ch16>>;
+
+type G22GrayAlphap<C, A> = Gray<C, A, Premultiplied, gamma::Gamma22>;
+/// [Gray](struct.Gray.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [2.2 power law](gamma/type.Gamma22.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G22GrayAlpha8p = G22GrayAlphap<Ch8, Translucent<Ch8>>;
+/// [Gray](struct.Gray.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [2.2 power law](gamma/type.Gamma22.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G22GrayAlpha16p = G22GrayAlphap<Ch16, Translucent<Ch16>>;
 
 #[cfg(test)]
 mod test {
@@ -335,5 +496,9 @@ mod test {
         assert_eq!(std::mem::size_of::<SGrayAlpha8>(), 2);
         assert_eq!(std::mem::size_of::<SGrayAlpha16>(), 4);
         assert_eq!(std::mem::size_of::<SGrayAlpha32>(), 8);
+        assert_eq!(std::mem::size_of::<SGray16f>(), 2);
+        assert_eq!(std::mem::size_of::<SGrayAlpha16f>(), 4);
+        assert_eq!(std::mem::size_of::<SGray64>(), 8);
+        assert_eq!(std::mem::size_of::<SGrayAlpha64>(), 16);
     }
 }
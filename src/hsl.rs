@@ -0,0 +1,352 @@
+// hsl.rs       HSL color model.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{
+    self, AChannel, Opaque, Premultiplied, Straight, Translucent,
+};
+use crate::format::Model;
+use crate::gamma::{self, Linear, Srgb};
+use crate::gamut;
+use crate::hue::{
+    hue_difference, rgb_from_hue_chroma, rgb_to_f32, rgb_to_hue_chroma,
+};
+use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel};
+use std::marker::PhantomData;
+
+/// HSL bi-hexcone [color model].
+///
+/// The components are *hue*, *saturation* and *lightness*, with optional
+/// *[alpha]*.  *Hue* is a turn around the bi-hexcone, with
+/// [Channel::MIN] and [Channel::MAX] both being *red*.
+///
+/// [alpha]: alpha/trait.AChannel.html
+/// [Channel::MAX]: trait.Channel.html#associatedconstant.MAX
+/// [Channel::MIN]: trait.Channel.html#associatedconstant.MIN
+/// [color model]: trait.ColorModel.html
+///
+/// ### Convert from `Rgb`
+/// ```
+/// # use pix::*;
+/// let hsl: SHsl8 = SRgb8::new(0x00, 0xFF, 0x00).convert();
+/// assert_eq!(hsl, SHsl8::new(0x55, 0xFF, 0x80));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Hsl<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    components: [C; 3],
+    alpha: A,
+    mode: PhantomData<M>,
+    gamma: PhantomData<G>,
+}
+
+impl<C, A, M, G> Hsl<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying *hue*,
+    /// *saturation* and *lightness*.
+    pub fn new<H>(hue: H, saturation: H, lightness: H) -> Self
+    where
+        C: From<H>,
+        A: From<Opaque<C>>,
+    {
+        Self::with_alpha(hue, saturation, lightness, Opaque::default())
+    }
+    /// Create a [Translucent](alpha/struct.Translucent.html) color by
+    /// specifying *hue*, *saturation*, *lightness* and *alpha*.
+    pub fn with_alpha<H, B>(
+        hue: H,
+        saturation: H,
+        lightness: H,
+        alpha: B,
+    ) -> Self
+    where
+        C: From<H>,
+        A: From<B>,
+    {
+        let hue = C::from(hue);
+        let saturation = C::from(saturation);
+        let lightness = C::from(lightness);
+        let components = [hue, saturation, lightness];
+        let alpha = A::from(alpha);
+        Hsl {
+            components,
+            alpha,
+            mode: PhantomData,
+            gamma: PhantomData,
+        }
+    }
+    /// Get the *hue* component.
+    pub fn hue(self) -> C {
+        self.components[0]
+    }
+    /// Get the *saturation* component.
+    pub fn saturation(self) -> C {
+        self.components[1]
+    }
+    /// Get the *lightness* component.
+    pub fn lightness(self) -> C {
+        self.components[2]
+    }
+}
+
+impl<C, A, M, G> ColorModel for Hsl<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Chan = C;
+    const MODEL: Model = Model::Hsl;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
+
+    /// Get all non-alpha components
+    fn components(&self) -> &[Self::Chan] {
+        &self.components
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.alpha.value()
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        let hue = self.hue().into();
+        let saturation: f32 = self.saturation().into();
+        let lightness: f32 = self.lightness().into();
+        let chroma = (1.0 - (lightness * 2.0 - 1.0).abs()) * saturation;
+        let offset = lightness - chroma / 2.0;
+        let rgb = rgb_from_hue_chroma(hue, chroma, offset);
+        [rgb[0].into(), rgb[1].into(), rgb[2].into(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        let (hue, chroma, min, max) = rgb_to_hue_chroma(rgb_to_f32(rgba));
+        let lightness = (max + min) / 2.0;
+        let range = 1.0 - (lightness * 2.0 - 1.0).abs();
+        let saturation = if range > 0.0 { chroma / range } else { 0.0 };
+        Hsl::with_alpha(hue, saturation, lightness, rgba[3])
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let h = hue_difference(self.hue(), rhs.hue());
+        let s = if self.saturation() > rhs.saturation() {
+            self.saturation() - rhs.saturation()
+        } else {
+            rhs.saturation() - self.saturation()
+        };
+        let l = if self.lightness() > rhs.lightness() {
+            self.lightness() - rhs.lightness()
+        } else {
+            rhs.lightness() - self.lightness()
+        };
+        let a = if self.alpha() > rhs.alpha() {
+            self.alpha() - rhs.alpha()
+        } else {
+            rhs.alpha() - self.alpha()
+        };
+        Hsl::with_alpha(h, s, l, a)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        self.hue() <= rhs.hue()
+            && self.saturation() <= rhs.saturation()
+            && self.lightness() <= rhs.lightness()
+            && self.alpha() <= rhs.alpha()
+    }
+}
+
+impl<C, A, M, G> Pixel for Hsl<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Alpha = M;
+    type Gamma = G;
+    type Primaries = gamut::Srgb;
+}
+
+impl<C, A, M, G> Iterator for Hsl<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<C, M, G> From<Hsl<C, Translucent<C>, M, G>> for Hsl<C, Opaque<C>, M, G>
+where
+    C: Channel,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn from(c: Hsl<C, Translucent<C>, M, G>) -> Self {
+        Hsl::new(c.hue(), c.saturation(), c.lightness())
+    }
+}
+
+impl<C, M, G> From<Hsl<C, Opaque<C>, M, G>> for Hsl<C, Translucent<C>, M, G>
+where
+    C: Channel,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn from(c: Hsl<C, Opaque<C>, M, G>) -> Self {
+        Hsl::with_alpha(c.hue(), c.saturation(), c.lightness(), C::MAX)
+    }
+}
+
+
+/// [Hsl](struct.Hsl.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsl8 = Hsl<Ch8, Opaque<Ch8>, Straight, Linear>;
+/// [Hsl](struct.Hsl.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsl16 = Hsl<Ch16, Opaque<Ch16>, Straight, Linear>;
+/// [Hsl](struct.Hsl.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsl32 = Hsl<Ch32, Opaque<Ch32>, Straight, Linear>;
+
+/// [Hsl](struct.Hsl.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsla8 = Hsl<Ch8, Translucent<Ch8>, Straight, Linear>;
+/// [Hsl](struct.Hsl.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsla16 = Hsl<Ch16, Translucent<Ch16>, Straight, Linear>;
+/// [Hsl](struct.Hsl.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsla32 = Hsl<Ch32, Translucent<Ch32>, Straight, Linear>;
+
+/// [Hsl](struct.Hsl.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsla8p = Hsl<Ch8, Translucent<Ch8>, Premultiplied, Linear>;
+/// [Hsl](struct.Hsl.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsla16p = Hsl<Ch16, Translucent<Ch16>, Premultiplied, Linear>;
+/// [Hsl](struct.Hsl.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsla32p = Hsl<Ch32, Translucent<Ch32>, Premultiplied, Linear>;
+
+/// [Hsl](struct.Hsl.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsl8 = Hsl<Ch8, Opaque<Ch8>, Straight, Srgb>;
+/// [Hsl](struct.Hsl.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsl16 = Hsl<Ch16, Opaque<Ch16>, Straight, Srgb>;
+/// [Hsl](struct.Hsl.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsl32 = Hsl<Ch32, Opaque<Ch32>, Straight, Srgb>;
+
+/// [Hsl](struct.Hsl.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsla8 = Hsl<Ch8, Translucent<Ch8>, Straight, Srgb>;
+/// [Hsl](struct.Hsl.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsla16 = Hsl<Ch16, Translucent<Ch16>, Straight, Srgb>;
+/// [Hsl](struct.Hsl.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsla32 = Hsl<Ch32, Translucent<Ch32>, Straight, Srgb>;
+
+/// [Hsl](struct.Hsl.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsla8p = Hsl<Ch8, Translucent<Ch8>, Premultiplied, Srgb>;
+/// [Hsl](struct.Hsl.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsla16p = Hsl<Ch16, Translucent<Ch16>, Premultiplied, Srgb>;
+/// [Hsl](struct.Hsl.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsla32p = Hsl<Ch32, Translucent<Ch32>, Premultiplied, Srgb>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<SHsl8>(), 3);
+        assert_eq!(std::mem::size_of::<SHsl16>(), 6);
+        assert_eq!(std::mem::size_of::<SHsl32>(), 12);
+        assert_eq!(std::mem::size_of::<SHsla8>(), 4);
+        assert_eq!(std::mem::size_of::<SHsla16>(), 8);
+        assert_eq!(std::mem::size_of::<SHsla32>(), 16);
+    }
+
+    #[test]
+    fn hsl_to_rgb() {
+        assert_eq!(
+            SRgb8::new(255, 255, 0),
+            SHsl32::new(60.0 / 360.0, 1.0, 0.5).convert(),
+        );
+        assert_eq!(
+            SRgb8::new(255, 128, 128),
+            SHsl32::new(0.0, 1.0, 0.75).convert(),
+        );
+        assert_eq!(
+            SRgb32::new(0.25, 0.25, 0.75),
+            SHsl32::new(240.0 / 360.0, 0.5, 0.5).convert(),
+        );
+        assert_eq!(
+            SRgb32::new(1.0, 1.0, 1.0),
+            SHsl32::new(0.3, 1.0, 1.0).convert(),
+        );
+    }
+
+    #[test]
+    fn rgb_to_hsl() {
+        assert_eq!(
+            SHsl32::new(0.0, 1.0, 0.5),
+            SRgb32::new(1.0, 0.0, 0.0).convert(),
+        );
+        assert_eq!(
+            SHsl32::new(0.5, 1.0, 0.75),
+            SRgb32::new(0.5, 1.0, 1.0).convert(),
+        );
+        assert_eq!(
+            SHsl32::new(0.0, 0.0, 0.25),
+            SRgb32::new(0.25, 0.25, 0.25).convert(),
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb =
+                        SRgb16::new(Ch8::new(r), Ch8::new(g), Ch8::new(b));
+                    let hsl: SHsl32 = rgb.convert();
+                    assert_eq!(rgb, hsl.convert());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,348 @@
+// hsv.rs       HSV color model.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{
+    self, AChannel, Opaque, Premultiplied, Straight, Translucent,
+};
+use crate::format::Model;
+use crate::gamma::{self, Linear, Srgb};
+use crate::gamut;
+use crate::hue::{
+    hue_difference, rgb_from_hue_chroma, rgb_to_f32, rgb_to_hue_chroma,
+};
+use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel};
+use std::marker::PhantomData;
+
+/// HSV hexcone [color model], also known as HSB.
+///
+/// The components are *hue*, *saturation* and *value* (or *brightness*), with
+/// optional *[alpha]*.  *Hue* is a turn around the hexcone, with
+/// [Channel::MIN] and [Channel::MAX] both being *red*.
+///
+/// [alpha]: alpha/trait.AChannel.html
+/// [Channel::MAX]: trait.Channel.html#associatedconstant.MAX
+/// [Channel::MIN]: trait.Channel.html#associatedconstant.MIN
+/// [color model]: trait.ColorModel.html
+///
+/// ### Convert from `Rgb`
+/// ```
+/// # use pix::*;
+/// let hsv: SHsv8 = SRgb8::new(0x00, 0xFF, 0x00).convert();
+/// assert_eq!(hsv, SHsv8::new(0x55, 0xFF, 0xFF));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Hsv<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    components: [C; 3],
+    alpha: A,
+    mode: PhantomData<M>,
+    gamma: PhantomData<G>,
+}
+
+impl<C, A, M, G> Hsv<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying *hue*,
+    /// *saturation* and *value*.
+    pub fn new<H>(hue: H, saturation: H, value: H) -> Self
+    where
+        C: From<H>,
+        A: From<Opaque<C>>,
+    {
+        Self::with_alpha(hue, saturation, value, Opaque::default())
+    }
+    /// Create a [Translucent](alpha/struct.Translucent.html) color by
+    /// specifying *hue*, *saturation*, *value* and *alpha*.
+    pub fn with_alpha<H, B>(hue: H, saturation: H, value: H, alpha: B) -> Self
+    where
+        C: From<H>,
+        A: From<B>,
+    {
+        let hue = C::from(hue);
+        let saturation = C::from(saturation);
+        let value = C::from(value);
+        let components = [hue, saturation, value];
+        let alpha = A::from(alpha);
+        Hsv {
+            components,
+            alpha,
+            mode: PhantomData,
+            gamma: PhantomData,
+        }
+    }
+    /// Get the *hue* component.
+    pub fn hue(self) -> C {
+        self.components[0]
+    }
+    /// Get the *saturation* component.
+    pub fn saturation(self) -> C {
+        self.components[1]
+    }
+    /// Get the *value* (or *brightness*) component.
+    pub fn value(self) -> C {
+        self.components[2]
+    }
+}
+
+impl<C, A, M, G> ColorModel for Hsv<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Chan = C;
+    const MODEL: Model = Model::Hsv;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
+
+    /// Get all non-alpha components
+    fn components(&self) -> &[Self::Chan] {
+        &self.components
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.alpha.value()
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        let hue = self.hue().into();
+        let saturation: f32 = self.saturation().into();
+        let value: f32 = self.value().into();
+        let chroma = value * saturation;
+        let offset = value - chroma;
+        let rgb = rgb_from_hue_chroma(hue, chroma, offset);
+        [rgb[0].into(), rgb[1].into(), rgb[2].into(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        let (hue, chroma, _, value) = rgb_to_hue_chroma(rgb_to_f32(rgba));
+        let saturation = if value > 0.0 { chroma / value } else { 0.0 };
+        Hsv::with_alpha(hue, saturation, value, rgba[3])
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let h = hue_difference(self.hue(), rhs.hue());
+        let s = if self.saturation() > rhs.saturation() {
+            self.saturation() - rhs.saturation()
+        } else {
+            rhs.saturation() - self.saturation()
+        };
+        let v = if self.value() > rhs.value() {
+            self.value() - rhs.value()
+        } else {
+            rhs.value() - self.value()
+        };
+        let a = if self.alpha() > rhs.alpha() {
+            self.alpha() - rhs.alpha()
+        } else {
+            rhs.alpha() - self.alpha()
+        };
+        Hsv::with_alpha(h, s, v, a)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        self.hue() <= rhs.hue()
+            && self.saturation() <= rhs.saturation()
+            && self.value() <= rhs.value()
+            && self.alpha() <= rhs.alpha()
+    }
+}
+
+impl<C, A, M, G> Pixel for Hsv<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Alpha = M;
+    type Gamma = G;
+    type Primaries = gamut::Srgb;
+}
+
+impl<C, A, M, G> Iterator for Hsv<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<C, M, G> From<Hsv<C, Translucent<C>, M, G>> for Hsv<C, Opaque<C>, M, G>
+where
+    C: Channel,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn from(c: Hsv<C, Translucent<C>, M, G>) -> Self {
+        Hsv::new(c.hue(), c.saturation(), c.value())
+    }
+}
+
+impl<C, M, G> From<Hsv<C, Opaque<C>, M, G>> for Hsv<C, Translucent<C>, M, G>
+where
+    C: Channel,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn from(c: Hsv<C, Opaque<C>, M, G>) -> Self {
+        Hsv::with_alpha(c.hue(), c.saturation(), c.value(), C::MAX)
+    }
+}
+
+/// [Hsv](struct.Hsv.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsv8 = Hsv<Ch8, Opaque<Ch8>, Straight, Linear>;
+/// [Hsv](struct.Hsv.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsv16 = Hsv<Ch16, Opaque<Ch16>, Straight, Linear>;
+/// [Hsv](struct.Hsv.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsv32 = Hsv<Ch32, Opaque<Ch32>, Straight, Linear>;
+
+/// [Hsv](struct.Hsv.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsva8 = Hsv<Ch8, Translucent<Ch8>, Straight, Linear>;
+/// [Hsv](struct.Hsv.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsva16 = Hsv<Ch16, Translucent<Ch16>, Straight, Linear>;
+/// [Hsv](struct.Hsv.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsva32 = Hsv<Ch32, Translucent<Ch32>, Straight, Linear>;
+
+/// [Hsv](struct.Hsv.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsva8p = Hsv<Ch8, Translucent<Ch8>, Premultiplied, Linear>;
+/// [Hsv](struct.Hsv.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsva16p = Hsv<Ch16, Translucent<Ch16>, Premultiplied, Linear>;
+/// [Hsv](struct.Hsv.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hsva32p = Hsv<Ch32, Translucent<Ch32>, Premultiplied, Linear>;
+
+/// [Hsv](struct.Hsv.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsv8 = Hsv<Ch8, Opaque<Ch8>, Straight, Srgb>;
+/// [Hsv](struct.Hsv.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsv16 = Hsv<Ch16, Opaque<Ch16>, Straight, Srgb>;
+/// [Hsv](struct.Hsv.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsv32 = Hsv<Ch32, Opaque<Ch32>, Straight, Srgb>;
+
+/// [Hsv](struct.Hsv.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsva8 = Hsv<Ch8, Translucent<Ch8>, Straight, Srgb>;
+/// [Hsv](struct.Hsv.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsva16 = Hsv<Ch16, Translucent<Ch16>, Straight, Srgb>;
+/// [Hsv](struct.Hsv.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsva32 = Hsv<Ch32, Translucent<Ch32>, Straight, Srgb>;
+
+/// [Hsv](struct.Hsv.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsva8p = Hsv<Ch8, Translucent<Ch8>, Premultiplied, Srgb>;
+/// [Hsv](struct.Hsv.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsva16p = Hsv<Ch16, Translucent<Ch16>, Premultiplied, Srgb>;
+/// [Hsv](struct.Hsv.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHsva32p = Hsv<Ch32, Translucent<Ch32>, Premultiplied, Srgb>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<SHsv8>(), 3);
+        assert_eq!(std::mem::size_of::<SHsv16>(), 6);
+        assert_eq!(std::mem::size_of::<SHsv32>(), 12);
+        assert_eq!(std::mem::size_of::<SHsva8>(), 4);
+        assert_eq!(std::mem::size_of::<SHsva16>(), 8);
+        assert_eq!(std::mem::size_of::<SHsva32>(), 16);
+    }
+
+    #[test]
+    fn hsv_to_rgb() {
+        assert_eq!(
+            SRgb8::new(255, 1, 1),
+            SHsv8::new(Ch8::new(0), Ch8::new(254), Ch8::new(255)).convert(),
+        );
+        assert_eq!(
+            SRgb8::new(255, 255, 0),
+            SHsv32::new(60.0 / 360.0, 1.0, 1.0).convert(),
+        );
+        assert_eq!(
+            SRgb8::new(0, 0, 128),
+            SHsv32::new(240.0 / 360.0, 1.0, 0.5).convert(),
+        );
+        assert_eq!(
+            SRgb32::new(0.5, 0.5, 0.5),
+            SHsv32::new(0.7, 0.0, 0.5).convert(),
+        );
+    }
+
+    #[test]
+    fn rgb_to_hsv() {
+        assert_eq!(
+            SHsv32::new(0.0, 1.0, 1.0),
+            SRgb32::new(1.0, 0.0, 0.0).convert(),
+        );
+        assert_eq!(
+            SHsv32::new(0.5, 0.5, 1.0),
+            SRgb32::new(0.5, 1.0, 1.0).convert(),
+        );
+        assert_eq!(
+            SHsv32::new(0.0, 0.0, 0.25),
+            SRgb32::new(0.25, 0.25, 0.25).convert(),
+        );
+        assert_eq!(
+            SHsva16::with_alpha(0x0000, 0xFFFF, 0xFFFF, 0x8000),
+            SRgba16::with_alpha(0xFFFF, 0x0000, 0x0000, 0x8000).convert(),
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb =
+                        SRgb16::new(Ch8::new(r), Ch8::new(g), Ch8::new(b));
+                    let hsv: SHsv32 = rgb.convert();
+                    assert_eq!(rgb, hsv.convert());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+// hue.rs       Hue conversion helpers.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::Channel;
+
+/// Get *red*, *green* and *blue* components as `f32` values.
+pub fn rgb_to_f32<C: Channel>(rgba: [C; 4]) -> [f32; 3] {
+    [rgba[0].into(), rgba[1].into(), rgba[2].into()]
+}
+
+/// Get *hue* and *chroma* from *red*, *green* and *blue* components.
+///
+/// * `rgb` Components, ranging from 0 to 1.
+///
+/// # Returns
+/// Tuple of *hue* (0 to 1, a full turn of the hexcone), *chroma*, minimum
+/// component and maximum component.
+pub fn rgb_to_hue_chroma(rgb: [f32; 3]) -> (f32, f32, f32, f32) {
+    let [red, green, blue] = rgb;
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let chroma = max - min;
+    let hue = if chroma > 0.0 {
+        let h = if max == red {
+            ((green - blue) / chroma).rem_euclid(6.0)
+        } else if max == green {
+            (blue - red) / chroma + 2.0
+        } else {
+            (red - green) / chroma + 4.0
+        };
+        (h / 6.0).rem_euclid(1.0)
+    } else {
+        0.0
+    };
+    (hue, chroma, min, max)
+}
+
+/// Get *red*, *green* and *blue* components from *hue* and *chroma*.
+///
+/// * `hue` Hue, ranging from 0 to 1 (a full turn of the hexcone).
+/// * `chroma` Chroma, ranging from 0 to 1.
+/// * `offset` Value added to each component to match lightness.
+pub fn rgb_from_hue_chroma(hue: f32, chroma: f32, offset: f32) -> [f32; 3] {
+    let h = hue.rem_euclid(1.0) * 6.0;
+    let x = chroma * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+    let (red, green, blue) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    [red + offset, green + offset, blue + offset]
+}
+
+/// Get the absolute difference between two *hue* channels.
+///
+/// Hue is circular, so the shorter distance around the hexcone is used.
+pub fn hue_difference<C: Channel>(a: C, b: C) -> C {
+    let d = if a > b { a - b } else { b - a };
+    let e = C::MAX - d;
+    d.min(e)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn hue_chroma() {
+        assert_eq!(rgb_to_hue_chroma([1.0, 0.0, 0.0]), (0.0, 1.0, 0.0, 1.0));
+        assert_eq!(
+            rgb_to_hue_chroma([0.0, 0.5, 0.0]),
+            (1.0 / 3.0, 0.5, 0.0, 0.5)
+        );
+        assert_eq!(
+            rgb_to_hue_chroma([0.25, 0.25, 0.75]),
+            (2.0 / 3.0, 0.5, 0.25, 0.75)
+        );
+        assert_eq!(rgb_to_hue_chroma([0.5, 0.5, 0.5]), (0.0, 0.0, 0.5, 0.5));
+    }
+    #[test]
+    fn from_hue_chroma() {
+        assert_eq!(rgb_from_hue_chroma(0.0, 1.0, 0.0), [1.0, 0.0, 0.0]);
+        assert_eq!(rgb_from_hue_chroma(0.5, 0.5, 0.25), [0.25, 0.75, 0.75]);
+        assert_eq!(rgb_from_hue_chroma(1.0, 0.5, 0.0), [0.5, 0.0, 0.0]);
+        assert_eq!(rgb_from_hue_chroma(5.0 / 6.0, 1.0, 0.0), [1.0, 0.0, 1.0]);
+    }
+}
@@ -0,0 +1,358 @@
+// hwb.rs       HWB color model.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{
+    self, AChannel, Opaque, Premultiplied, Straight, Translucent,
+};
+use crate::format::Model;
+use crate::gamma::{self, Linear, Srgb};
+use crate::gamut;
+use crate::hue::{
+    hue_difference, rgb_from_hue_chroma, rgb_to_f32, rgb_to_hue_chroma,
+};
+use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel};
+use std::marker::PhantomData;
+
+/// HWB hexcone [color model].
+///
+/// The components are *hue*, *whiteness* and *blackness*, with optional
+/// *[alpha]*.  When *whiteness* plus *blackness* is at least
+/// [Channel::MAX], the color is a shade of gray.  *Hue* is a turn around the
+/// hexcone, with
+/// [Channel::MIN] and [Channel::MAX] both being *red*.
+///
+/// [alpha]: alpha/trait.AChannel.html
+/// [Channel::MAX]: trait.Channel.html#associatedconstant.MAX
+/// [Channel::MIN]: trait.Channel.html#associatedconstant.MIN
+/// [color model]: trait.ColorModel.html
+///
+/// ### Convert from `Rgb`
+/// ```
+/// # use pix::*;
+/// let hwb: SHwb8 = SRgb8::new(0x00, 0xFF, 0x00).convert();
+/// assert_eq!(hwb, SHwb8::new(0x55, 0x00, 0x00));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Hwb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    components: [C; 3],
+    alpha: A,
+    mode: PhantomData<M>,
+    gamma: PhantomData<G>,
+}
+
+impl<C, A, M, G> Hwb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying *hue*,
+    /// *whiteness* and *blackness*.
+    pub fn new<H>(hue: H, whiteness: H, blackness: H) -> Self
+    where
+        C: From<H>,
+        A: From<Opaque<C>>,
+    {
+        Self::with_alpha(hue, whiteness, blackness, Opaque::default())
+    }
+    /// Create a [Translucent](alpha/struct.Translucent.html) color by
+    /// specifying *hue*, *whiteness*, *blackness* and *alpha*.
+    pub fn with_alpha<H, B>(
+        hue: H,
+        whiteness: H,
+        blackness: H,
+        alpha: B,
+    ) -> Self
+    where
+        C: From<H>,
+        A: From<B>,
+    {
+        let hue = C::from(hue);
+        let whiteness = C::from(whiteness);
+        let blackness = C::from(blackness);
+        let components = [hue, whiteness, blackness];
+        let alpha = A::from(alpha);
+        Hwb {
+            components,
+            alpha,
+            mode: PhantomData,
+            gamma: PhantomData,
+        }
+    }
+    /// Get the *hue* component.
+    pub fn hue(self) -> C {
+        self.components[0]
+    }
+    /// Get the *whiteness* component.
+    pub fn whiteness(self) -> C {
+        self.components[1]
+    }
+    /// Get the *blackness* component.
+    pub fn blackness(self) -> C {
+        self.components[2]
+    }
+}
+
+impl<C, A, M, G> ColorModel for Hwb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Chan = C;
+    const MODEL: Model = Model::Hwb;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
+
+    /// Get all non-alpha components
+    fn components(&self) -> &[Self::Chan] {
+        &self.components
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.alpha.value()
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        let hue = self.hue().into();
+        let whiteness: f32 = self.whiteness().into();
+        let blackness: f32 = self.blackness().into();
+        let total = whiteness + blackness;
+        let rgb = if total >= 1.0 {
+            let v = whiteness / total;
+            [v, v, v]
+        } else {
+            let chroma = 1.0 - total;
+            rgb_from_hue_chroma(hue, chroma, whiteness)
+        };
+        [rgb[0].into(), rgb[1].into(), rgb[2].into(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        let (hue, _, min, max) = rgb_to_hue_chroma(rgb_to_f32(rgba));
+        let whiteness = min;
+        let blackness = 1.0 - max;
+        Hwb::with_alpha(hue, whiteness, blackness, rgba[3])
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let h = hue_difference(self.hue(), rhs.hue());
+        let w = if self.whiteness() > rhs.whiteness() {
+            self.whiteness() - rhs.whiteness()
+        } else {
+            rhs.whiteness() - self.whiteness()
+        };
+        let b = if self.blackness() > rhs.blackness() {
+            self.blackness() - rhs.blackness()
+        } else {
+            rhs.blackness() - self.blackness()
+        };
+        let a = if self.alpha() > rhs.alpha() {
+            self.alpha() - rhs.alpha()
+        } else {
+            rhs.alpha() - self.alpha()
+        };
+        Hwb::with_alpha(h, w, b, a)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        self.hue() <= rhs.hue()
+            && self.whiteness() <= rhs.whiteness()
+            && self.blackness() <= rhs.blackness()
+            && self.alpha() <= rhs.alpha()
+    }
+}
+
+impl<C, A, M, G> Pixel for Hwb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Alpha = M;
+    type Gamma = G;
+    type Primaries = gamut::Srgb;
+}
+
+impl<C, A, M, G> Iterator for Hwb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<C, M, G> From<Hwb<C, Translucent<C>, M, G>> for Hwb<C, Opaque<C>, M, G>
+where
+    C: Channel,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn from(c: Hwb<C, Translucent<C>, M, G>) -> Self {
+        Hwb::new(c.hue(), c.whiteness(), c.blackness())
+    }
+}
+
+impl<C, M, G> From<Hwb<C, Opaque<C>, M, G>> for Hwb<C, Translucent<C>, M, G>
+where
+    C: Channel,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn from(c: Hwb<C, Opaque<C>, M, G>) -> Self {
+        Hwb::with_alpha(c.hue(), c.whiteness(), c.blackness(), C::MAX)
+    }
+}
+
+
+/// [Hwb](struct.Hwb.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hwb8 = Hwb<Ch8, Opaque<Ch8>, Straight, Linear>;
+/// [Hwb](struct.Hwb.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hwb16 = Hwb<Ch16, Opaque<Ch16>, Straight, Linear>;
+/// [Hwb](struct.Hwb.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hwb32 = Hwb<Ch32, Opaque<Ch32>, Straight, Linear>;
+
+/// [Hwb](struct.Hwb.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hwba8 = Hwb<Ch8, Translucent<Ch8>, Straight, Linear>;
+/// [Hwb](struct.Hwb.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hwba16 = Hwb<Ch16, Translucent<Ch16>, Straight, Linear>;
+/// [Hwb](struct.Hwb.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hwba32 = Hwb<Ch32, Translucent<Ch32>, Straight, Linear>;
+
+/// [Hwb](struct.Hwb.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hwba8p = Hwb<Ch8, Translucent<Ch8>, Premultiplied, Linear>;
+/// [Hwb](struct.Hwb.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hwba16p = Hwb<Ch16, Translucent<Ch16>, Premultiplied, Linear>;
+/// [Hwb](struct.Hwb.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Hwba32p = Hwb<Ch32, Translucent<Ch32>, Premultiplied, Linear>;
+
+/// [Hwb](struct.Hwb.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHwb8 = Hwb<Ch8, Opaque<Ch8>, Straight, Srgb>;
+/// [Hwb](struct.Hwb.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHwb16 = Hwb<Ch16, Opaque<Ch16>, Straight, Srgb>;
+/// [Hwb](struct.Hwb.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHwb32 = Hwb<Ch32, Opaque<Ch32>, Straight, Srgb>;
+
+/// [Hwb](struct.Hwb.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHwba8 = Hwb<Ch8, Translucent<Ch8>, Straight, Srgb>;
+/// [Hwb](struct.Hwb.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHwba16 = Hwb<Ch16, Translucent<Ch16>, Straight, Srgb>;
+/// [Hwb](struct.Hwb.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHwba32 = Hwb<Ch32, Translucent<Ch32>, Straight, Srgb>;
+
+/// [Hwb](struct.Hwb.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHwba8p = Hwb<Ch8, Translucent<Ch8>, Premultiplied, Srgb>;
+/// [Hwb](struct.Hwb.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHwba16p = Hwb<Ch16, Translucent<Ch16>, Premultiplied, Srgb>;
+/// [Hwb](struct.Hwb.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SHwba32p = Hwb<Ch32, Translucent<Ch32>, Premultiplied, Srgb>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<SHwb8>(), 3);
+        assert_eq!(std::mem::size_of::<SHwb16>(), 6);
+        assert_eq!(std::mem::size_of::<SHwb32>(), 12);
+        assert_eq!(std::mem::size_of::<SHwba8>(), 4);
+        assert_eq!(std::mem::size_of::<SHwba16>(), 8);
+        assert_eq!(std::mem::size_of::<SHwba32>(), 16);
+    }
+
+    #[test]
+    fn hwb_to_rgb() {
+        assert_eq!(
+            SRgb8::new(0, 255, 255),
+            SHwb32::new(0.5, 0.0, 0.0).convert(),
+        );
+        assert_eq!(
+            SRgb32::new(0.75, 0.25, 0.25),
+            SHwb32::new(0.0, 0.25, 0.25).convert(),
+        );
+        assert_eq!(
+            SRgb32::new(0.25, 0.25, 0.25),
+            SHwb32::new(0.7, 0.25, 0.75).convert(),
+        );
+        assert_eq!(
+            SRgb32::new(0.5, 0.5, 0.5),
+            SHwb32::new(0.1, 1.0, 1.0).convert(),
+        );
+    }
+
+    #[test]
+    fn rgb_to_hwb() {
+        assert_eq!(
+            SHwb32::new(0.0, 0.0, 0.0),
+            SRgb32::new(1.0, 0.0, 0.0).convert(),
+        );
+        assert_eq!(
+            SHwb32::new(0.5, 0.5, 0.0),
+            SRgb32::new(0.5, 1.0, 1.0).convert(),
+        );
+        assert_eq!(
+            SHwb32::new(0.0, 0.25, 0.75),
+            SRgb32::new(0.25, 0.25, 0.25).convert(),
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb =
+                        SRgb16::new(Ch8::new(r), Ch8::new(g), Ch8::new(b));
+                    let hwb: SHwb32 = rgb.convert();
+                    assert_eq!(rgb, hwb.convert());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,614 @@
+// icc.rs       ICC color profiles.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+//! Module for ICC profile items
+use crate::alpha::Mode as _;
+use crate::gamma::Mode as _;
+use crate::gamut;
+use crate::matrix::{mul_vec, Matrix3};
+use crate::white::{Point, D50};
+use crate::{Conversion, Pixel, Raster, RasterBuilder};
+use std::fmt;
+
+/// Size of profile header, in bytes
+const HEADER_SIZE: usize = 128;
+
+/// Error parsing an ICC profile
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Profile data is too short, or a tag is out of bounds
+    Truncated,
+    /// Missing `acsp` profile file signature
+    InvalidSignature,
+    /// Profile version is not 2 or 4
+    UnsupportedVersion(u8),
+    /// Color space or PCS is not supported
+    UnsupportedColorSpace([u8; 4]),
+    /// A required tag is missing
+    MissingTag([u8; 4]),
+    /// Tag type is not supported
+    UnsupportedType([u8; 4]),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Truncated => write!(f, "truncated profile"),
+            Error::InvalidSignature => write!(f, "invalid profile signature"),
+            Error::UnsupportedVersion(v) => {
+                write!(f, "unsupported profile version: {}", v)
+            }
+            Error::UnsupportedColorSpace(s) => {
+                write!(f, "unsupported color space: {}", sig_str(s))
+            }
+            Error::MissingTag(s) => write!(f, "missing tag: {}", sig_str(s)),
+            Error::UnsupportedType(s) => {
+                write!(f, "unsupported tag type: {}", sig_str(s))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Format a 4-byte signature
+fn sig_str(sig: &[u8; 4]) -> String {
+    String::from_utf8_lossy(sig).trim_end().to_string()
+}
+
+/// Tone reproduction curve (TRC), from device values to linear light.
+///
+/// Inputs and outputs are normalized to the range 0 to 1.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Curve {
+    /// Identity curve (`curv` type with no entries)
+    Identity,
+    /// Power law with an exponent (`curv` type with one entry)
+    Gamma(f64),
+    /// Sampled table, interpolated linearly (`curv` type)
+    Table(Vec<u16>),
+    /// Parametric curve (`para` type), with parameters *g*, *a*, *b*, *c*,
+    /// *d*, *e* and *f* of function type 4.  Other function types are
+    /// converted to function type 4 when parsed.
+    Parametric([f64; 7]),
+}
+
+impl Curve {
+    /// Parse a curve from tag data
+    fn parse(data: &[u8]) -> Result<Self, Error> {
+        match sig(data, 0)? {
+            [b'c', b'u', b'r', b'v'] => {
+                let count = u32_at(data, 8)? as usize;
+                match count {
+                    0 => Ok(Curve::Identity),
+                    1 => {
+                        let g = f64::from(u16_at(data, 12)?) / 256.0;
+                        Ok(Curve::Gamma(g))
+                    }
+                    _ => {
+                        let table = (0..count)
+                            .map(|i| u16_at(data, 12 + i * 2))
+                            .collect::<Result<_, _>>()?;
+                        Ok(Curve::Table(table))
+                    }
+                }
+            }
+            [b'p', b'a', b'r', b'a'] => {
+                let function = u16_at(data, 8)?;
+                let count = match function {
+                    0 => 1,
+                    1 => 3,
+                    2 => 4,
+                    3 => 5,
+                    4 => 7,
+                    _ => return Err(Error::UnsupportedType(*b"para")),
+                };
+                let mut p = [0.0; 7];
+                for (i, v) in p.iter_mut().take(count).enumerate() {
+                    *v = s15_fixed16(data, 12 + i * 4)?;
+                }
+                let [g, a, b, c, d, e, f] = p;
+                // Convert to function type 4
+                let d0 = if a != 0.0 { -b / a } else { 0.0 };
+                let p = match function {
+                    0 => [g, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+                    1 => [g, a, b, 0.0, d0, 0.0, 0.0],
+                    2 => [g, a, b, 0.0, d0, c, c],
+                    3 => [g, a, b, c, d, 0.0, 0.0],
+                    _ => [g, a, b, c, d, e, f],
+                };
+                Ok(Curve::Parametric(p))
+            }
+            s => Err(Error::UnsupportedType(s)),
+        }
+    }
+
+    /// Evaluate the curve.
+    ///
+    /// * `x` Device value, from 0 to 1.
+    ///
+    /// ```
+    /// # use pix::icc::Curve;
+    /// assert_eq!(Curve::Gamma(2.0).eval(0.5), 0.25);
+    /// assert_eq!(Curve::Table(vec![0x0000, 0xFFFF]).eval(0.5), 0.5);
+    /// ```
+    pub fn eval(&self, x: f64) -> f64 {
+        let x = x.clamp(0.0, 1.0);
+        match self {
+            Curve::Identity => x,
+            Curve::Gamma(g) => x.powf(*g),
+            Curve::Table(t) => match t.len() {
+                0 => x,
+                1 => f64::from(t[0]) / 65535.0,
+                n => {
+                    let pos = x * (n - 1) as f64;
+                    let i = (pos as usize).min(n - 2);
+                    let frac = pos - i as f64;
+                    let y0 = f64::from(t[i]);
+                    let y1 = f64::from(t[i + 1]);
+                    (y0 + (y1 - y0) * frac) / 65535.0
+                }
+            },
+            Curve::Parametric([g, a, b, c, d, e, f]) => {
+                if x >= *d {
+                    (a * x + b).max(0.0).powf(*g) + e
+                } else {
+                    c * x + f
+                }
+            }
+        }
+    }
+}
+
+/// ICC color profile (version 2 or 4).
+///
+/// Matrix/TRC RGB profiles and gray TRC profiles are supported, with an
+/// *XYZ* profile connection space (PCS).  Pixels are converted with the
+/// relative colorimetric intent: device values are decoded by the tone
+/// reproduction [curves], transformed to the PCS, then adapted to the white
+/// point of the destination [primaries].  [Conversion] settings select the
+/// gamut mapping and chromatic adaptation.
+///
+/// [Conversion]: ../struct.Conversion.html
+/// [curves]: enum.Curve.html
+/// [primaries]: ../gamut/trait.Primaries.html
+///
+/// ### Convert a raster from an embedded profile to sRGB
+/// ```no_run
+/// # use pix::*;
+/// # use pix::icc::Profile;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let profile = Profile::parse(&std::fs::read("camera.icc")?)?;
+/// let src = RasterBuilder::<Rgb8>::new().with_clear(64, 64);
+/// let dst: Raster<SRgb8> = profile.convert_raster(&src);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    /// Major and minor version
+    version: (u8, u8),
+    /// PCS illuminant
+    illuminant: [f64; 3],
+    /// Media white point
+    white_point: [f64; 3],
+    /// Matrix from linear device RGB to PCS (RGB profiles only)
+    matrix: Option<Matrix3>,
+    /// Tone reproduction curves
+    curves: Vec<Curve>,
+    /// Conversion settings
+    conversion: Conversion,
+}
+
+impl Profile {
+    /// Parse a profile from bytes.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        if data.len() < HEADER_SIZE + 4 {
+            return Err(Error::Truncated);
+        }
+        let size = u32_at(data, 0)? as usize;
+        let data = data.get(..size).ok_or(Error::Truncated)?;
+        if &sig(data, 36)? != b"acsp" {
+            return Err(Error::InvalidSignature);
+        }
+        let version = (data[8], data[9] >> 4);
+        if version.0 != 2 && version.0 != 4 {
+            return Err(Error::UnsupportedVersion(version.0));
+        }
+        let pcs = sig(data, 20)?;
+        if &pcs != b"XYZ " {
+            return Err(Error::UnsupportedColorSpace(pcs));
+        }
+        let mut illuminant = xyz_at(data, 68)?;
+        if illuminant[1] <= 0.0 {
+            illuminant = D50::XYZ;
+        }
+        let tags = Tags::new(data)?;
+        let white_point = match tags.find(*b"wtpt") {
+            Ok(t) => xyz_tag(t)?,
+            Err(_) => illuminant,
+        };
+        let (matrix, curves) = match &sig(data, 16)? {
+            b"RGB " => {
+                let r = xyz_tag(tags.find(*b"rXYZ")?)?;
+                let g = xyz_tag(tags.find(*b"gXYZ")?)?;
+                let b = xyz_tag(tags.find(*b"bXYZ")?)?;
+                let matrix = [
+                    [r[0], g[0], b[0]],
+                    [r[1], g[1], b[1]],
+                    [r[2], g[2], b[2]],
+                ];
+                let curves = vec![
+                    Curve::parse(tags.find(*b"rTRC")?)?,
+                    Curve::parse(tags.find(*b"gTRC")?)?,
+                    Curve::parse(tags.find(*b"bTRC")?)?,
+                ];
+                (Some(matrix), curves)
+            }
+            b"GRAY" => (None, vec![Curve::parse(tags.find(*b"kTRC")?)?]),
+            s => return Err(Error::UnsupportedColorSpace(*s)),
+        };
+        Ok(Profile {
+            version,
+            illuminant,
+            white_point,
+            matrix,
+            curves,
+            conversion: Conversion::default(),
+        })
+    }
+    /// Set the conversion settings.
+    pub fn with_conversion(mut self, conversion: Conversion) -> Self {
+        self.conversion = conversion;
+        self
+    }
+    /// Get the conversion settings.
+    pub fn conversion(&self) -> Conversion {
+        self.conversion
+    }
+    /// Get the major and minor version.
+    pub fn version(&self) -> (u8, u8) {
+        self.version
+    }
+    /// Get the media white point (*XYZ*).
+    pub fn white_point(&self) -> [f64; 3] {
+        self.white_point
+    }
+    /// Get the matrix from linear device RGB to PCS *XYZ*.
+    ///
+    /// Returns `None` for gray profiles.
+    pub fn matrix(&self) -> Option<[[f64; 3]; 3]> {
+        self.matrix
+    }
+    /// Get the tone reproduction curves.
+    ///
+    /// RGB profiles have three curves; gray profiles have one.
+    pub fn curves(&self) -> &[Curve] {
+        &self.curves
+    }
+    /// Convert a pixel from the profile to another format.
+    ///
+    /// * `S` `Pixel` format of source, containing device values.  Its gamma
+    ///   mode and primaries are ignored.  For gray profiles, only the
+    ///   *red* component (or *value* of gray pixels) is used.
+    /// * `D` Destination format.
+    pub fn convert<S, D>(&self, src: S) -> D
+    where
+        S: Pixel,
+        D: Pixel,
+    {
+        let rgba = src.to_rgba_f64();
+        let alpha = rgba[3];
+        // Decode device values to linear, straight alpha
+        let linear = |c: f64, curve: &Curve| {
+            S::Alpha::decode_f64(curve.eval(c), alpha)
+        };
+        let xyz = match self.matrix {
+            Some(m) => {
+                let mut rgb = [0.0; 3];
+                for (v, (c, curve)) in
+                    rgb.iter_mut().zip(rgba.iter().zip(&self.curves))
+                {
+                    *v = linear(*c, curve);
+                }
+                mul_vec(&m, rgb)
+            }
+            None => {
+                let y = linear(rgba[0], &self.curves[0]);
+                let w = self.illuminant;
+                [w[0] * y, w[1] * y, w[2] * y]
+            }
+        };
+        let rgb = gamut::from_xyz::<D::Primaries>(
+            xyz,
+            self.illuminant,
+            self.conversion.mapping(),
+            self.conversion.adaptation(),
+        );
+        // Encode, without quantizing for tristimulus destinations
+        let encode = |c: f64| {
+            D::Gamma::from_linear_f64(D::Alpha::encode_f64(c, alpha))
+        };
+        D::with_rgba_f64([
+            encode(rgb[0]),
+            encode(rgb[1]),
+            encode(rgb[2]),
+            alpha,
+        ])
+    }
+    /// Convert a `Raster` from the profile to another format.
+    ///
+    /// * `S` `Pixel` format of source `Raster`, containing device values.
+    /// * `D` `Pixel` format of destination `Raster`.
+    pub fn convert_raster<S, D>(&self, src: &Raster<S>) -> Raster<D>
+    where
+        S: Pixel,
+        D: Pixel,
+    {
+        RasterBuilder::new().with_map(src, |p| self.convert(p))
+    }
+}
+
+/// Profile tag table
+struct Tags<'a> {
+    /// Profile data
+    data: &'a [u8],
+    /// Number of tags
+    count: usize,
+}
+
+impl<'a> Tags<'a> {
+    /// Create a tag table
+    fn new(data: &'a [u8]) -> Result<Self, Error> {
+        let count = u32_at(data, HEADER_SIZE)? as usize;
+        let end = count
+            .checked_mul(12)
+            .and_then(|n| n.checked_add(HEADER_SIZE + 4))
+            .ok_or(Error::Truncated)?;
+        if end > data.len() {
+            return Err(Error::Truncated);
+        }
+        Ok(Tags { data, count })
+    }
+
+    /// Find data for a tag
+    fn find(&self, tag: [u8; 4]) -> Result<&'a [u8], Error> {
+        for i in 0..self.count {
+            let pos = HEADER_SIZE + 4 + i * 12;
+            if sig(self.data, pos)? == tag {
+                let offset = u32_at(self.data, pos + 4)? as usize;
+                let size = u32_at(self.data, pos + 8)? as usize;
+                let end = offset.checked_add(size).ok_or(Error::Truncated)?;
+                return self.data.get(offset..end).ok_or(Error::Truncated);
+            }
+        }
+        Err(Error::MissingTag(tag))
+    }
+}
+
+/// Get a 4-byte signature
+fn sig(data: &[u8], pos: usize) -> Result<[u8; 4], Error> {
+    let b = data.get(pos..pos + 4).ok_or(Error::Truncated)?;
+    Ok([b[0], b[1], b[2], b[3]])
+}
+
+/// Get a big-endian `u16`
+fn u16_at(data: &[u8], pos: usize) -> Result<u16, Error> {
+    let b = data.get(pos..pos + 2).ok_or(Error::Truncated)?;
+    Ok(u16::from_be_bytes([b[0], b[1]]))
+}
+
+/// Get a big-endian `u32`
+fn u32_at(data: &[u8], pos: usize) -> Result<u32, Error> {
+    Ok(u32::from_be_bytes(sig(data, pos)?))
+}
+
+/// Get an `s15Fixed16Number`
+fn s15_fixed16(data: &[u8], pos: usize) -> Result<f64, Error> {
+    Ok(u32_at(data, pos)? as i32 as f64 / 65536.0)
+}
+
+/// Get an `XYZNumber`
+fn xyz_at(data: &[u8], pos: usize) -> Result<[f64; 3], Error> {
+    Ok([
+        s15_fixed16(data, pos)?,
+        s15_fixed16(data, pos + 4)?,
+        s15_fixed16(data, pos + 8)?,
+    ])
+}
+
+/// Parse an `XYZ ` type tag
+fn xyz_tag(data: &[u8]) -> Result<[f64; 3], Error> {
+    match sig(data, 0)? {
+        [b'X', b'Y', b'Z', b' '] => xyz_at(data, 8),
+        s => Err(Error::UnsupportedType(s)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::*;
+    use super::Error;
+
+    fn s15(v: f64) -> [u8; 4] {
+        ((v * 65536.0).round() as i32).to_be_bytes()
+    }
+
+    fn xyz(v: [f64; 3]) -> Vec<u8> {
+        let mut d = b"XYZ \0\0\0\0".to_vec();
+        v.iter().for_each(|v| d.extend(&s15(*v)));
+        d
+    }
+
+    fn curv(t: &[u16]) -> Vec<u8> {
+        let mut d = b"curv\0\0\0\0".to_vec();
+        d.extend(&(t.len() as u32).to_be_bytes());
+        t.iter().for_each(|v| d.extend(&v.to_be_bytes()));
+        d
+    }
+
+    fn para(function: u16, p: &[f64]) -> Vec<u8> {
+        let mut d = b"para\0\0\0\0".to_vec();
+        d.extend(&function.to_be_bytes());
+        d.extend(&[0, 0]);
+        p.iter().for_each(|v| d.extend(&s15(*v)));
+        d
+    }
+
+    fn profile(space: &[u8; 4], tags: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+        let mut d = vec![0; HEADER_SIZE];
+        d[8] = 4;
+        d[9] = 0x30;
+        d[12..16].copy_from_slice(b"mntr");
+        d[16..20].copy_from_slice(space);
+        d[20..24].copy_from_slice(b"XYZ ");
+        d[36..40].copy_from_slice(b"acsp");
+        for (i, v) in D50::XYZ.iter().enumerate() {
+            d[68 + i * 4..72 + i * 4].copy_from_slice(&s15(*v));
+        }
+        d.extend(&(tags.len() as u32).to_be_bytes());
+        let base = HEADER_SIZE + 4 + tags.len() * 12;
+        let mut body = vec![];
+        for (tag, data) in tags {
+            d.extend(tag.iter());
+            d.extend(&((base + body.len()) as u32).to_be_bytes());
+            d.extend(&(data.len() as u32).to_be_bytes());
+            body.extend(data);
+            while body.len() % 4 != 0 {
+                body.push(0);
+            }
+        }
+        d.extend(body);
+        let size = (d.len() as u32).to_be_bytes();
+        d[0..4].copy_from_slice(&size);
+        d
+    }
+
+    fn srgb_profile() -> Vec<u8> {
+        let trc = para(
+            3,
+            &[2.4, 1.0 / 1.055, 0.055 / 1.055, 1.0 / 12.92, 0.040_45],
+        );
+        profile(
+            b"RGB ",
+            &[
+                (b"wtpt", xyz(D50::XYZ)),
+                (b"rXYZ", xyz([0.436_1, 0.222_5, 0.013_9])),
+                (b"gXYZ", xyz([0.385_1, 0.716_9, 0.097_1])),
+                (b"bXYZ", xyz([0.143_1, 0.060_6, 0.714_1])),
+                (b"rTRC", trc.clone()),
+                (b"gTRC", trc.clone()),
+                (b"bTRC", trc),
+            ],
+        )
+    }
+
+    #[test]
+    fn curves() {
+        let c = Curve::parse(&curv(&[])).unwrap();
+        assert_eq!(c, Curve::Identity);
+        let c = Curve::parse(&curv(&[0x0233])).unwrap();
+        assert!((c.eval(0.5) - 0.5_f64.powf(2.2)).abs() < 1e-3);
+        let c = Curve::parse(&curv(&[0, 0x1000, 0xFFFF])).unwrap();
+        assert_eq!(c.eval(0.0), 0.0);
+        assert_eq!(c.eval(1.0), 1.0);
+        let c = Curve::parse(&para(0, &[2.0])).unwrap();
+        assert_eq!(c.eval(0.5), 0.25);
+        let c = Curve::parse(&para(1, &[1.0, 2.0, -1.0])).unwrap();
+        assert_eq!(c.eval(0.25), 0.0);
+        assert_eq!(c.eval(0.75), 0.5);
+        let c = Curve::parse(&para(2, &[1.0, 2.0, -1.0, 0.25])).unwrap();
+        assert_eq!(c.eval(0.25), 0.25);
+        assert_eq!(c.eval(0.75), 0.75);
+        let p = [1.0, 1.0, 0.0, 0.5, 0.5, 0.1, 0.2];
+        let c = Curve::parse(&para(4, &p)).unwrap();
+        assert!((c.eval(0.25) - 0.325).abs() < 1e-4);
+        assert!((c.eval(0.75) - 0.85).abs() < 1e-4);
+        let e = Curve::parse(&para(5, &[1.0]));
+        assert_eq!(e, Err(Error::UnsupportedType(*b"para")));
+    }
+
+    #[test]
+    fn rgb_profile() {
+        let profile = Profile::parse(&srgb_profile()).unwrap();
+        assert_eq!(profile.version(), (4, 3));
+        assert_eq!(profile.curves().len(), 3);
+        assert!(profile.matrix().is_some());
+        for v in (0..=255).step_by(15) {
+            let p: SRgb8 = profile.convert(Rgb8::new(v, v, v));
+            let d = p.difference(SRgb8::new(v, v, v));
+            assert!(d.components().iter().all(|c| u8::from(*c) <= 1));
+        }
+        let src = RasterBuilder::<Rgba8>::new().with_pixels(
+            2,
+            1,
+            vec![
+                Rgba8::with_alpha(0xFF, 0x00, 0x00, 0x80),
+                Rgba8::with_alpha(0x20, 0x80, 0xC0, 0xFF),
+            ],
+        );
+        let dst: Raster<SRgba8> = profile.convert_raster(&src);
+        for (s, d) in src.as_slice().iter().zip(dst.as_slice()) {
+            let s = SRgba8::with_rgba(s.to_rgba());
+            let diff = s.difference(*d);
+            assert!(diff.to_rgba().iter().all(|c| u8::from(*c) <= 1));
+        }
+        let dst: Raster<P3Rgba8> = profile.convert_raster(&src);
+        let red: P3Rgba8 = SRgba8::with_alpha(0xFF, 0x00, 0x00, 0x80).convert();
+        let diff = red.difference(dst.pixel(0, 0));
+        assert!(diff.to_rgba().iter().all(|c| u8::from(*c) <= 1));
+    }
+
+    #[test]
+    fn tristimulus() {
+        let profile = Profile::parse(&srgb_profile()).unwrap();
+        for p in [
+            SRgb8::new(0x20, 0x10, 0x08),
+            SRgb8::new(0x00, 0x80, 0xFF),
+            SRgb8::new(0xFF, 0xFF, 0xFF),
+        ] {
+            let src = Rgb8::new(p.red(), p.green(), p.blue());
+            let lab: Lab8 = profile.convert(src);
+            let diff = lab.difference(p.convert());
+            assert!(diff.components().iter().all(|c| u8::from(*c) <= 1));
+            let lch: Lch8 = profile.convert(src);
+            let diff = lch.difference(p.convert());
+            assert!(u8::from(diff.lightness()) <= 1);
+            assert!(u8::from(diff.chroma()) <= 1);
+        }
+    }
+
+    #[test]
+    fn gray_profile() {
+        let data = profile(b"GRAY", &[(b"kTRC", curv(&[0x0233]))]);
+        let profile = Profile::parse(&data).unwrap();
+        assert_eq!(profile.matrix(), None);
+        assert_eq!(profile.white_point(), profile.illuminant);
+        let p: Gray16 = profile.convert(Gray8::new(0x80));
+        let e = (128.0_f32 / 255.0).powf(563.0 / 256.0) * 65535.0;
+        assert!((f32::from(u16::from(p.value())) - e).abs() < 16.0);
+    }
+
+    #[test]
+    fn errors() {
+        let data = srgb_profile();
+        assert_eq!(Profile::parse(&data[..100]), Err(Error::Truncated));
+        assert_eq!(Profile::parse(&data[..200]), Err(Error::Truncated));
+        let mut d = data.clone();
+        d[36] = b'x';
+        assert_eq!(Profile::parse(&d), Err(Error::InvalidSignature));
+        let mut d = data.clone();
+        d[8] = 3;
+        assert_eq!(Profile::parse(&d), Err(Error::UnsupportedVersion(3)));
+        let mut d = data.clone();
+        d[16..20].copy_from_slice(b"CMYK");
+        let e = Profile::parse(&d);
+        assert_eq!(e, Err(Error::UnsupportedColorSpace(*b"CMYK")));
+        let data = profile(b"RGB ", &[(b"rXYZ", xyz(D50::XYZ))]);
+        let e = Profile::parse(&data);
+        assert_eq!(e, Err(Error::MissingTag(*b"gXYZ")));
+        assert_eq!(e.unwrap_err().to_string(), "missing tag: gXYZ");
+    }
+}
@@ -0,0 +1,445 @@
+// lab.rs       CIE L*a*b* color model.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{
+    self, AChannel, Opaque, Premultiplied, Straight, Translucent,
+};
+use crate::format::Model;
+use crate::gamma::Linear;
+use crate::gamut::CieXyz;
+use crate::white::{self, D65};
+use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel};
+use std::marker::PhantomData;
+
+/// CIE threshold between linear and cube root segments (6/29)³
+const EPSILON: f64 = 216.0 / 24389.0;
+
+/// CIE slope of linear segment (29/3)³
+const KAPPA: f64 = 24389.0 / 27.0;
+
+/// Offset of *a\** and *b\** components when stored in a `Channel`
+const AB_OFFSET: f64 = 128.0;
+
+/// Scale of *a\** and *b\** components when stored in a `Channel`
+const AB_SCALE: f64 = 255.0;
+
+/// Compress a relative tristimulus value
+fn lab_f(t: f64) -> f64 {
+    if t > EPSILON {
+        t.cbrt()
+    } else {
+        (KAPPA * t + 16.0) / 116.0
+    }
+}
+
+/// Expand a compressed tristimulus value
+fn lab_f_inv(f: f64) -> f64 {
+    let t = f * f * f;
+    if t > EPSILON {
+        t
+    } else {
+        (116.0 * f - 16.0) / KAPPA
+    }
+}
+
+/// Convert relative *XYZ* to *L\**, *a\** and *b\**.
+///
+/// *L\** ranges from 0 to 100, while *a\** and *b\** are unbounded.
+pub(crate) fn xyz_to_lab(xyz: [f64; 3]) -> [f64; 3] {
+    let fx = lab_f(xyz[0]);
+    let fy = lab_f(xyz[1]);
+    let fz = lab_f(xyz[2]);
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Convert *L\**, *a\** and *b\** to relative *XYZ*.
+pub(crate) fn lab_to_xyz(lab: [f64; 3]) -> [f64; 3] {
+    let fy = (lab[0] + 16.0) / 116.0;
+    let fx = fy + lab[1] / 500.0;
+    let fz = fy - lab[2] / 200.0;
+    let y = if lab[0] > KAPPA * EPSILON {
+        fy * fy * fy
+    } else {
+        lab[0] / KAPPA
+    };
+    [lab_f_inv(fx), y, lab_f_inv(fz)]
+}
+
+/// CIE 1976 L\*a\*b\* perceptual [color model].
+///
+/// The components are *L\** (lightness), *a\** (green to red) and *b\**
+/// (blue to yellow), with optional *[alpha]*.  Colors are relative to a
+/// [white point].
+///
+/// *L\** from 0 to 100 is stored in the full `Channel` range.  *a\** and
+/// *b\** from -128 to 127 are stored with an offset of 128, scaled by 1/255
+/// (like ICC 8-bit encoding), so neutral colors have a `Ch8` value of 128.
+///
+/// Channels clamped to the range 0 to 1 cannot store *a\** or *b\** outside
+/// of -128 to 127.  This covers the sRGB and Display P3 gamuts, but not
+/// wider ones, such as Rec. 2020 green (*a\** of -172).  Unclamped channels
+/// ([Ch16f] and [Ch32h]) store any value with the same encoding.
+///
+/// [alpha]: alpha/trait.AChannel.html
+/// [Ch16f]: struct.Ch16f.html
+/// [Ch32h]: struct.Ch32h.html
+/// [color model]: trait.ColorModel.html
+/// [white point]: white/trait.Point.html
+///
+/// ### Convert from `Rgb`
+/// ```
+/// # use pix::*;
+/// let lab: Lab8 = SRgb8::new(0xFF, 0xFF, 0xFF).convert();
+/// assert_eq!(lab, Lab8::new(0xFF, 0x80, 0x80));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Lab<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    components: [C; 3],
+    alpha: A,
+    mode: PhantomData<M>,
+    white: PhantomData<W>,
+}
+
+impl<C, A, M, W> Lab<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying
+    /// *L\**, *a\** and *b\** values.
+    pub fn new<H>(l: H, a: H, b: H) -> Self
+    where
+        C: From<H>,
+        A: From<Opaque<C>>,
+    {
+        Self::with_alpha(l, a, b, Opaque::default())
+    }
+    /// Create a [Translucent](alpha/struct.Translucent.html) color by
+    /// specifying *L\**, *a\**, *b\** and *alpha* values.
+    pub fn with_alpha<H, B>(l: H, a: H, b: H, alpha: B) -> Self
+    where
+        C: From<H>,
+        A: From<B>,
+    {
+        let components = [C::from(l), C::from(a), C::from(b)];
+        let alpha = A::from(alpha);
+        Lab {
+            components,
+            alpha,
+            mode: PhantomData,
+            white: PhantomData,
+        }
+    }
+    /// Get the *L\** (lightness) component.
+    pub fn l(self) -> C {
+        self.components[0]
+    }
+    /// Get the *a\** (green to red) component.
+    pub fn a(self) -> C {
+        self.components[1]
+    }
+    /// Get the *b\** (blue to yellow) component.
+    pub fn b(self) -> C {
+        self.components[2]
+    }
+}
+
+impl<C, A, M, W> ColorModel for Lab<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    type Chan = C;
+    const MODEL: Model = Model::Lab;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
+
+    /// Get all non-alpha components
+    fn components(&self) -> &[Self::Chan] {
+        &self.components
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.alpha.value()
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        let rgba = self.to_rgba_f64();
+        [rgba[0].into(), rgba[1].into(), rgba[2].into(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        let rgb = [rgba[0].into(), rgba[1].into(), rgba[2].into()];
+        Self::with_rgba_f64([rgb[0], rgb[1], rgb[2], rgba[3].into()])
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components, without
+    /// quantizing to the `Channel` type
+    fn to_rgba_f64(self) -> [f64; 4] {
+        let l: f64 = self.l().into();
+        let a: f64 = self.a().into();
+        let b: f64 = self.b().into();
+        let a = a * AB_SCALE - AB_OFFSET;
+        let b = b * AB_SCALE - AB_OFFSET;
+        let xyz = lab_to_xyz([l * 100.0, a, b]);
+        [xyz[0], xyz[1], xyz[2], self.alpha().into()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components, without
+    /// quantizing them to the `Channel` type first
+    fn with_rgba_f64(rgba: [f64; 4]) -> Self {
+        let lab = xyz_to_lab([rgba[0], rgba[1], rgba[2]]);
+        let l = lab[0] / 100.0;
+        let a = (lab[1] + AB_OFFSET) / AB_SCALE;
+        let b = (lab[2] + AB_OFFSET) / AB_SCALE;
+        Lab::with_alpha(l, a, b, C::from(rgba[3]))
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let l = if self.l() > rhs.l() {
+            self.l() - rhs.l()
+        } else {
+            rhs.l() - self.l()
+        };
+        let a = if self.a() > rhs.a() {
+            self.a() - rhs.a()
+        } else {
+            rhs.a() - self.a()
+        };
+        let b = if self.b() > rhs.b() {
+            self.b() - rhs.b()
+        } else {
+            rhs.b() - self.b()
+        };
+        let alpha = if self.alpha() > rhs.alpha() {
+            self.alpha() - rhs.alpha()
+        } else {
+            rhs.alpha() - self.alpha()
+        };
+        Lab::with_alpha(l, a, b, alpha)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        self.l() <= rhs.l()
+            && self.a() <= rhs.a()
+            && self.b() <= rhs.b()
+            && self.alpha() <= rhs.alpha()
+    }
+}
+
+impl<C, A, M, W> Pixel for Lab<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    type Alpha = M;
+    type Gamma = Linear;
+    type Primaries = CieXyz<W>;
+}
+
+impl<C, A, M, W> Iterator for Lab<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<C, M, W> From<Lab<C, Translucent<C>, M, W>> for Lab<C, Opaque<C>, M, W>
+where
+    C: Channel,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    fn from(c: Lab<C, Translucent<C>, M, W>) -> Self {
+        Lab::new(c.l(), c.a(), c.b())
+    }
+}
+
+impl<C, M, W> From<Lab<C, Opaque<C>, M, W>> for Lab<C, Translucent<C>, M, W>
+where
+    C: Channel,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    fn from(c: Lab<C, Opaque<C>, M, W>) -> Self {
+        Lab::with_alpha(c.l(), c.a(), c.b(), C::MAX)
+    }
+}
+
+/// [Lab](struct.Lab.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Lab8 = Lab<Ch8, Opaque<Ch8>, Straight, D65>;
+/// [Lab](struct.Lab.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Lab16 = Lab<Ch16, Opaque<Ch16>, Straight, D65>;
+/// [Lab](struct.Lab.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+///
+/// *a\** and *b\** are limited to -128 to 127 (see [Lab](struct.Lab.html)).
+pub type Lab32 = Lab<Ch32, Opaque<Ch32>, Straight, D65>;
+
+/// [Lab](struct.Lab.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Laba8 = Lab<Ch8, Translucent<Ch8>, Straight, D65>;
+/// [Lab](struct.Lab.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Laba16 = Lab<Ch16, Translucent<Ch16>, Straight, D65>;
+/// [Lab](struct.Lab.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+///
+/// *a\** and *b\** are limited to -128 to 127 (see [Lab](struct.Lab.html)).
+pub type Laba32 = Lab<Ch32, Translucent<Ch32>, Straight, D65>;
+
+/// [Lab](struct.Lab.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Laba8p = Lab<Ch8, Translucent<Ch8>, Premultiplied, D65>;
+/// [Lab](struct.Lab.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Laba16p = Lab<Ch16, Translucent<Ch16>, Premultiplied, D65>;
+/// [Lab](struct.Lab.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+///
+/// *a\** and *b\** are limited to -128 to 127 (see [Lab](struct.Lab.html)).
+pub type Laba32p = Lab<Ch32, Translucent<Ch32>, Premultiplied, D65>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+    use super::*;
+    use crate::white::D50;
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<Lab8>(), 3);
+        assert_eq!(std::mem::size_of::<Lab16>(), 6);
+        assert_eq!(std::mem::size_of::<Lab32>(), 12);
+        assert_eq!(std::mem::size_of::<Laba8>(), 4);
+        assert_eq!(std::mem::size_of::<Laba16>(), 8);
+        assert_eq!(std::mem::size_of::<Laba32>(), 16);
+    }
+
+    #[test]
+    fn xyz_lab() {
+        let lab = xyz_to_lab([1.0, 1.0, 1.0]);
+        assert_eq!(lab, [100.0, 0.0, 0.0]);
+        let lab = xyz_to_lab([0.0, 0.0, 0.0]);
+        assert_eq!(lab, [0.0, 0.0, 0.0]);
+        let xyz = lab_to_xyz([50.0, 20.0, -30.0]);
+        let lab = xyz_to_lab(xyz);
+        assert!((lab[0] - 50.0).abs() < 1e-4);
+        assert!((lab[1] - 20.0).abs() < 1e-4);
+        assert!((lab[2] + 30.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn rgb_to_lab() {
+        assert_eq!(Lab8::new(136, 208, 195), SRgb8::new(0xFF, 0, 0).convert());
+        assert_eq!(Lab8::new(0, 128, 128), SRgb8::new(0, 0, 0).convert());
+        assert_eq!(
+            Lab16::new(35117, 32896, 32896),
+            SRgb8::new(0x80, 0x80, 0x80).convert()
+        );
+        type Lab8D50 = Lab<Ch8, Opaque<Ch8>, Straight, D50>;
+        assert_eq!(
+            Lab8D50::new(138, 209, 198),
+            SRgb8::new(0xFF, 0, 0).convert()
+        );
+        assert_eq!(
+            Lab8D50::new(0xFF, 128, 128),
+            SRgb8::new(0xFF, 0xFF, 0xFF).convert()
+        );
+    }
+
+    #[test]
+    fn lab_to_rgb() {
+        assert_eq!(
+            SRgb8::new(0xFF, 0x02, 0x01),
+            Lab8::new(136, 208, 195).convert()
+        );
+        assert_eq!(
+            SRgb8::new(0x80, 0x80, 0x80),
+            Lab16::new(35117, 32896, 32896).convert()
+        );
+    }
+
+    #[test]
+    fn adaptation() {
+        use crate::white::Adaptation;
+        type Lab16D50 = Lab<Ch16, Opaque<Ch16>, Straight, D50>;
+        let lab = Lab16D50::new(0x8000, 0xA000, 0x6000);
+        let conv = |a| -> SRgb16 {
+            Conversion::default().with_adaptation(a).convert(lab)
+        };
+        let b = conv(Adaptation::Bradford);
+        let c = conv(Adaptation::Cat02);
+        let v = conv(Adaptation::VonKries);
+        assert_eq!(b, SRgb16::new(38605, 25541, 44941));
+        assert!(b != c && b != v && c != v);
+    }
+
+    #[test]
+    fn shadows() {
+        let lab: Vec<Lab8> = [1, 4, 8, 12, 16]
+            .iter()
+            .map(|g| SRgb8::new(*g, *g, *g).convert())
+            .collect();
+        for (a, b) in lab.iter().zip(lab.iter().skip(1)) {
+            assert!(a.l() < b.l());
+        }
+        let lab: Lab16 = SRgb8::new(12, 12, 12).convert();
+        assert_eq!(SRgb8::new(12, 12, 12), lab.convert());
+    }
+
+    #[test]
+    fn wide_gamut() {
+        // Rec. 2020 green has an a* below -128
+        type Lab32h = Lab<Ch32h, Opaque<Ch32h>, Straight, D65>;
+        let p = Rec2020Rgb16::new(0, 0xFFFF, 0);
+        let lab: Lab32h = p.convert();
+        assert!(f32::from(lab.a()) * 255.0 - 128.0 < -128.0);
+        let back: Rec2020Rgb16 = lab.convert();
+        assert_eq!(back, p);
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = SRgb8::new(r, g, b);
+                    let lab: Lab32 = rgb.convert();
+                    let back: SRgb16 = lab.convert();
+                    assert_eq!(rgb, back.convert());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,365 @@
+// lch.rs       CIE LCh color model.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{
+    self, AChannel, Opaque, Premultiplied, Straight, Translucent,
+};
+use crate::format::Model;
+use crate::gamma::Linear;
+use crate::gamut::CieXyz;
+use crate::hue::hue_difference;
+use crate::lab::{lab_to_xyz, xyz_to_lab};
+use crate::white::{self, D65};
+use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel};
+use std::f64::consts::PI;
+use std::marker::PhantomData;
+
+/// Scale of *chroma* component when stored in a `Channel`
+const CHROMA_SCALE: f64 = 150.0;
+
+/// Chroma below which a color is treated as achromatic (hue of 0)
+const ACHROMATIC: f64 = 1e-2;
+
+/// CIE LCh(ab) [color model], the cylindrical form of [Lab].
+///
+/// The components are *lightness*, *chroma* and *hue*, with optional
+/// *[alpha]*.  Colors are relative to a [white point].
+///
+/// *Lightness* from 0 to 100 and *chroma* from 0 to 150 are stored in the full
+/// `Channel` range.  *Hue* is a turn around the *a\**, *b\** plane, starting
+/// at positive *a\** (red).
+///
+/// Channels clamped to the range 0 to 1 cannot store *chroma* above 150.
+/// This covers the sRGB gamut, but not wider ones, such as Display P3 green
+/// (*chroma* of 158) or Rec. 2020 green (208).  Unclamped channels ([Ch16f]
+/// and [Ch32h]) store any value with the same encoding.
+///
+/// [alpha]: alpha/trait.AChannel.html
+/// [Ch16f]: struct.Ch16f.html
+/// [Ch32h]: struct.Ch32h.html
+/// [color model]: trait.ColorModel.html
+/// [Lab]: struct.Lab.html
+/// [white point]: white/trait.Point.html
+///
+/// ### Convert from `Rgb`
+/// ```
+/// # use pix::*;
+/// let lch: Lch8 = SRgb8::new(0xFF, 0xFF, 0xFF).convert();
+/// assert_eq!(lch, Lch8::new(0xFF, 0x00, 0x00));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Lch<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    components: [C; 3],
+    alpha: A,
+    mode: PhantomData<M>,
+    white: PhantomData<W>,
+}
+
+impl<C, A, M, W> Lch<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying
+    /// *lightness*, *chroma* and *hue*.
+    pub fn new<H>(lightness: H, chroma: H, hue: H) -> Self
+    where
+        C: From<H>,
+        A: From<Opaque<C>>,
+    {
+        Self::with_alpha(lightness, chroma, hue, Opaque::default())
+    }
+    /// Create a [Translucent](alpha/struct.Translucent.html) color by
+    /// specifying *lightness*, *chroma*, *hue* and *alpha*.
+    pub fn with_alpha<H, B>(
+        lightness: H,
+        chroma: H,
+        hue: H,
+        alpha: B,
+    ) -> Self
+    where
+        C: From<H>,
+        A: From<B>,
+    {
+        let components = [C::from(lightness), C::from(chroma), C::from(hue)];
+        let alpha = A::from(alpha);
+        Lch {
+            components,
+            alpha,
+            mode: PhantomData,
+            white: PhantomData,
+        }
+    }
+    /// Get the *lightness* component.
+    pub fn lightness(self) -> C {
+        self.components[0]
+    }
+    /// Get the *chroma* component.
+    pub fn chroma(self) -> C {
+        self.components[1]
+    }
+    /// Get the *hue* component.
+    pub fn hue(self) -> C {
+        self.components[2]
+    }
+}
+
+impl<C, A, M, W> ColorModel for Lch<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    type Chan = C;
+    const MODEL: Model = Model::Lch;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
+
+    /// Get all non-alpha components
+    fn components(&self) -> &[Self::Chan] {
+        &self.components
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.alpha.value()
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        let rgba = self.to_rgba_f64();
+        [rgba[0].into(), rgba[1].into(), rgba[2].into(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        let rgb = [rgba[0].into(), rgba[1].into(), rgba[2].into()];
+        Self::with_rgba_f64([rgb[0], rgb[1], rgb[2], rgba[3].into()])
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components, without
+    /// quantizing to the `Channel` type
+    fn to_rgba_f64(self) -> [f64; 4] {
+        let lightness: f64 = self.lightness().into();
+        let chroma: f64 = self.chroma().into();
+        let hue: f64 = self.hue().into();
+        let chroma = chroma * CHROMA_SCALE;
+        let hue = hue * 2.0 * PI;
+        let lab = [lightness * 100.0, chroma * hue.cos(), chroma * hue.sin()];
+        let xyz = lab_to_xyz(lab);
+        [xyz[0], xyz[1], xyz[2], self.alpha().into()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components, without
+    /// quantizing them to the `Channel` type first
+    fn with_rgba_f64(rgba: [f64; 4]) -> Self {
+        let lab = xyz_to_lab([rgba[0], rgba[1], rgba[2]]);
+        let lightness = lab[0] / 100.0;
+        let chroma = lab[1].hypot(lab[2]);
+        let hue = if chroma > ACHROMATIC {
+            (lab[2].atan2(lab[1]) / (2.0 * PI)).rem_euclid(1.0)
+        } else {
+            0.0
+        };
+        let chroma = chroma / CHROMA_SCALE;
+        Lch::with_alpha(lightness, chroma, hue, C::from(rgba[3]))
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let l = if self.lightness() > rhs.lightness() {
+            self.lightness() - rhs.lightness()
+        } else {
+            rhs.lightness() - self.lightness()
+        };
+        let c = if self.chroma() > rhs.chroma() {
+            self.chroma() - rhs.chroma()
+        } else {
+            rhs.chroma() - self.chroma()
+        };
+        let h = hue_difference(self.hue(), rhs.hue());
+        let a = if self.alpha() > rhs.alpha() {
+            self.alpha() - rhs.alpha()
+        } else {
+            rhs.alpha() - self.alpha()
+        };
+        Lch::with_alpha(l, c, h, a)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        self.lightness() <= rhs.lightness()
+            && self.chroma() <= rhs.chroma()
+            && self.hue() <= rhs.hue()
+            && self.alpha() <= rhs.alpha()
+    }
+}
+
+impl<C, A, M, W> Pixel for Lch<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    type Alpha = M;
+    type Gamma = Linear;
+    type Primaries = CieXyz<W>;
+}
+
+impl<C, A, M, W> Iterator for Lch<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<C, M, W> From<Lch<C, Translucent<C>, M, W>> for Lch<C, Opaque<C>, M, W>
+where
+    C: Channel,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    fn from(c: Lch<C, Translucent<C>, M, W>) -> Self {
+        Lch::new(c.lightness(), c.chroma(), c.hue())
+    }
+}
+
+impl<C, M, W> From<Lch<C, Opaque<C>, M, W>> for Lch<C, Translucent<C>, M, W>
+where
+    C: Channel,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    fn from(c: Lch<C, Opaque<C>, M, W>) -> Self {
+        Lch::with_alpha(c.lightness(), c.chroma(), c.hue(), C::MAX)
+    }
+}
+
+/// [Lch](struct.Lch.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Lch8 = Lch<Ch8, Opaque<Ch8>, Straight, D65>;
+/// [Lch](struct.Lch.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Lch16 = Lch<Ch16, Opaque<Ch16>, Straight, D65>;
+/// [Lch](struct.Lch.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+///
+/// *Chroma* is limited to 150 (see [Lch](struct.Lch.html)).
+pub type Lch32 = Lch<Ch32, Opaque<Ch32>, Straight, D65>;
+
+/// [Lch](struct.Lch.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Lcha8 = Lch<Ch8, Translucent<Ch8>, Straight, D65>;
+/// [Lch](struct.Lch.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Lcha16 = Lch<Ch16, Translucent<Ch16>, Straight, D65>;
+/// [Lch](struct.Lch.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+///
+/// *Chroma* is limited to 150 (see [Lch](struct.Lch.html)).
+pub type Lcha32 = Lch<Ch32, Translucent<Ch32>, Straight, D65>;
+
+/// [Lch](struct.Lch.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Lcha8p = Lch<Ch8, Translucent<Ch8>, Premultiplied, D65>;
+/// [Lch](struct.Lch.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Lcha16p = Lch<Ch16, Translucent<Ch16>, Premultiplied, D65>;
+/// [Lch](struct.Lch.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+///
+/// *Chroma* is limited to 150 (see [Lch](struct.Lch.html)).
+pub type Lcha32p = Lch<Ch32, Translucent<Ch32>, Premultiplied, D65>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+    use crate::alpha::{Opaque, Straight};
+    use crate::white::{D50, D65};
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<Lch8>(), 3);
+        assert_eq!(std::mem::size_of::<Lch16>(), 6);
+        assert_eq!(std::mem::size_of::<Lch32>(), 12);
+        assert_eq!(std::mem::size_of::<Lcha8>(), 4);
+        assert_eq!(std::mem::size_of::<Lcha16>(), 8);
+        assert_eq!(std::mem::size_of::<Lcha32>(), 16);
+    }
+
+    #[test]
+    fn rgb_to_lch() {
+        assert_eq!(Lch8::new(136, 178, 28), SRgb8::new(0xFF, 0, 0).convert());
+        assert_eq!(Lch8::new(0, 0, 0), SRgb8::new(0, 0, 0).convert());
+        assert_eq!(
+            Lch8::new(0xFF, 0, 0),
+            SRgb8::new(0xFF, 0xFF, 0xFF).convert()
+        );
+    }
+
+    #[test]
+    fn achromatic() {
+        for g in (0..=255).step_by(5) {
+            let lch: Lch32 = SRgb8::new(g, g, g).convert();
+            assert_eq!(lch.hue(), Ch32::new(0.0));
+        }
+    }
+
+    #[test]
+    fn lab_to_lch() {
+        let lab = Lab32::new(0.5, 0.75, 128.0 / 255.0);
+        let lch: Lch32 = lab.convert();
+        assert!((f32::from(lch.lightness()) - 0.5).abs() < 1e-3);
+        assert!((f32::from(lch.hue()) - 0.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn wide_gamut() {
+        // Display P3 green has a chroma above 150
+        type Lch32h = Lch<Ch32h, Opaque<Ch32h>, Straight, D65>;
+        let p = P3Rgb16::new(0, 0xFFFF, 0);
+        let lch: Lch32h = p.convert();
+        assert!(f32::from(lch.chroma()) > 1.0);
+        let back: P3Rgb16 = lch.convert();
+        assert_eq!(back, p);
+    }
+
+    #[test]
+    fn round_trip() {
+        type Lch32D50 = Lch<Ch32, Opaque<Ch32>, Straight, D50>;
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = SRgb8::new(r, g, b);
+                    let lch: Lch32 = rgb.convert();
+                    let back: SRgb16 = lch.convert();
+                    assert_eq!(rgb, back.convert());
+                    let lch: Lch32D50 = rgb.convert();
+                    let back: SRgb16 = lch.convert();
+                    assert_eq!(rgb, back.convert());
+                }
+            }
+        }
+    }
+}
@@ -6,14 +6,26 @@
 //! Pixel and raster image library.
 //!
 //! A [raster] is a rectangular array of [pixel]s whose format is parameterized
-//! by [color model], [channel], [alpha mode] and [gamma mode].
+//! by [color model], [channel], [alpha mode], [gamma mode] and [primaries]
+//! (or [white point]).
 //!
 //! [alpha mode]: alpha/trait.Mode.html
+//! [as_bytes]: struct.Raster.html#method.as_bytes
+//! [Ch8]: struct.Ch8.html
+//! [Ch16]: struct.Ch16.html
 //! [channel]: trait.Channel.html
 //! [color model]: trait.ColorModel.html
+//! [from_bytes]: struct.Raster.html#method.from_bytes
 //! [gamma mode]: gamma/trait.Mode.html
+//! [Gray]: struct.Gray.html
+//! [Mask]: struct.Mask.html
+//! [Palette]: struct.Palette.html
 //! [pixel]: trait.Pixel.html
+//! [primaries]: gamut/trait.Primaries.html
 //! [raster]: struct.Raster.html
+//! [Region]: struct.Region.html
+//! [Rgb]: struct.Rgb.html
+//! [white point]: white/trait.Point.html
 //!
 //! ### Example: Convert Raster Format
 //! ```
@@ -23,35 +35,155 @@
 //! let dst: Raster<Rgba8p> = RasterBuilder::new().with_raster(&src);
 //! ```
 //!
+//! ### Cargo Features
+//! * `bytemuck`: Implements `Pod` and `Zeroable` for channels and pixels
+//!   (`Pod` only with [Ch8] or [Ch16] channels), and adds `Raster`::
+//!   [as_bytes] and [from_bytes] for checked casts.
+//! * `rayon`: Whole-raster operations, such as format conversion,
+//!   `set_region` and `clear`, run in parallel by rows.  Results are
+//!   identical to the serial operations.
+//! * `serde`: Implements `Serialize` and `Deserialize` for channels, [Rgb],
+//!   [Gray] and [Mask] pixels, [Palette], [Region] and [Raster].  A `Raster`
+//!   is serialized with its width, height and a blob of pixel bytes.
+//!
 #![warn(missing_docs)]
 #![warn(missing_doc_code_examples)]
 
 pub mod alpha;
+mod argb;
+mod bgr;
 mod channel;
+mod cmyk;
+mod dyn_raster;
+mod error;
 mod pixel;
+pub mod format;
 pub mod gamma;
+pub mod gamut;
 mod gray;
+mod hsl;
+mod hsv;
+mod hue;
+mod hwb;
+pub mod icc;
+mod lab;
+mod lch;
 mod mask;
+mod matrix;
 mod model;
+mod oklab;
+mod oklch;
+mod packed;
+mod packed_rgb;
+pub mod packing;
 mod palette;
+mod planar;
+#[cfg(feature = "bytemuck")]
+mod pod;
 mod private;
 mod raster;
 mod rgb;
+#[cfg(feature = "serde")]
+mod serialize;
+mod simd;
+mod view;
+pub mod video;
+pub mod white;
+mod xyz;
+mod ycbcr;
 
-pub use crate::channel::{Ch16, Ch32, Ch8, Channel};
-pub use crate::pixel::Pixel;
+pub use crate::argb::{
+    Argb, Argb16, Argb16p, Argb32, Argb32p, Argb8, Argb8p, SArgb16, SArgb16p,
+    SArgb32, SArgb32p, SArgb8, SArgb8p,
+};
+pub use crate::bgr::{
+    Bgr, Bgr16, Bgr32, Bgr8, Bgra16, Bgra16p, Bgra32, Bgra32p, Bgra8, Bgra8p,
+    SBgr16, SBgr32, SBgr8, SBgra16, SBgra16p, SBgra32, SBgra32p, SBgra8,
+    SBgra8p,
+};
+pub use crate::channel::{Ch16, Ch16f, Ch32, Ch32h, Ch64, Ch8, Channel};
+pub use crate::cmyk::{
+    Cmyk, Cmyk16, Cmyk32, Cmyk8, Cmyka16, Cmyka16p, Cmyka32, Cmyka32p, Cmyka8,
+    Cmyka8p, SCmyk16, SCmyk32, SCmyk8, SCmyka16, SCmyka16p, SCmyka32,
+    SCmyka32p, SCmyka8, SCmyka8p, Separation,
+};
+pub use crate::dyn_raster::DynRaster;
+pub use crate::error::Error;
+pub use crate::pixel::{Conversion, Pixel};
 pub use crate::gray::{
-    Gray, Gray16, Gray32, Gray8, GrayAlpha16, GrayAlpha16p, GrayAlpha32,
-    GrayAlpha32p, GrayAlpha8, GrayAlpha8p, SGray16, SGray32, SGray8,
-    SGrayAlpha16, SGrayAlpha16p, SGrayAlpha32, SGrayAlpha32p, SGrayAlpha8,
-    SGrayAlpha8p,
+    G18Gray16, G18Gray8, G18GrayAlpha16, G18GrayAlpha16p, G18GrayAlpha8,
+    G18GrayAlpha8p, G22Gray16, G22Gray8, G22GrayAlpha16, G22GrayAlpha16p,
+    G22GrayAlpha8, G22GrayAlpha8p, Gray, Gray16, Gray16f, Gray32, Gray32h,
+    Gray64, Gray8, GrayAlpha16, GrayAlpha16f, GrayAlpha16fp, GrayAlpha16p,
+    GrayAlpha32, GrayAlpha32h, GrayAlpha32hp, GrayAlpha32p, GrayAlpha64,
+    GrayAlpha64p, GrayAlpha8, GrayAlpha8p, Rec709Gray16, Rec709Gray8,
+    Rec709GrayAlpha16, Rec709GrayAlpha16p, Rec709GrayAlpha8, Rec709GrayAlpha8p,
+    SGray16, SGray16f, SGray32, SGray64, SGray8, SGrayAlpha16, SGrayAlpha16f,
+    SGrayAlpha16fp, SGrayAlpha16p, SGrayAlpha32, SGrayAlpha32p, SGrayAlpha64,
+    SGrayAlpha64p, SGrayAlpha8, SGrayAlpha8p,
+};
+pub use crate::hsl::{
+    Hsl, Hsl16, Hsl32, Hsl8, Hsla16, Hsla16p, Hsla32, Hsla32p, Hsla8, Hsla8p,
+    SHsl16, SHsl32, SHsl8, SHsla16, SHsla16p, SHsla32, SHsla32p, SHsla8,
+    SHsla8p,
+};
+pub use crate::hsv::{
+    Hsv, Hsv16, Hsv32, Hsv8, Hsva16, Hsva16p, Hsva32, Hsva32p, Hsva8, Hsva8p,
+    SHsv16, SHsv32, SHsv8, SHsva16, SHsva16p, SHsva32, SHsva32p, SHsva8,
+    SHsva8p,
+};
+pub use crate::hwb::{
+    Hwb, Hwb16, Hwb32, Hwb8, Hwba16, Hwba16p, Hwba32, Hwba32p, Hwba8, Hwba8p,
+    SHwb16, SHwb32, SHwb8, SHwba16, SHwba16p, SHwba32, SHwba32p, SHwba8,
+    SHwba8p,
+};
+pub use crate::lab::{
+    Lab, Lab16, Lab32, Lab8, Laba16, Laba16p, Laba32, Laba32p, Laba8, Laba8p,
 };
-pub use crate::mask::{Mask, Mask16, Mask32, Mask8};
+pub use crate::lch::{
+    Lch, Lch16, Lch32, Lch8, Lcha16, Lcha16p, Lcha32, Lcha32p, Lcha8, Lcha8p,
+};
+pub use crate::mask::{Mask, Mask16, Mask16f, Mask32, Mask32h, Mask64, Mask8};
 pub use crate::model::ColorModel;
+pub use crate::oklab::{
+    Oklab, Oklab16, Oklab32, Oklab8, Oklaba16, Oklaba16p, Oklaba32, Oklaba32p,
+    Oklaba8, Oklaba8p,
+};
+pub use crate::oklch::{
+    Oklch, Oklch16, Oklch32, Oklch8, Oklcha16, Oklcha16p, Oklcha32, Oklcha32p,
+    Oklcha8, Oklcha8p,
+};
 pub use crate::palette::Palette;
+pub use crate::packed::{
+    BitOrder, PackedDepth, PackedRaster, PackedRasterBuilder, PackedRasterIter,
+};
+pub use crate::packed_rgb::{
+    PackedRgb, Rgb10a2, Rgb555, Rgb565, Rgba4444, SRgb10a2, SRgb555, SRgb565,
+    SRgba4444,
+};
+pub use crate::planar::{
+    ChromaFilter, PlanarLayout, PlanarRaster, PlanarRasterBuilder,
+};
 pub use crate::raster::{Raster, RasterBuilder, RasterIter, Region};
 pub use crate::rgb::{
-    Rgb, Rgb16, Rgb32, Rgb8, Rgba16, Rgba16p, Rgba32, Rgba32p, Rgba8, Rgba8p,
-    SRgb16, SRgb32, SRgb8, SRgba16, SRgba16p, SRgba32, SRgba32p, SRgba8,
-    SRgba8p,
+    AcesCgRgb16f, AcesCgRgb32, AcesCgRgba16f, AcesCgRgba16fp, AcesCgRgba32,
+    AcesCgRgba32p, AdobeRgb16, AdobeRgb8, AdobeRgba16, AdobeRgba16p,
+    AdobeRgba8, AdobeRgba8p, G18Rgb16, G18Rgb8, G18Rgba16, G18Rgba16p,
+    G18Rgba8, G18Rgba8p, G22Rgb16, G22Rgb8, G22Rgba16, G22Rgba16p, G22Rgba8,
+    G22Rgba8p, P3Rgb16, P3Rgb8, P3Rgba16, P3Rgba16p, P3Rgba8, P3Rgba8p,
+    Rec2020Rgb16, Rec2020Rgb8, Rec2020Rgba16, Rec2020Rgba16p, Rec2020Rgba8,
+    Rec2020Rgba8p, Rec709Rgb16, Rec709Rgb8, Rec709Rgba16, Rec709Rgba16p,
+    Rec709Rgba8, Rec709Rgba8p, Rgb, Rgb16, Rgb16f, Rgb32, Rgb32h, Rgb64, Rgb8,
+    Rgba16, Rgba16f, Rgba16fp, Rgba16p, Rgba32, Rgba32h, Rgba32hp, Rgba32p,
+    Rgba64, Rgba64p, Rgba8, Rgba8p, SRgb16, SRgb16f, SRgb32, SRgb64, SRgb8,
+    SRgba16, SRgba16f, SRgba16fp, SRgba16p, SRgba32, SRgba32p, SRgba64,
+    SRgba64p, SRgba8, SRgba8p,
+};
+pub use crate::view::{RasterView, RasterViewMut};
+pub use crate::xyz::{
+    Xyz, Xyz16, Xyz32, Xyz8, Xyza16, Xyza16p, Xyza32, Xyza32p, Xyza8, Xyza8p,
+};
+pub use crate::ycbcr::{
+    YCbCr, YCbCr16, YCbCr32, YCbCr8, YCbCra16, YCbCra16p, YCbCra32, YCbCra32p,
+    YCbCra8, YCbCra8p,
 };
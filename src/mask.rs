@@ -4,8 +4,12 @@
 // Copyright (c) 2019-2020  Jeron Aldaron Lau
 //
 use crate::alpha::{AChannel, Premultiplied, Straight, Translucent};
+use crate::format::Model;
 use crate::gamma::{self, Linear};
-use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Gray, Pixel, Rgb};
+use crate::gamut;
+use crate::{
+    Ch16, Ch16f, Ch32, Ch32h, Ch64, Ch8, Channel, ColorModel, Gray, Pixel, Rgb,
+};
 use std::ops::Mul;
 
 /// [Translucent] alpha mask [color model].
@@ -24,13 +28,15 @@ impl<C: Channel> Mask<C> {
     where
         C: From<A>,
     {
-        let alpha = C::from(alpha).into();
+        let alpha = Translucent::new(C::from(alpha));
         Mask { alpha }
     }
 }
 
 impl<C: Channel> ColorModel for Mask<C> {
     type Chan = C;
+    const MODEL: Model = Model::Mask;
+    const HAS_ALPHA: bool = true;
 
     /// Get all non-alpha components
     fn components(&self) -> &[Self::Chan] {
@@ -74,6 +80,7 @@ where
 {
     type Alpha = Straight;
     type Gamma = Linear;
+    type Primaries = gamut::Srgb;
 }
 
 impl<C: Channel> Iterator for Mask<C> {
@@ -105,11 +112,12 @@ impl From<f32> for Mask32 {
     }
 }
 
-impl<C, A, G> From<Mask<C>> for Rgb<C, A, Straight, G>
+impl<C, A, G, P> From<Mask<C>> for Rgb<C, A, Straight, G, P>
 where
     C: Channel,
     A: AChannel<Chan = C> + From<C>,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
     /// Get an `Rgb` from a `Mask`
     fn from(c: Mask<C>) -> Self {
@@ -121,11 +129,12 @@ where
     }
 }
 
-impl<C, A, G> From<Mask<C>> for Rgb<C, A, Premultiplied, G>
+impl<C, A, G, P> From<Mask<C>> for Rgb<C, A, Premultiplied, G, P>
 where
     C: Channel,
     A: AChannel<Chan = C> + From<C>,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
     /// Get an `Rgb` from a `Mask`
     fn from(c: Mask<C>) -> Self {
@@ -185,6 +194,22 @@ pub type Mask16 = Mask<Ch16>;
 /// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
 pub type Mask32 = Mask<Ch32>;
 
+/// [Mask](struct.Mask.html) 16-bit floating-point
+/// [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Mask16f = Mask<Ch16f>;
+
+/// [Mask](struct.Mask.html) 64-bit floating-point
+/// [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Mask64 = Mask<Ch64>;
+
+/// [Mask](struct.Mask.html) 32-bit floating-point
+/// [high dynamic range](struct.Ch32h.html)
+/// [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Mask32h = Mask<Ch32h>;
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -194,5 +219,8 @@ mod test {
         assert_eq!(std::mem::size_of::<Mask8>(), 1);
         assert_eq!(std::mem::size_of::<Mask16>(), 2);
         assert_eq!(std::mem::size_of::<Mask32>(), 4);
+        assert_eq!(std::mem::size_of::<Mask16f>(), 2);
+        assert_eq!(std::mem::size_of::<Mask32h>(), 4);
+        assert_eq!(std::mem::size_of::<Mask64>(), 8);
     }
 }
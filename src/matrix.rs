@@ -0,0 +1,116 @@
+// matrix.rs    3x3 matrix helpers.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+
+/// Row-major 3x3 matrix
+pub type Matrix3 = [[f64; 3]; 3];
+
+/// Multiply a matrix by a column vector.
+pub fn mul_vec(m: &Matrix3, v: [f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+/// Multiply two matrices.
+pub const fn mul(a: &Matrix3, b: &Matrix3) -> Matrix3 {
+    let mut m = [[0.0; 3]; 3];
+    let mut i = 0;
+    while i < 3 {
+        let mut j = 0;
+        while j < 3 {
+            m[i][j] =
+                a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j];
+            j += 1;
+        }
+        i += 1;
+    }
+    m
+}
+
+/// Invert a matrix.
+pub const fn invert(m: &Matrix3) -> Matrix3 {
+    let c00 = m[1][1] * m[2][2] - m[1][2] * m[2][1];
+    let c01 = m[1][2] * m[2][0] - m[1][0] * m[2][2];
+    let c02 = m[1][0] * m[2][1] - m[1][1] * m[2][0];
+    let det = m[0][0] * c00 + m[0][1] * c01 + m[0][2] * c02;
+    [
+        [
+            c00 / det,
+            (m[0][2] * m[2][1] - m[0][1] * m[2][2]) / det,
+            (m[0][1] * m[1][2] - m[0][2] * m[1][1]) / det,
+        ],
+        [
+            c01 / det,
+            (m[0][0] * m[2][2] - m[0][2] * m[2][0]) / det,
+            (m[0][2] * m[1][0] - m[0][0] * m[1][2]) / det,
+        ],
+        [
+            c02 / det,
+            (m[0][1] * m[2][0] - m[0][0] * m[2][1]) / det,
+            (m[0][0] * m[1][1] - m[0][1] * m[1][0]) / det,
+        ],
+    ]
+}
+
+/// Calculate the matrix to convert linear RGB to *XYZ*.
+///
+/// * `red`, `green`, `blue` CIE 1931 *xy* chromaticity of each primary.
+/// * `white` *XYZ* tristimulus values of the white point.
+pub const fn rgb_to_xyz(
+    red: [f64; 2],
+    green: [f64; 2],
+    blue: [f64; 2],
+    white: [f64; 3],
+) -> Matrix3 {
+    let p = [
+        [red[0] / red[1], green[0] / green[1], blue[0] / blue[1]],
+        [1.0, 1.0, 1.0],
+        [
+            (1.0 - red[0] - red[1]) / red[1],
+            (1.0 - green[0] - green[1]) / green[1],
+            (1.0 - blue[0] - blue[1]) / blue[1],
+        ],
+    ];
+    let inv = invert(&p);
+    let s = [
+        inv[0][0] * white[0] + inv[0][1] * white[1] + inv[0][2] * white[2],
+        inv[1][0] * white[0] + inv[1][1] * white[1] + inv[1][2] * white[2],
+        inv[2][0] * white[0] + inv[2][1] * white[1] + inv[2][2] * white[2],
+    ];
+    [
+        [p[0][0] * s[0], p[0][1] * s[1], p[0][2] * s[2]],
+        [p[1][0] * s[0], p[1][1] * s[1], p[1][2] * s[2]],
+        [p[2][0] * s[0], p[2][1] * s[1], p[2][2] * s[2]],
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn identity() {
+        let m = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        assert_eq!(mul_vec(&m, [0.25, 0.5, 0.75]), [0.25, 0.5, 0.75]);
+        let m = [[0.0, 0.0, 1.0], [0.0, 2.0, 0.0], [1.0, 0.0, 0.0]];
+        assert_eq!(mul_vec(&m, [0.25, 0.5, 0.75]), [0.75, 1.0, 0.25]);
+    }
+    #[test]
+    fn product() {
+        let a = [[2.0, 0.0, 1.0], [1.0, 1.0, 0.0], [0.0, 3.0, 1.0]];
+        let b = [[1.0, 2.0, 0.0], [0.0, 1.0, 0.0], [4.0, 0.0, 1.0]];
+        let v = [0.25, 0.5, 0.75];
+        assert_eq!(mul_vec(&mul(&a, &b), v), mul_vec(&a, mul_vec(&b, v)));
+    }
+    #[test]
+    fn inverse() {
+        let m = [[2.0, 0.0, 1.0], [1.0, 1.0, 0.0], [0.0, 3.0, 1.0]];
+        let v = mul_vec(&invert(&m), mul_vec(&m, [0.25, 0.5, 0.75]));
+        for (a, b) in v.iter().zip([0.25, 0.5, 0.75].iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+}
@@ -3,6 +3,7 @@
 // Copyright (c) 2020  Douglas P Lau
 //
 //! Module for color model items
+use crate::format::Model;
 use crate::private::Sealed;
 use crate::Channel;
 
@@ -10,10 +11,15 @@ use crate::Channel;
 ///
 /// This trait is *sealed*, and cannot be implemented outside of this crate.
 pub trait ColorModel: Sealed {
-
     /// Component `Channel` type
     type Chan: Channel;
 
+    /// Color model of the pixel format
+    const MODEL: Model;
+
+    /// Whether the pixel format has an *alpha* component
+    const HAS_ALPHA: bool;
+
     /// Get all non-alpha components
     fn components(&self) -> &[Self::Chan];
 
@@ -26,6 +32,30 @@ pub trait ColorModel: Sealed {
     /// Convert from *red*, *green*, *blue* and *alpha* components
     fn with_rgba(rgba: [Self::Chan; 4]) -> Self;
 
+    /// Convert to *red*, *green*, *blue* and *alpha* components, without
+    /// quantizing to the `Channel` type
+    fn to_rgba_f64(self) -> [f64; 4]
+    where
+        Self: Sized,
+    {
+        let rgba = self.to_rgba();
+        [rgba[0].into(), rgba[1].into(), rgba[2].into(), rgba[3].into()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components, without
+    /// quantizing them to the `Channel` type first
+    fn with_rgba_f64(rgba: [f64; 4]) -> Self
+    where
+        Self: Sized,
+    {
+        Self::with_rgba([
+            rgba[0].into(),
+            rgba[1].into(),
+            rgba[2].into(),
+            rgba[3].into(),
+        ])
+    }
+
     /// Get channel-wise difference
     fn difference(self, rhs: Self) -> Self;
 
@@ -0,0 +1,377 @@
+// oklab.rs     Oklab color model.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{
+    self, AChannel, Opaque, Premultiplied, Straight, Translucent,
+};
+use crate::format::Model;
+use crate::gamma::Linear;
+use crate::gamut::{self, CieXyz, Primaries};
+use crate::matrix::{invert, mul, mul_vec, Matrix3};
+use crate::white::D65;
+use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel};
+use std::marker::PhantomData;
+
+/// Matrix to convert linear sRGB to LMS cone response
+const RGB_TO_LMS: Matrix3 = [
+    [0.412_221_470_8, 0.536_332_536_3, 0.051_445_992_9],
+    [0.211_903_498_2, 0.680_699_545_1, 0.107_396_956_6],
+    [0.088_302_461_9, 0.281_718_837_6, 0.629_978_700_5],
+];
+
+/// Matrix to convert compressed LMS to Oklab
+const LMS_TO_OKLAB: Matrix3 = [
+    [0.210_454_255_3, 0.793_617_785_0, -0.004_072_046_8],
+    [1.977_998_495_1, -2.428_592_205_0, 0.450_593_709_9],
+    [0.025_904_037_1, 0.782_771_766_2, -0.808_675_766_0],
+];
+
+/// Matrix to convert Oklab to compressed LMS
+const OKLAB_TO_LMS: Matrix3 = [
+    [1.0, 0.396_337_777_4, 0.215_803_757_3],
+    [1.0, -0.105_561_345_8, -0.063_854_172_8],
+    [1.0, -0.089_484_177_5, -1.291_485_548_0],
+];
+
+/// Matrix to convert *XYZ* relative to a D65 white point to LMS cone
+/// response, derived from the linear sRGB matrix
+const XYZ_TO_LMS: Matrix3 = mul(
+    &mul(&RGB_TO_LMS, &gamut::Srgb::FROM_XYZ),
+    &CieXyz::<D65>::TO_XYZ,
+);
+
+/// Matrix to convert LMS cone response to *XYZ* relative to a D65 white
+/// point
+const LMS_TO_XYZ: Matrix3 = invert(&XYZ_TO_LMS);
+
+/// Offset of *a* and *b* components when stored in a `Channel`
+const AB_OFFSET: f64 = 128.0 / 255.0;
+
+/// Convert *XYZ* relative to a D65 white point to *L*, *a* and *b*.
+///
+/// *L* ranges from 0 to 1, while *a* and *b* are unbounded.
+pub(crate) fn xyz_to_oklab(xyz: [f64; 3]) -> [f64; 3] {
+    let lms = mul_vec(&XYZ_TO_LMS, xyz);
+    let lms = [lms[0].cbrt(), lms[1].cbrt(), lms[2].cbrt()];
+    mul_vec(&LMS_TO_OKLAB, lms)
+}
+
+/// Convert *L*, *a* and *b* to *XYZ* relative to a D65 white point.
+pub(crate) fn oklab_to_xyz(lab: [f64; 3]) -> [f64; 3] {
+    let lms = mul_vec(&OKLAB_TO_LMS, lab);
+    let lms = [lms[0].powi(3), lms[1].powi(3), lms[2].powi(3)];
+    mul_vec(&LMS_TO_XYZ, lms)
+}
+
+/// Oklab perceptual [color model].
+///
+/// The components are *L* (lightness), *a* (green to red) and *b* (blue to
+/// yellow), with optional *[alpha]*.  The white point is D65, and colors are
+/// converted through *XYZ*, so they are not limited to the sRGB gamut.
+///
+/// *L* from 0 to 1 is stored in the full `Channel` range.  *a* and *b* from
+/// -0.5 to 0.5 are stored with an offset of 128/255, so neutral colors have a
+/// `Ch8` value of 128.
+///
+/// [alpha]: alpha/trait.AChannel.html
+/// [color model]: trait.ColorModel.html
+///
+/// ### Convert from `Rgb`
+/// ```
+/// # use pix::*;
+/// let lab: Oklab8 = SRgb8::new(0xFF, 0xFF, 0xFF).convert();
+/// assert_eq!(lab, Oklab8::new(0xFF, 0x80, 0x80));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Oklab<C, A, M>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+{
+    components: [C; 3],
+    alpha: A,
+    mode: PhantomData<M>,
+}
+
+impl<C, A, M> Oklab<C, A, M>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+{
+    /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying
+    /// *L*, *a* and *b* values.
+    pub fn new<H>(l: H, a: H, b: H) -> Self
+    where
+        C: From<H>,
+        A: From<Opaque<C>>,
+    {
+        Self::with_alpha(l, a, b, Opaque::default())
+    }
+    /// Create a [Translucent](alpha/struct.Translucent.html) color by
+    /// specifying *L*, *a*, *b* and *alpha* values.
+    pub fn with_alpha<H, B>(l: H, a: H, b: H, alpha: B) -> Self
+    where
+        C: From<H>,
+        A: From<B>,
+    {
+        let components = [C::from(l), C::from(a), C::from(b)];
+        let alpha = A::from(alpha);
+        Oklab {
+            components,
+            alpha,
+            mode: PhantomData,
+        }
+    }
+    /// Get the *L* (lightness) component.
+    pub fn l(self) -> C {
+        self.components[0]
+    }
+    /// Get the *a* (green to red) component.
+    pub fn a(self) -> C {
+        self.components[1]
+    }
+    /// Get the *b* (blue to yellow) component.
+    pub fn b(self) -> C {
+        self.components[2]
+    }
+}
+
+impl<C, A, M> ColorModel for Oklab<C, A, M>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+{
+    type Chan = C;
+    const MODEL: Model = Model::Oklab;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
+
+    /// Get all non-alpha components
+    fn components(&self) -> &[Self::Chan] {
+        &self.components
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.alpha.value()
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        let rgba = self.to_rgba_f64();
+        [rgba[0].into(), rgba[1].into(), rgba[2].into(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        let rgb = [rgba[0].into(), rgba[1].into(), rgba[2].into()];
+        Self::with_rgba_f64([rgb[0], rgb[1], rgb[2], rgba[3].into()])
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components, without
+    /// quantizing to the `Channel` type
+    fn to_rgba_f64(self) -> [f64; 4] {
+        let l: f64 = self.l().into();
+        let a: f64 = self.a().into();
+        let b: f64 = self.b().into();
+        let xyz = oklab_to_xyz([l, a - AB_OFFSET, b - AB_OFFSET]);
+        [xyz[0], xyz[1], xyz[2], self.alpha().into()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components, without
+    /// quantizing them to the `Channel` type first
+    fn with_rgba_f64(rgba: [f64; 4]) -> Self {
+        let lab = xyz_to_oklab([rgba[0], rgba[1], rgba[2]]);
+        let l = lab[0];
+        let a = lab[1] + AB_OFFSET;
+        let b = lab[2] + AB_OFFSET;
+        Oklab::with_alpha(l, a, b, C::from(rgba[3]))
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let l = if self.l() > rhs.l() {
+            self.l() - rhs.l()
+        } else {
+            rhs.l() - self.l()
+        };
+        let a = if self.a() > rhs.a() {
+            self.a() - rhs.a()
+        } else {
+            rhs.a() - self.a()
+        };
+        let b = if self.b() > rhs.b() {
+            self.b() - rhs.b()
+        } else {
+            rhs.b() - self.b()
+        };
+        let alpha = if self.alpha() > rhs.alpha() {
+            self.alpha() - rhs.alpha()
+        } else {
+            rhs.alpha() - self.alpha()
+        };
+        Oklab::with_alpha(l, a, b, alpha)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        self.l() <= rhs.l()
+            && self.a() <= rhs.a()
+            && self.b() <= rhs.b()
+            && self.alpha() <= rhs.alpha()
+    }
+}
+
+impl<C, A, M> Pixel for Oklab<C, A, M>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+{
+    type Alpha = M;
+    type Gamma = Linear;
+    type Primaries = CieXyz<D65>;
+}
+
+impl<C, A, M> Iterator for Oklab<C, A, M>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<C, M> From<Oklab<C, Translucent<C>, M>> for Oklab<C, Opaque<C>, M>
+where
+    C: Channel,
+    M: alpha::Mode,
+{
+    fn from(c: Oklab<C, Translucent<C>, M>) -> Self {
+        Oklab::new(c.l(), c.a(), c.b())
+    }
+}
+
+impl<C, M> From<Oklab<C, Opaque<C>, M>> for Oklab<C, Translucent<C>, M>
+where
+    C: Channel,
+    M: alpha::Mode,
+{
+    fn from(c: Oklab<C, Opaque<C>, M>) -> Self {
+        Oklab::with_alpha(c.l(), c.a(), c.b(), C::MAX)
+    }
+}
+
+
+/// [Oklab](struct.Oklab.html) 8-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha) [pixel](trait.Pixel.html) format.
+pub type Oklab8 = Oklab<Ch8, Opaque<Ch8>, Straight>;
+/// [Oklab](struct.Oklab.html) 16-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha) [pixel](trait.Pixel.html) format.
+pub type Oklab16 = Oklab<Ch16, Opaque<Ch16>, Straight>;
+/// [Oklab](struct.Oklab.html) 32-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha) [pixel](trait.Pixel.html) format.
+pub type Oklab32 = Oklab<Ch32, Opaque<Ch32>, Straight>;
+
+/// [Oklab](struct.Oklab.html) 8-bit [straight](alpha/struct.Straight.html)
+/// alpha [pixel](trait.Pixel.html) format.
+pub type Oklaba8 = Oklab<Ch8, Translucent<Ch8>, Straight>;
+/// [Oklab](struct.Oklab.html) 16-bit [straight](alpha/struct.Straight.html)
+/// alpha [pixel](trait.Pixel.html) format.
+pub type Oklaba16 = Oklab<Ch16, Translucent<Ch16>, Straight>;
+/// [Oklab](struct.Oklab.html) 32-bit [straight](alpha/struct.Straight.html)
+/// alpha [pixel](trait.Pixel.html) format.
+pub type Oklaba32 = Oklab<Ch32, Translucent<Ch32>, Straight>;
+
+/// [Oklab](struct.Oklab.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [pixel](trait.Pixel.html) format.
+pub type Oklaba8p = Oklab<Ch8, Translucent<Ch8>, Premultiplied>;
+/// [Oklab](struct.Oklab.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [pixel](trait.Pixel.html) format.
+pub type Oklaba16p = Oklab<Ch16, Translucent<Ch16>, Premultiplied>;
+/// [Oklab](struct.Oklab.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [pixel](trait.Pixel.html) format.
+pub type Oklaba32p = Oklab<Ch32, Translucent<Ch32>, Premultiplied>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<Oklab8>(), 3);
+        assert_eq!(std::mem::size_of::<Oklab16>(), 6);
+        assert_eq!(std::mem::size_of::<Oklab32>(), 12);
+        assert_eq!(std::mem::size_of::<Oklaba8>(), 4);
+        assert_eq!(std::mem::size_of::<Oklaba16>(), 8);
+        assert_eq!(std::mem::size_of::<Oklaba32>(), 16);
+    }
+
+    #[test]
+    fn rgb_to_oklab() {
+        assert_eq!(
+            Oklab8::new(160, 185, 160),
+            SRgb8::new(0xFF, 0, 0).convert()
+        );
+        assert_eq!(
+            Oklab8::new(221, 68, 174),
+            SRgb8::new(0, 0xFF, 0).convert()
+        );
+        assert_eq!(
+            Oklab8::new(115, 120, 49),
+            SRgb8::new(0, 0, 0xFF).convert()
+        );
+        assert_eq!(
+            Oklab16::new(39313, 32896, 32896),
+            SRgb8::new(0x80, 0x80, 0x80).convert()
+        );
+    }
+
+    #[test]
+    fn shadows() {
+        let lab: Vec<Oklab8> = [1, 4, 8, 12, 16]
+            .iter()
+            .map(|g| SRgb8::new(*g, *g, *g).convert())
+            .collect();
+        for (a, b) in lab.iter().zip(lab.iter().skip(1)) {
+            assert!(a.l() < b.l());
+        }
+        let lab: Oklab16 = SRgb8::new(12, 12, 12).convert();
+        assert_eq!(SRgb8::new(12, 12, 12), lab.convert());
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = SRgb8::new(r, g, b);
+                    let lab: Oklab32 = rgb.convert();
+                    let back: SRgb16 = lab.convert();
+                    assert_eq!(rgb, back.convert());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wide_gamut() {
+        let rgb = Rec2020Rgb16::new(0, 0xFFFF, 0);
+        let lab: Oklab32 = rgb.convert();
+        let back: Rec2020Rgb16 = lab.convert();
+        assert!(u16::from(back.red()) < 0x10);
+        assert!(u16::from(back.green()) > 0xFFF0);
+        assert!(u16::from(back.blue()) < 0x10);
+    }
+}
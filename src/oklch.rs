@@ -0,0 +1,339 @@
+// oklch.rs     Oklch color model.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{
+    self, AChannel, Opaque, Premultiplied, Straight, Translucent,
+};
+use crate::format::Model;
+use crate::gamma::Linear;
+use crate::gamut::CieXyz;
+use crate::hue::hue_difference;
+use crate::oklab::{oklab_to_xyz, xyz_to_oklab};
+use crate::white::D65;
+use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel};
+use std::f64::consts::PI;
+use std::marker::PhantomData;
+
+/// Scale of *chroma* component when stored in a `Channel`
+const CHROMA_SCALE: f64 = 0.4;
+
+/// Chroma below which a color is treated as achromatic (hue of 0)
+const ACHROMATIC: f64 = 1e-4;
+
+/// Oklch [color model], the cylindrical form of [Oklab].
+///
+/// The components are *lightness*, *chroma* and *hue*, with optional
+/// *[alpha]*.
+///
+/// *Lightness* from 0 to 1 and *chroma* from 0 to 0.4 are stored in the full
+/// `Channel` range.  *Hue* is a turn around the *a*, *b* plane, starting at
+/// positive *a* (red).  Editing *lightness* or *chroma* preserves perceived
+/// *hue*.
+///
+/// Colors are converted through *XYZ* with a D65 white point, so they are
+/// not limited to the sRGB gamut.  *Chroma* above 0.4 (the most saturated
+/// Rec. 2020 colors) is clamped, except by unclamped `Channel`s such as
+/// [Ch32h].
+///
+/// [alpha]: alpha/trait.AChannel.html
+/// [Ch32h]: struct.Ch32h.html
+/// [color model]: trait.ColorModel.html
+/// [Oklab]: struct.Oklab.html
+///
+/// ### Convert from `Rgb`
+/// ```
+/// # use pix::*;
+/// let lch: Oklch8 = SRgb8::new(0xFF, 0xFF, 0xFF).convert();
+/// assert_eq!(lch, Oklch8::new(0xFF, 0x00, 0x00));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Oklch<C, A, M>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+{
+    components: [C; 3],
+    alpha: A,
+    mode: PhantomData<M>,
+}
+
+impl<C, A, M> Oklch<C, A, M>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+{
+    /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying
+    /// *lightness*, *chroma* and *hue*.
+    pub fn new<H>(lightness: H, chroma: H, hue: H) -> Self
+    where
+        C: From<H>,
+        A: From<Opaque<C>>,
+    {
+        Self::with_alpha(lightness, chroma, hue, Opaque::default())
+    }
+    /// Create a [Translucent](alpha/struct.Translucent.html) color by
+    /// specifying *lightness*, *chroma*, *hue* and *alpha*.
+    pub fn with_alpha<H, B>(
+        lightness: H,
+        chroma: H,
+        hue: H,
+        alpha: B,
+    ) -> Self
+    where
+        C: From<H>,
+        A: From<B>,
+    {
+        let components = [C::from(lightness), C::from(chroma), C::from(hue)];
+        let alpha = A::from(alpha);
+        Oklch {
+            components,
+            alpha,
+            mode: PhantomData,
+        }
+    }
+    /// Get the *lightness* component.
+    pub fn lightness(self) -> C {
+        self.components[0]
+    }
+    /// Get the *chroma* component.
+    pub fn chroma(self) -> C {
+        self.components[1]
+    }
+    /// Get the *hue* component.
+    pub fn hue(self) -> C {
+        self.components[2]
+    }
+}
+
+impl<C, A, M> ColorModel for Oklch<C, A, M>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+{
+    type Chan = C;
+    const MODEL: Model = Model::Oklch;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
+
+    /// Get all non-alpha components
+    fn components(&self) -> &[Self::Chan] {
+        &self.components
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.alpha.value()
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        let rgba = self.to_rgba_f64();
+        [rgba[0].into(), rgba[1].into(), rgba[2].into(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        let rgb = [rgba[0].into(), rgba[1].into(), rgba[2].into()];
+        Self::with_rgba_f64([rgb[0], rgb[1], rgb[2], rgba[3].into()])
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components, without
+    /// quantizing to the `Channel` type
+    fn to_rgba_f64(self) -> [f64; 4] {
+        let lightness: f64 = self.lightness().into();
+        let chroma: f64 = self.chroma().into();
+        let hue: f64 = self.hue().into();
+        let chroma = chroma * CHROMA_SCALE;
+        let hue = hue * 2.0 * PI;
+        let lab = [lightness, chroma * hue.cos(), chroma * hue.sin()];
+        let xyz = oklab_to_xyz(lab);
+        [xyz[0], xyz[1], xyz[2], self.alpha().into()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components, without
+    /// quantizing them to the `Channel` type first
+    fn with_rgba_f64(rgba: [f64; 4]) -> Self {
+        let lab = xyz_to_oklab([rgba[0], rgba[1], rgba[2]]);
+        let lightness = lab[0];
+        let chroma = lab[1].hypot(lab[2]);
+        let hue = if chroma > ACHROMATIC {
+            (lab[2].atan2(lab[1]) / (2.0 * PI)).rem_euclid(1.0)
+        } else {
+            0.0
+        };
+        let chroma = chroma / CHROMA_SCALE;
+        Oklch::with_alpha(lightness, chroma, hue, C::from(rgba[3]))
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let l = if self.lightness() > rhs.lightness() {
+            self.lightness() - rhs.lightness()
+        } else {
+            rhs.lightness() - self.lightness()
+        };
+        let c = if self.chroma() > rhs.chroma() {
+            self.chroma() - rhs.chroma()
+        } else {
+            rhs.chroma() - self.chroma()
+        };
+        let h = hue_difference(self.hue(), rhs.hue());
+        let a = if self.alpha() > rhs.alpha() {
+            self.alpha() - rhs.alpha()
+        } else {
+            rhs.alpha() - self.alpha()
+        };
+        Oklch::with_alpha(l, c, h, a)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        self.lightness() <= rhs.lightness()
+            && self.chroma() <= rhs.chroma()
+            && self.hue() <= rhs.hue()
+            && self.alpha() <= rhs.alpha()
+    }
+}
+
+impl<C, A, M> Pixel for Oklch<C, A, M>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+{
+    type Alpha = M;
+    type Gamma = Linear;
+    type Primaries = CieXyz<D65>;
+}
+
+impl<C, A, M> Iterator for Oklch<C, A, M>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<C, M> From<Oklch<C, Translucent<C>, M>> for Oklch<C, Opaque<C>, M>
+where
+    C: Channel,
+    M: alpha::Mode,
+{
+    fn from(c: Oklch<C, Translucent<C>, M>) -> Self {
+        Oklch::new(c.lightness(), c.chroma(), c.hue())
+    }
+}
+
+impl<C, M> From<Oklch<C, Opaque<C>, M>> for Oklch<C, Translucent<C>, M>
+where
+    C: Channel,
+    M: alpha::Mode,
+{
+    fn from(c: Oklch<C, Opaque<C>, M>) -> Self {
+        Oklch::with_alpha(c.lightness(), c.chroma(), c.hue(), C::MAX)
+    }
+}
+
+
+/// [Oklch](struct.Oklch.html) 8-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha) [pixel](trait.Pixel.html) format.
+pub type Oklch8 = Oklch<Ch8, Opaque<Ch8>, Straight>;
+/// [Oklch](struct.Oklch.html) 16-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha) [pixel](trait.Pixel.html) format.
+pub type Oklch16 = Oklch<Ch16, Opaque<Ch16>, Straight>;
+/// [Oklch](struct.Oklch.html) 32-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha) [pixel](trait.Pixel.html) format.
+pub type Oklch32 = Oklch<Ch32, Opaque<Ch32>, Straight>;
+
+/// [Oklch](struct.Oklch.html) 8-bit [straight](alpha/struct.Straight.html)
+/// alpha [pixel](trait.Pixel.html) format.
+pub type Oklcha8 = Oklch<Ch8, Translucent<Ch8>, Straight>;
+/// [Oklch](struct.Oklch.html) 16-bit [straight](alpha/struct.Straight.html)
+/// alpha [pixel](trait.Pixel.html) format.
+pub type Oklcha16 = Oklch<Ch16, Translucent<Ch16>, Straight>;
+/// [Oklch](struct.Oklch.html) 32-bit [straight](alpha/struct.Straight.html)
+/// alpha [pixel](trait.Pixel.html) format.
+pub type Oklcha32 = Oklch<Ch32, Translucent<Ch32>, Straight>;
+
+/// [Oklch](struct.Oklch.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [pixel](trait.Pixel.html) format.
+pub type Oklcha8p = Oklch<Ch8, Translucent<Ch8>, Premultiplied>;
+/// [Oklch](struct.Oklch.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [pixel](trait.Pixel.html) format.
+pub type Oklcha16p = Oklch<Ch16, Translucent<Ch16>, Premultiplied>;
+/// [Oklch](struct.Oklch.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [pixel](trait.Pixel.html) format.
+pub type Oklcha32p = Oklch<Ch32, Translucent<Ch32>, Premultiplied>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<Oklch8>(), 3);
+        assert_eq!(std::mem::size_of::<Oklch16>(), 6);
+        assert_eq!(std::mem::size_of::<Oklch32>(), 12);
+        assert_eq!(std::mem::size_of::<Oklcha8>(), 4);
+        assert_eq!(std::mem::size_of::<Oklcha16>(), 8);
+        assert_eq!(std::mem::size_of::<Oklcha32>(), 16);
+    }
+
+    #[test]
+    fn rgb_to_oklch() {
+        assert_eq!(
+            Oklch8::new(160, 164, 21),
+            SRgb8::new(0xFF, 0, 0).convert()
+        );
+        assert_eq!(
+            Oklch8::new(221, 188, 101),
+            SRgb8::new(0, 0xFF, 0).convert()
+        );
+        assert_eq!(Oklch8::new(0, 0, 0), SRgb8::new(0, 0, 0).convert());
+    }
+
+    #[test]
+    fn oklab_to_oklch() {
+        let lab = Oklab32::new(0.5, 128.0 / 255.0 + 0.02, 128.0 / 255.0);
+        let lch: Oklch32 = lab.convert();
+        assert!((f32::from(lch.lightness()) - 0.5).abs() < 1e-3);
+        assert!((f32::from(lch.chroma()) - 0.05).abs() < 1e-3);
+        assert!((f32::from(lch.hue()) - 0.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = SRgb8::new(r, g, b);
+                    let lch: Oklch32 = rgb.convert();
+                    let back: SRgb16 = lch.convert();
+                    assert_eq!(rgb, back.convert());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wide_gamut() {
+        let rgb = P3Rgb16::new(0, 0xFFFF, 0);
+        let lch: Oklch32 = rgb.convert();
+        let back: P3Rgb16 = lch.convert();
+        assert!(u16::from(back.red()) < 0x10);
+        assert!(u16::from(back.green()) > 0xFFF0);
+        assert!(u16::from(back.blue()) < 0x10);
+    }
+}
@@ -0,0 +1,648 @@
+// packed.rs    Packed sub-byte raster images.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::format::Model;
+use crate::{Channel, Error, Pixel, Raster, RasterBuilder, Region};
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+
+/// Number of bits per pixel in a [PackedRaster].
+///
+/// [PackedRaster]: struct.PackedRaster.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PackedDepth {
+    /// 1 bit per pixel (2 levels), 8 pixels per byte
+    Bits1,
+    /// 2 bits per pixel (4 levels), 4 pixels per byte
+    Bits2,
+    /// 4 bits per pixel (16 levels), 2 pixels per byte
+    Bits4,
+}
+
+/// Order of pixels within each byte of a [PackedRaster].
+///
+/// [PackedRaster]: struct.PackedRaster.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitOrder {
+    /// Leftmost pixel in the most significant bits (PBM, BMP, PNG)
+    MsbFirst,
+    /// Leftmost pixel in the least significant bits (TIFF *FillOrder* 2,
+    /// fax)
+    LsbFirst,
+}
+
+/// Builder for [PackedRaster](struct.PackedRaster.html) images.
+///
+/// After creating a `PackedRasterBuilder`, finish building a `PackedRaster`
+/// using one of the *with_* methods:
+/// * [with_clear](struct.PackedRasterBuilder.html#method.with_clear)
+/// * [with_raster](struct.PackedRasterBuilder.html#method.with_raster)
+/// * [with_u8_buffer](struct.PackedRasterBuilder.html#method.with_u8_buffer)
+///
+/// ### Create a `PackedRaster`
+/// ```
+/// # use pix::*;
+/// let r = PackedRasterBuilder::<Mask8>::new().with_clear(
+///     64,
+///     48,
+///     PackedDepth::Bits1,
+///     BitOrder::MsbFirst,
+/// );
+/// ```
+pub struct PackedRasterBuilder<P: Pixel> {
+    _pixel: PhantomData<P>,
+}
+
+/// Image with 1, 2 or 4 bits per pixel, for bitmaps and masks.
+///
+/// Each pixel is one *level* of a single component: the alpha value of a
+/// [Mask], or the gray value of other formats (as for [Gray]), which are
+/// always opaque.  Levels are evenly spaced between
+/// [Channel::MIN] and [Channel::MAX].  Each row starts on a byte boundary.
+///
+/// Convert to an unpacked [Raster] using `RasterBuilder`::[with_packed].
+///
+/// [Channel::MAX]: trait.Channel.html#associatedconstant.MAX
+/// [Channel::MIN]: trait.Channel.html#associatedconstant.MIN
+/// [Gray]: struct.Gray.html
+/// [Mask]: struct.Mask.html
+/// [Raster]: struct.Raster.html
+/// [with_packed]: struct.RasterBuilder.html#method.with_packed
+///
+/// ### Convert a 1 bpp bitmap to `Mask8`
+/// ```
+/// # use pix::*;
+/// let bitmap = PackedRasterBuilder::<Mask8>::new().with_u8_buffer(
+///     8,
+///     2,
+///     PackedDepth::Bits1,
+///     BitOrder::MsbFirst,
+///     vec![0b1000_0001, 0b0110_0000],
+/// );
+/// assert_eq!(bitmap.pixel(7, 0), Mask8::new(0xFF));
+/// let mask = RasterBuilder::<Mask8>::new().with_packed(&bitmap);
+/// assert_eq!(mask.pixel(1, 1), Mask8::new(0xFF));
+/// ```
+pub struct PackedRaster<P: Pixel> {
+    width: u32,
+    height: u32,
+    depth: PackedDepth,
+    order: BitOrder,
+    buffer: Box<[u8]>,
+    _pixel: PhantomData<P>,
+}
+
+/// `Iterator` for pixels within a [PackedRaster](struct.PackedRaster.html).
+///
+/// Use `PackedRaster`::[region_iter] to create.
+///
+/// [region_iter]: struct.PackedRaster.html#method.region_iter
+pub struct PackedRasterIter<'a, P: Pixel> {
+    raster: &'a PackedRaster<P>,
+    left: u32,
+    right: u32,
+    bottom: u32,
+    x: u32,
+    y: u32,
+}
+
+impl PackedDepth {
+    /// Get the number of bits per pixel.
+    pub fn bits(self) -> u32 {
+        match self {
+            PackedDepth::Bits1 => 1,
+            PackedDepth::Bits2 => 2,
+            PackedDepth::Bits4 => 4,
+        }
+    }
+    /// Get the highest level.
+    fn max_level(self) -> u8 {
+        (1 << self.bits()) - 1
+    }
+    /// Get the number of bytes in one row.
+    ///
+    /// * `width` Width in pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of bits in a row overflows `usize`.
+    pub fn stride(self, width: u32) -> usize {
+        self.checked_stride(width).expect("packed stride overflow")
+    }
+    /// Get the number of bytes in one row, or `None` on overflow.
+    fn checked_stride(self, width: u32) -> Option<usize> {
+        let bits = (width as usize).checked_mul(self.bits() as usize)?;
+        Some(bits / 8 + usize::from(bits % 8 != 0))
+    }
+    /// Get the number of bytes in a buffer.
+    ///
+    /// The number of pixels (`width` * `height`) must fit in a `u32`, as with
+    /// [Raster](struct.Raster.html).
+    fn buffer_len(self, width: u32, height: u32) -> Result<usize, Error> {
+        width.checked_mul(height).ok_or(Error::SizeOverflow)?;
+        self.checked_stride(width)
+            .and_then(|stride| stride.checked_mul(height as usize))
+            .ok_or(Error::SizeOverflow)
+    }
+}
+
+impl<P: Pixel> Default for PackedRasterBuilder<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Pixel> PackedRasterBuilder<P> {
+    /// Create a new packed raster builder.
+    ///
+    /// * `P` [Pixel](trait.Pixel.html) format of unpacked pixels.
+    pub fn new() -> Self {
+        let _pixel = PhantomData;
+        PackedRasterBuilder { _pixel }
+    }
+    /// Build a `PackedRaster` with all pixels set to level zero.
+    ///
+    /// * `width` Width of `PackedRaster`.
+    /// * `height` Height of `PackedRaster`.
+    /// * `depth` Bits per pixel.
+    /// * `order` Order of pixels within each byte.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` * `height` overflows.
+    pub fn with_clear(
+        self,
+        width: u32,
+        height: u32,
+        depth: PackedDepth,
+        order: BitOrder,
+    ) -> PackedRaster<P> {
+        self.try_with_clear(width, height, depth, order)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Try to build a `PackedRaster` with all pixels set to level zero.
+    ///
+    /// Returns [Error::SizeOverflow] if `width` * `height` overflows.
+    ///
+    /// [Error::SizeOverflow]: enum.Error.html#variant.SizeOverflow
+    ///
+    /// ## Example
+    /// ```
+    /// # use pix::*;
+    /// let r = PackedRasterBuilder::<Mask8>::new().try_with_clear(
+    ///     1 << 16,
+    ///     1 << 16,
+    ///     PackedDepth::Bits4,
+    ///     BitOrder::MsbFirst,
+    /// );
+    /// assert_eq!(r.err(), Some(Error::SizeOverflow));
+    /// ```
+    pub fn try_with_clear(
+        self,
+        width: u32,
+        height: u32,
+        depth: PackedDepth,
+        order: BitOrder,
+    ) -> Result<PackedRaster<P>, Error> {
+        let len = depth.buffer_len(width, height)?;
+        let buffer = vec![0; len].into_boxed_slice();
+        Ok(PackedRaster {
+            width,
+            height,
+            depth,
+            order,
+            buffer,
+            _pixel: PhantomData,
+        })
+    }
+    /// Build a `PackedRaster` by copying another `Raster`.
+    ///
+    /// Each pixel is quantized to the nearest level.
+    ///
+    /// * `S` `Pixel` format of source `Raster`.
+    /// * `depth` Bits per pixel.
+    /// * `order` Order of pixels within each byte.
+    ///
+    /// ### Threshold an SGray8 image to 1 bpp
+    /// ```
+    /// # use pix::*;
+    /// let r0 = RasterBuilder::<SGray8>::new().with_clear(64, 48);
+    /// let r1 = PackedRasterBuilder::<SGray8>::new().with_raster(
+    ///     &r0,
+    ///     PackedDepth::Bits1,
+    ///     BitOrder::MsbFirst,
+    /// );
+    /// ```
+    pub fn with_raster<S>(
+        self,
+        src: &Raster<S>,
+        depth: PackedDepth,
+        order: BitOrder,
+    ) -> PackedRaster<P>
+    where
+        S: Pixel,
+        P::Chan: From<S::Chan>,
+    {
+        let (width, height) = (src.width(), src.height());
+        let mut r = self.with_clear(width, height, depth, order);
+        for y in 0..height {
+            for (x, p) in src.as_slice_row(y).iter().enumerate() {
+                r.set_pixel(x as u32, y, p.convert::<P>());
+            }
+        }
+        r
+    }
+    /// Build a `PackedRaster` from a `u8` buffer.
+    ///
+    /// * `B` Owned buffer type (`Vec` or boxed slice).
+    /// * `width` Width of `PackedRaster`.
+    /// * `height` Height of `PackedRaster`.
+    /// * `depth` Bits per pixel.
+    /// * `order` Order of pixels within each byte.
+    /// * `buffer` Buffer of packed pixel data.  Each row must start on a byte
+    ///   boundary; unused bits at the end of a row are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` * `height` overflows, or if `buffer` length is not
+    /// equal to
+    /// `depth`.[stride](enum.PackedDepth.html#method.stride)(`width`) *
+    /// `height`.
+    pub fn with_u8_buffer<B>(
+        self,
+        width: u32,
+        height: u32,
+        depth: PackedDepth,
+        order: BitOrder,
+        buffer: B,
+    ) -> PackedRaster<P>
+    where
+        B: Into<Box<[u8]>>,
+    {
+        self.try_with_u8_buffer(width, height, depth, order, buffer)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Try to build a `PackedRaster` from a `u8` buffer.
+    ///
+    /// This is the same as
+    /// [with_u8_buffer](struct.PackedRasterBuilder.html#method.with_u8_buffer),
+    /// but returns an [Error](enum.Error.html) instead of panicking.
+    pub fn try_with_u8_buffer<B>(
+        self,
+        width: u32,
+        height: u32,
+        depth: PackedDepth,
+        order: BitOrder,
+        buffer: B,
+    ) -> Result<PackedRaster<P>, Error>
+    where
+        B: Into<Box<[u8]>>,
+    {
+        let buffer: Box<[u8]> = buffer.into();
+        let len = depth.buffer_len(width, height)?;
+        if buffer.len() != len {
+            return Err(Error::BufferLength {
+                expected: len,
+                actual: buffer.len(),
+            });
+        }
+        Ok(PackedRaster {
+            width,
+            height,
+            depth,
+            order,
+            buffer,
+            _pixel: PhantomData,
+        })
+    }
+}
+
+impl<P: Pixel> PackedRaster<P> {
+    /// Get width of `PackedRaster`.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    /// Get height of `PackedRaster`.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    /// Get bits per pixel.
+    pub fn depth(&self) -> PackedDepth {
+        self.depth
+    }
+    /// Get order of pixels within each byte.
+    pub fn bit_order(&self) -> BitOrder {
+        self.order
+    }
+    /// Get byte index and bit shift of one pixel.
+    fn position(&self, x: u32, y: u32) -> (usize, u32) {
+        assert!(x < self.width && y < self.height);
+        let bits = self.depth.bits();
+        let i = self.depth.stride(self.width) * y as usize;
+        let i = i + x as usize * bits as usize / 8;
+        let slot = x % (8 / bits);
+        let shift = match self.order {
+            BitOrder::MsbFirst => 8 - bits * (slot + 1),
+            BitOrder::LsbFirst => bits * slot,
+        };
+        (i, shift)
+    }
+    /// Get the level of one pixel.
+    pub fn level(&self, x: u32, y: u32) -> u8 {
+        let (i, shift) = self.position(x, y);
+        (self.buffer[i] >> shift) & self.depth.max_level()
+    }
+    /// Set the level of one pixel.
+    ///
+    /// Bits above the pixel depth are ignored.
+    pub fn set_level(&mut self, x: u32, y: u32, level: u8) {
+        let (i, shift) = self.position(x, y);
+        let max = self.depth.max_level();
+        let b = self.buffer[i] & !(max << shift);
+        self.buffer[i] = b | ((level & max) << shift);
+    }
+    /// Get one pixel.
+    pub fn pixel(&self, x: u32, y: u32) -> P {
+        let level = self.level(x, y);
+        let v = f32::from(level) / f32::from(self.depth.max_level());
+        let v = <P::Chan as From<f32>>::from(v);
+        if P::MODEL == Model::Mask {
+            P::with_rgba([P::Chan::MAX, P::Chan::MAX, P::Chan::MAX, v])
+        } else {
+            P::with_rgba([v, v, v, P::Chan::MAX])
+        }
+    }
+    /// Set one pixel value, quantized to the nearest level.
+    pub fn set_pixel<S>(&mut self, x: u32, y: u32, p: S)
+    where
+        P: From<S>,
+    {
+        let p: P = p.into();
+        let v = if P::MODEL == Model::Mask {
+            p.alpha()
+        } else {
+            let rgba = p.to_rgba();
+            rgba[0].max(rgba[1]).max(rgba[2])
+        };
+        let max = f32::from(self.depth.max_level());
+        let level = (Into::<f32>::into(v) * max).round() as u8;
+        self.set_level(x, y, level);
+    }
+    /// Clear all pixels to level zero.
+    pub fn clear(&mut self) {
+        for b in self.buffer.iter_mut() {
+            *b = 0;
+        }
+    }
+    /// Get `Region` of entire `PackedRaster`.
+    pub fn region(&self) -> Region {
+        Region::new(0, 0, self.width(), self.height())
+    }
+    /// Get an `Iterator` of pixels within a `Region`.
+    ///
+    /// * `reg` Region within `PackedRaster`.
+    pub fn region_iter<R>(&self, reg: R) -> PackedRasterIter<'_, P>
+    where
+        R: Into<Region>,
+    {
+        let reg = self.region().intersection(reg);
+        PackedRasterIter::new(self, reg)
+    }
+    /// Get view of packed pixel data as a `u8` slice.
+    pub fn as_u8_slice(&self) -> &[u8] {
+        &self.buffer
+    }
+    /// Get view of a row of packed pixel data as a `u8` slice.
+    pub fn as_u8_slice_row(&self, y: u32) -> &[u8] {
+        debug_assert!(y < self.height);
+        let stride = self.depth.stride(self.width);
+        let s = stride * y as usize;
+        &self.buffer[s..s + stride]
+    }
+}
+
+impl<'a, P: Pixel> PackedRasterIter<'a, P> {
+    /// Create a new `PackedRaster` pixel `Iterator`.
+    ///
+    /// * `region` Region of pixels to iterate.
+    fn new(raster: &'a PackedRaster<P>, region: Region) -> Self {
+        let y = u32::try_from(region.y).unwrap_or(0);
+        let bottom = u32::try_from(region.bottom()).unwrap_or(0);
+        let x = u32::try_from(region.x).unwrap_or(0);
+        let right = u32::try_from(region.right()).unwrap_or(0);
+        let left = x;
+        PackedRasterIter {
+            raster,
+            left,
+            right,
+            bottom,
+            x,
+            y,
+        }
+    }
+}
+
+impl<'a, P: Pixel> Iterator for PackedRasterIter<'a, P> {
+    type Item = P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.x >= self.right {
+            self.x = self.left;
+            self.y += 1;
+        }
+        if self.y >= self.bottom || self.left >= self.right {
+            return None;
+        }
+        let p = self.raster.pixel(self.x, self.y);
+        self.x += 1;
+        Some(p)
+    }
+}
+
+impl<P: Pixel> RasterBuilder<P> {
+    /// Build a `Raster` by unpacking a
+    /// [PackedRaster](struct.PackedRaster.html).
+    ///
+    /// * `S` `Pixel` format of source `PackedRaster`.
+    ///
+    /// ### Unpack a 4 bpp image to SGray8
+    /// ```
+    /// # use pix::*;
+    /// let packed = PackedRasterBuilder::<SGray8>::new().with_clear(
+    ///     16,
+    ///     16,
+    ///     PackedDepth::Bits4,
+    ///     BitOrder::LsbFirst,
+    /// );
+    /// let gray = RasterBuilder::<SGray8>::new().with_packed(&packed);
+    /// ```
+    pub fn with_packed<S>(self, src: &PackedRaster<S>) -> Raster<P>
+    where
+        S: Pixel,
+        P::Chan: From<S::Chan>,
+    {
+        self.with_rows(src.width(), src.height(), |y, row| {
+            for (x, p) in row.iter_mut().enumerate() {
+                *p = src.pixel(x as u32, y).convert();
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+    use super::*;
+
+    #[test]
+    fn strides() {
+        assert_eq!(PackedDepth::Bits1.stride(9), 2);
+        assert_eq!(PackedDepth::Bits2.stride(9), 3);
+        assert_eq!(PackedDepth::Bits4.stride(9), 5);
+        assert_eq!(PackedDepth::Bits4.stride(u32::MAX), 1 << 31);
+    }
+
+    #[test]
+    fn bit_order() {
+        let mut m = PackedRasterBuilder::<Mask8>::new().with_clear(
+            10,
+            1,
+            PackedDepth::Bits1,
+            BitOrder::MsbFirst,
+        );
+        m.set_pixel(0, 0, Mask8::new(0xFF));
+        m.set_pixel(9, 0, Mask8::new(0x80));
+        m.set_pixel(3, 0, Mask8::new(0x7F));
+        assert_eq!(m.as_u8_slice(), &[0b1000_0000, 0b0100_0000]);
+        let mut l = PackedRasterBuilder::<Mask8>::new().with_clear(
+            10,
+            1,
+            PackedDepth::Bits1,
+            BitOrder::LsbFirst,
+        );
+        l.set_pixel(0, 0, Mask8::new(0xFF));
+        l.set_pixel(9, 0, Mask8::new(0xFF));
+        assert_eq!(l.as_u8_slice(), &[0b0000_0001, 0b0000_0010]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        let mut m = PackedRasterBuilder::<Mask8>::new().with_clear(
+            10,
+            2,
+            PackedDepth::Bits1,
+            BitOrder::MsbFirst,
+        );
+        m.set_pixel(20, 0, Mask8::new(0xFF));
+    }
+
+    #[test]
+    fn try_build() {
+        let r = PackedRasterBuilder::<Mask8>::new().try_with_u8_buffer(
+            9,
+            2,
+            PackedDepth::Bits1,
+            BitOrder::MsbFirst,
+            vec![0; 3],
+        );
+        assert_eq!(
+            r.err(),
+            Some(Error::BufferLength {
+                expected: 4,
+                actual: 3
+            })
+        );
+        let r = PackedRasterBuilder::<Mask8>::new().try_with_clear(
+            1 << 16,
+            1 << 16,
+            PackedDepth::Bits2,
+            BitOrder::LsbFirst,
+        );
+        assert_eq!(r.err(), Some(Error::SizeOverflow));
+    }
+
+    #[test]
+    fn gray2() {
+        let mut r = PackedRasterBuilder::<SGray8>::new().with_u8_buffer(
+            3,
+            2,
+            PackedDepth::Bits2,
+            BitOrder::MsbFirst,
+            vec![0b0001_1011, 0b1110_0100],
+        );
+        assert_eq!(r.level(1, 0), 1);
+        assert_eq!(r.pixel(0, 1), SGray8::new(0xFF));
+        assert_eq!(r.pixel(2, 0), SGray8::new(0xAA));
+        r.set_pixel(2, 1, SGray8::new(0x50));
+        assert_eq!(r.as_u8_slice_row(1), &[0b1110_0100]);
+        let v: Vec<_> = r.region_iter((1, 0, 5, 5)).collect();
+        assert_eq!(
+            v,
+            vec![
+                SGray8::new(0x55),
+                SGray8::new(0xAA),
+                SGray8::new(0xAA),
+                SGray8::new(0x55),
+            ]
+        );
+    }
+
+    #[test]
+    fn gray4() {
+        let r = PackedRasterBuilder::<SGray8>::new().with_u8_buffer(
+            3,
+            1,
+            PackedDepth::Bits4,
+            BitOrder::LsbFirst,
+            vec![0xF1, 0x08],
+        );
+        let g = RasterBuilder::<SGray8>::new().with_packed(&r);
+        assert_eq!(g.as_u8_slice(), &[0x11, 0xFF, 0x88]);
+        let r2 = PackedRasterBuilder::<SGray8>::new().with_raster(
+            &g,
+            PackedDepth::Bits4,
+            BitOrder::LsbFirst,
+        );
+        assert_eq!(r2.as_u8_slice(), &[0xF1, 0x08]);
+    }
+
+    #[test]
+    fn mask1() {
+        let r = PackedRasterBuilder::<Mask8>::new().with_u8_buffer(
+            4,
+            2,
+            PackedDepth::Bits1,
+            BitOrder::MsbFirst,
+            vec![0b1010_1111, 0b0101_0000],
+        );
+        let m = RasterBuilder::<Mask8>::new().with_packed(&r);
+        assert_eq!(
+            m.as_u8_slice(),
+            &[0xFF, 0x00, 0xFF, 0x00, 0x00, 0xFF, 0x00, 0xFF]
+        );
+        assert_eq!(r.region_iter((2, 2, 4, 4)).count(), 0);
+    }
+
+    #[test]
+    fn rgb1() {
+        let mut r = PackedRasterBuilder::<SRgb565>::new().with_clear(
+            2,
+            1,
+            PackedDepth::Bits1,
+            BitOrder::MsbFirst,
+        );
+        r.set_pixel(1, 0, SRgb565::new(0xFF, 0xFF, 0xFF));
+        assert_eq!(r.as_u8_slice(), &[0b0100_0000]);
+        assert_eq!(r.pixel(0, 0), SRgb565::new(0, 0, 0));
+        let mut r = PackedRasterBuilder::<Bgr8>::new().with_clear(
+            2,
+            1,
+            PackedDepth::Bits1,
+            BitOrder::MsbFirst,
+        );
+        r.set_pixel(0, 0, Bgr8::new(0x00, 0x00, 0xFF));
+        assert_eq!(r.as_u8_slice(), &[0b1000_0000]);
+    }
+}
@@ -0,0 +1,390 @@
+// packed_rgb.rs    Packed RGB pixel formats.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::Straight;
+use crate::format::Model;
+use crate::gamma::{self, Linear, Srgb};
+use crate::gamut;
+use crate::packing::{Layout, A2B10G10R10, R4G4B4A4, R5G6B5, X1R5G5B5};
+use crate::raster::{box_pixels, raster_len};
+use crate::{Channel, ColorModel, Error, Pixel, Raster, RasterBuilder};
+use std::marker::PhantomData;
+
+/// RGB [color model] packed into one integer *word*.
+///
+/// The bit layout is determined by a [packing::Layout].  Pixels are stored
+/// in little-endian byte order, so the bytes from `Raster::as_u8_slice`
+/// match the layout exactly, as expected by framebuffers and GPUs.
+///
+/// Components are unpacked to a [Channel] when converting, which is
+/// lossless for every packed value.  Since there are no addressable
+/// components, [components] returns an empty slice; use [red], [green],
+/// [blue] and [alpha] instead.  Alpha is always [straight].
+///
+/// A `Raster` can be built from a buffer of words with [with_word_buffer].
+///
+/// [alpha]: struct.PackedRgb.html#method.alpha
+/// [blue]: struct.PackedRgb.html#method.blue
+/// [Channel]: trait.Channel.html
+/// [color model]: trait.ColorModel.html
+/// [components]: trait.ColorModel.html#tymethod.components
+/// [green]: struct.PackedRgb.html#method.green
+/// [packing::Layout]: packing/trait.Layout.html
+/// [red]: struct.PackedRgb.html#method.red
+/// [straight]: alpha/struct.Straight.html
+/// [with_word_buffer]: struct.RasterBuilder.html#method.with_word_buffer
+///
+/// ```
+/// # use pix::*;
+/// let p: SRgb565 = SRgb8::new(0xFF, 0x80, 0x00).convert();
+/// assert_eq!(p.to_bits(), 0xFC00);
+/// let r = RasterBuilder::<SRgb565>::new().with_color(2, 1, p);
+/// assert_eq!(r.as_u8_slice(), &[0x00, 0xFC, 0x00, 0xFC]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct PackedRgb<L, G>
+where
+    L: Layout,
+    G: gamma::Mode,
+{
+    word: L::Word,
+    gamma: PhantomData<G>,
+}
+
+impl<L, G> ColorModel for PackedRgb<L, G>
+where
+    L: Layout,
+    G: gamma::Mode,
+{
+    type Chan = L::Chan;
+    const MODEL: Model = Model::PackedRgb;
+    const HAS_ALPHA: bool = L::BITS[3] != 0;
+
+    /// Get all non-alpha components (always empty)
+    fn components(&self) -> &[Self::Chan] {
+        &[]
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.component(3)
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        [self.red(), self.green(), self.blue(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        PackedRgb::with_alpha(rgba[0], rgba[1], rgba[2], rgba[3])
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let a = self.to_rgba();
+        let b = rhs.to_rgba();
+        let mut d = [Self::Chan::MIN; 4];
+        for i in 0..4 {
+            d[i] = if a[i] > b[i] { a[i] - b[i] } else { b[i] - a[i] };
+        }
+        PackedRgb::with_rgba(d)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        let a = self.to_rgba();
+        let b = rhs.to_rgba();
+        a.iter().zip(b.iter()).all(|(a, b)| a <= b)
+    }
+}
+
+impl<L, G> Pixel for PackedRgb<L, G>
+where
+    L: Layout,
+    G: gamma::Mode,
+{
+    type Alpha = Straight;
+    type Gamma = G;
+    type Primaries = gamut::Srgb;
+}
+
+impl<L, G> Iterator for PackedRgb<L, G>
+where
+    L: Layout,
+    G: gamma::Mode,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<L, G> PackedRgb<L, G>
+where
+    L: Layout,
+    G: gamma::Mode,
+{
+    /// Create an opaque color by specifying *red*, *green* and *blue*
+    /// values.
+    pub fn new<H>(red: H, green: H, blue: H) -> Self
+    where
+        L::Chan: From<H>,
+    {
+        let red = L::Chan::from(red);
+        let green = L::Chan::from(green);
+        let blue = L::Chan::from(blue);
+        Self::with_alpha::<L::Chan>(red, green, blue, L::Chan::MAX)
+    }
+    /// Create a color by specifying *red*, *green*, *blue* and *alpha*
+    /// values.
+    ///
+    /// Values are rounded to the nearest packed value.  *Alpha* is ignored
+    /// for layouts without alpha bits.
+    pub fn with_alpha<H>(red: H, green: H, blue: H, alpha: H) -> Self
+    where
+        L::Chan: From<H>,
+    {
+        let rgba = [
+            L::Chan::from(red),
+            L::Chan::from(green),
+            L::Chan::from(blue),
+            L::Chan::from(alpha),
+        ];
+        let mut bits = 0;
+        for (i, c) in rgba.iter().enumerate() {
+            let max = ((1u64 << L::BITS[i]) - 1) as f32;
+            let v = (Into::<f32>::into(*c) * max).round() as u32;
+            bits |= v << L::SHIFT[i];
+        }
+        Self::from_bits(bits)
+    }
+    /// Create a color from a packed word.
+    ///
+    /// Bits outside of the layout are kept, but ignored.
+    pub fn from_bits(bits: u32) -> Self {
+        let mut word = L::Word::default();
+        for (i, b) in word.as_mut().iter_mut().enumerate() {
+            *b = (bits >> (i * 8)) as u8;
+        }
+        PackedRgb {
+            word,
+            gamma: PhantomData,
+        }
+    }
+    /// Get the packed word.
+    pub fn to_bits(self) -> u32 {
+        let mut bits = 0;
+        for (i, b) in self.word.as_ref().iter().enumerate() {
+            bits |= u32::from(*b) << (i * 8);
+        }
+        bits
+    }
+    /// Unpack one component.
+    fn component(self, i: usize) -> L::Chan {
+        if L::BITS[i] == 0 {
+            return L::Chan::MAX;
+        }
+        let max = (1u64 << L::BITS[i]) - 1;
+        let v = (u64::from(self.to_bits()) >> L::SHIFT[i]) & max;
+        <L::Chan as From<f32>>::from(v as f32 / max as f32)
+    }
+    /// Get the red component.
+    pub fn red(self) -> L::Chan {
+        self.component(0)
+    }
+    /// Get the green component.
+    pub fn green(self) -> L::Chan {
+        self.component(1)
+    }
+    /// Get the blue component.
+    pub fn blue(self) -> L::Chan {
+        self.component(2)
+    }
+    /// Get the alpha component.
+    pub fn alpha(self) -> L::Chan {
+        self.component(3)
+    }
+}
+
+impl<L, G> RasterBuilder<PackedRgb<L, G>>
+where
+    L: Layout,
+    G: gamma::Mode,
+{
+    /// Build a `Raster` from a `u8` buffer of packed words.
+    ///
+    /// Unlike [with_u8_buffer], this works with every [Layout], including
+    /// those which unpack to [Ch16](struct.Ch16.html) components.
+    ///
+    /// * `B` Owned buffer type (`Vec` or boxed slice).
+    /// * `width` Width of `Raster`.
+    /// * `height` Height of `Raster`.
+    /// * `buffer` Buffer of words, in little-endian byte order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` * `height` overflows, or if `buffer` length is not
+    /// equal to `width` * `height` * the word size, in bytes.
+    ///
+    /// [Layout]: packing/trait.Layout.html
+    /// [with_u8_buffer]: struct.RasterBuilder.html#method.with_u8_buffer
+    ///
+    /// ### Load an RGB10A2 framebuffer
+    /// ```
+    /// # use pix::*;
+    /// let buf = vec![0xFF, 0x03, 0x00, 0xC0];
+    /// let r = RasterBuilder::<SRgb10a2>::new().with_word_buffer(1, 1, buf);
+    /// assert_eq!(r.pixel(0, 0).to_bits(), 0xC000_03FF);
+    /// ```
+    pub fn with_word_buffer<B>(
+        self,
+        width: u32,
+        height: u32,
+        buffer: B,
+    ) -> Raster<PackedRgb<L, G>>
+    where
+        B: Into<Box<[u8]>>,
+    {
+        self.try_with_word_buffer(width, height, buffer)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Try to build a `Raster` from a `u8` buffer of packed words.
+    ///
+    /// This is the same as [with_word_buffer](#method.with_word_buffer), but
+    /// returns an [Error](enum.Error.html) instead of panicking.
+    pub fn try_with_word_buffer<B>(
+        self,
+        width: u32,
+        height: u32,
+        buffer: B,
+    ) -> Result<Raster<PackedRgb<L, G>>, Error>
+    where
+        B: Into<Box<[u8]>>,
+    {
+        let len = raster_len::<PackedRgb<L, G>>(width, height)?;
+        let pixels = box_pixels(buffer.into(), len)?;
+        self.try_with_pixels(width, height, pixels)
+    }
+}
+
+/// [PackedRgb](struct.PackedRgb.html) 16-bit
+/// [R5G6B5](packing/struct.R5G6B5.html)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rgb565 = PackedRgb<R5G6B5, Linear>;
+/// [PackedRgb](struct.PackedRgb.html) 16-bit
+/// [X1R5G5B5](packing/struct.X1R5G5B5.html)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rgb555 = PackedRgb<X1R5G5B5, Linear>;
+/// [PackedRgb](struct.PackedRgb.html) 16-bit
+/// [R4G4B4A4](packing/struct.R4G4B4A4.html)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rgba4444 = PackedRgb<R4G4B4A4, Linear>;
+/// [PackedRgb](struct.PackedRgb.html) 32-bit
+/// [A2B10G10R10](packing/struct.A2B10G10R10.html)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rgb10a2 = PackedRgb<A2B10G10R10, Linear>;
+
+/// [PackedRgb](struct.PackedRgb.html) 16-bit
+/// [R5G6B5](packing/struct.R5G6B5.html)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SRgb565 = PackedRgb<R5G6B5, Srgb>;
+/// [PackedRgb](struct.PackedRgb.html) 16-bit
+/// [X1R5G5B5](packing/struct.X1R5G5B5.html)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SRgb555 = PackedRgb<X1R5G5B5, Srgb>;
+/// [PackedRgb](struct.PackedRgb.html) 16-bit
+/// [R4G4B4A4](packing/struct.R4G4B4A4.html)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SRgba4444 = PackedRgb<R4G4B4A4, Srgb>;
+/// [PackedRgb](struct.PackedRgb.html) 32-bit
+/// [A2B10G10R10](packing/struct.A2B10G10R10.html)
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SRgb10a2 = PackedRgb<A2B10G10R10, Srgb>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+    use super::*;
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<SRgb565>(), 2);
+        assert_eq!(std::mem::size_of::<SRgb555>(), 2);
+        assert_eq!(std::mem::size_of::<SRgba4444>(), 2);
+        assert_eq!(std::mem::size_of::<SRgb10a2>(), 4);
+    }
+
+    #[test]
+    fn rgb565() {
+        let p: SRgb565 = SRgb8::new(0xFF, 0x00, 0x00).convert();
+        assert_eq!(p.to_bits(), 0xF800);
+        let p: SRgb565 = SRgb8::new(0x00, 0xFF, 0x00).convert();
+        assert_eq!(p.to_bits(), 0x07E0);
+        let p = SRgb565::from_bits(0x001F);
+        assert_eq!(SRgb8::new(0x00, 0x00, 0xFF), p.convert());
+        for bits in 0..=0xFFFF {
+            let p = SRgb565::from_bits(bits);
+            let s: SRgb8 = p.convert();
+            let p2: SRgb565 = s.convert();
+            assert_eq!(p, p2);
+        }
+    }
+
+    #[test]
+    fn rgb555() {
+        let p: SRgb555 = SRgb8::new(0xFF, 0x84, 0x08).convert();
+        assert_eq!(p.to_bits(), 0x7E01);
+        assert_eq!(p.alpha(), Ch8::MAX);
+        for bits in 0..=0x7FFF {
+            let p = SRgb555::from_bits(bits);
+            let s: SRgba16 = p.convert();
+            let p2: SRgb555 = s.convert();
+            assert_eq!(p, p2);
+        }
+    }
+
+    #[test]
+    fn rgba4444() {
+        let p: SRgba4444 =
+            SRgba8::with_alpha(0x11, 0x22, 0x33, 0x44).convert();
+        assert_eq!(p.to_bits(), 0x1234);
+        let r = RasterBuilder::<SRgba4444>::new().with_color(1, 1, p);
+        assert_eq!(r.as_u8_slice(), &[0x34, 0x12]);
+        let s: SRgba8 = p.convert();
+        assert_eq!(s, SRgba8::with_alpha(0x11, 0x22, 0x33, 0x44));
+    }
+
+    #[test]
+    fn rgb10a2() {
+        let p: SRgb10a2 = SRgba16::with_alpha(0xFFFF, 0, 0, 0xFFFF).convert();
+        assert_eq!(p.to_bits(), 0xC000_03FF);
+        let r = RasterBuilder::<SRgb10a2>::new().with_color(1, 1, p);
+        assert_eq!(r.as_u8_slice(), &[0xFF, 0x03, 0x00, 0xC0]);
+        let b = vec![0xFF, 0x03, 0x00, 0xC0, 0x00, 0xFC, 0x0F, 0x40];
+        let r = RasterBuilder::<SRgb10a2>::new().with_word_buffer(2, 1, b);
+        assert_eq!(r.pixel(0, 0), p);
+        assert_eq!(r.pixel(1, 0).to_bits(), 0x400F_FC00);
+        assert_eq!(r.pixel(1, 0).red(), Ch16::MIN);
+        assert_eq!(r.pixel(1, 0).green(), Ch16::MAX);
+        let r = RasterBuilder::<SRgb10a2>::new()
+            .try_with_word_buffer(2, 1, vec![0; 7]);
+        assert_eq!(
+            r.err(),
+            Some(Error::BufferLength {
+                expected: 8,
+                actual: 7
+            })
+        );
+        for v in 0..1024 {
+            let bits = v | (v << 10) | (v << 20) | 0x4000_0000;
+            let p = Rgb10a2::from_bits(bits);
+            let s: Rgba16 = p.convert();
+            let p2: Rgb10a2 = s.convert();
+            assert_eq!(p, p2);
+        }
+    }
+}
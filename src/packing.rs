@@ -0,0 +1,89 @@
+// packing.rs   Packed pixel layouts.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+//! Module for packed pixel layout items
+use crate::private::Sealed;
+use crate::{Ch16, Ch8, Channel};
+use std::any::Any;
+use std::fmt::Debug;
+
+/// Bit layout of a [PackedRgb](../struct.PackedRgb.html) pixel.
+///
+/// All components are stored in one little-endian integer *word*.  Layouts
+/// are named from the most significant bits to the least significant bits,
+/// as in Vulkan `_PACK16` / `_PACK32` formats.
+///
+/// This trait is *sealed*, and cannot be implemented outside of this crate.
+pub trait Layout:
+    Any + Copy + Clone + Debug + Default + PartialEq + Send + Sync + Sealed
+{
+    /// Unpacked component `Channel` type
+    type Chan: Channel;
+
+    /// Storage for the packed word, in little-endian byte order
+    type Word: Copy
+        + Clone
+        + Debug
+        + Default
+        + PartialEq
+        + Send
+        + Sync
+        + AsRef<[u8]>
+        + AsMut<[u8]>;
+
+    /// Number of bits for *red*, *green*, *blue* and *alpha* components.
+    /// Layouts without alpha have 0 alpha bits.
+    const BITS: [u32; 4];
+
+    /// Bit offset of *red*, *green*, *blue* and *alpha* components
+    const SHIFT: [u32; 4];
+}
+
+/// 16-bit layout with 5 bits of *red* (11-15), 6 bits of *green* (5-10) and
+/// 5 bits of *blue* (0-4), also known as RGB565
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct R5G6B5;
+
+/// 16-bit layout with 1 unused bit (15), 5 bits of *red* (10-14), 5 bits of
+/// *green* (5-9) and 5 bits of *blue* (0-4), also known as RGB555
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct X1R5G5B5;
+
+/// 16-bit layout with 4 bits of *red* (12-15), *green* (8-11), *blue* (4-7)
+/// and *alpha* (0-3), also known as RGBA4444
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct R4G4B4A4;
+
+/// 32-bit layout with 2 bits of *alpha* (30-31) and 10 bits of *blue*
+/// (20-29), *green* (10-19) and *red* (0-9), also known as RGB10A2
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct A2B10G10R10;
+
+impl Layout for R5G6B5 {
+    type Chan = Ch8;
+    type Word = [u8; 2];
+    const BITS: [u32; 4] = [5, 6, 5, 0];
+    const SHIFT: [u32; 4] = [11, 5, 0, 0];
+}
+
+impl Layout for X1R5G5B5 {
+    type Chan = Ch8;
+    type Word = [u8; 2];
+    const BITS: [u32; 4] = [5, 5, 5, 0];
+    const SHIFT: [u32; 4] = [10, 5, 0, 0];
+}
+
+impl Layout for R4G4B4A4 {
+    type Chan = Ch8;
+    type Word = [u8; 2];
+    const BITS: [u32; 4] = [4, 4, 4, 4];
+    const SHIFT: [u32; 4] = [12, 8, 4, 0];
+}
+
+impl Layout for A2B10G10R10 {
+    type Chan = Ch16;
+    type Word = [u8; 4];
+    const BITS: [u32; 4] = [10, 10, 10, 2];
+    const SHIFT: [u32; 4] = [0, 10, 20, 30];
+}
@@ -10,7 +10,7 @@ pub struct Palette<P>
 where
     P: Pixel<Chan = Ch8>,
 {
-    table: Vec<P>,
+    pub(crate) table: Vec<P>,
     threshold_fn: fn(usize) -> P,
 }
 
@@ -40,9 +40,8 @@ where
     /// Set the threshold function for matching entries.
     ///
     /// * `threshold_fn` Called when checking whether a color matches an
-    ///                  existing entry.  The parameter is the palette table
-    ///                  size.  Returns the maximum `Channel`-wise difference
-    ///                  to match.
+    ///   existing entry.  The parameter is the palette table size.  Returns
+    ///   the maximum `Channel`-wise difference to match.
     pub fn set_threshold_fn(&mut self, threshold_fn: fn(usize) -> P) {
         self.threshold_fn = threshold_fn;
     }
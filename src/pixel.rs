@@ -4,8 +4,11 @@
 // Copyright (c) 2019-2020  Jeron Aldaron Lau
 //
 use crate::alpha::{self, Mode as _};
+use crate::format::Model;
 use crate::gamma::{self, Mode as _};
-use crate::ColorModel;
+use crate::gamut::{self, Mapping};
+use crate::white::Adaptation;
+use crate::{simd, Channel, ColorModel, Raster, RasterBuilder};
 use std::any::{Any, TypeId};
 
 /// Pixel format determines [color model], bit depth, [alpha mode] and
@@ -20,72 +23,79 @@ use std::any::{Any, TypeId};
 ///
 /// ### Type Alias Naming Scheme
 ///
-/// * _Gamma_: `S` for [sRGB] gamma encoding; [linear] if omitted.
-/// * _Color model_: [Gray] / `GrayAlpha` / [Rgb] / `Rgba` / [Mask].
-/// * _Bit depth_: `8` / `16` / `32` for 8-bit integer, 16-bit integer and
-///   32-bit floating-point [channels].
+/// * _Gamma_: `S` for [sRGB], `Rec709` for [Rec. 709], `G18` / `G22` for
+///   [1.8] / [2.2] power law gamma encoding; [linear] if omitted.  `Rec709`,
+///   `G18` and `G22` aliases exist for [Gray] and [Rgb] with 8- and 16-bit
+///   [channels] only.
+/// * _Primaries_: `P3` for [Display P3], `Rec2020` for [Rec. 2020], `Adobe`
+///   for [Adobe RGB] and `AcesCg` for [ACEScg]; [sRGB primaries] if omitted.
+///   These [Rgb] aliases use the standard gamma for their color space
+///   ([linear] for `AcesCg`), so they have no gamma prefix.
+/// * _Color model_: [Gray] / `GrayAlpha` / [Rgb] / `Rgba` / [Bgr] / `Bgra` /
+///   [Argb] / [Mask] / [Cmyk] / `Cmyka` / [Hsv] / `Hsva` / [Hsl] / `Hsla` /
+///   [Hwb] / `Hwba` / [Xyz] / `Xyza` / [Lab] / `Laba` / [Lch] / `Lcha` /
+///   [Oklab] / `Oklaba` / [Oklch] / `Oklcha` / [YCbCr] / `YCbCra`.  [Argb]
+///   aliases always have alpha.  [Xyz], [Lab] and [Lch] aliases always have
+///   [linear] gamma and a [D65] white point; [Oklab] and [Oklch] are always
+///   [linear].  [YCbCr] aliases always have [sRGB] gamma, [BT.601] matrix
+///   coefficients and [full] range.
+/// * _Packed_: `Rgb565` / `Rgb555` / `Rgba4444` / `Rgb10a2` are [PackedRgb]
+///   formats, which include the bit depth in their name.
+/// * _Bit depth_: `8` / `16` / `16f` / `32` / `32h` / `64` for 8-bit
+///   integer, 16-bit integer, 16-bit floating-point, 32-bit floating-point,
+///   32-bit floating-point high dynamic range and 64-bit floating-point
+///   [channels].  `16f`, `32h` and `64` aliases exist for [Gray], [Rgb] and
+///   [Mask] only; `32h` aliases are also [linear] only.
 /// * _Alpha mode_: `p` for [premultiplied]; [straight] if omitted.
 ///
+/// [1.8]: gamma/type.Gamma18.html
+/// [2.2]: gamma/type.Gamma22.html
+/// [ACEScg]: gamut/struct.AcesCg.html
+/// [Adobe RGB]: gamut/struct.AdobeRgb.html
+/// [Argb]: struct.Argb.html
+/// [Bgr]: struct.Bgr.html
+/// [BT.601]: video/struct.Bt601.html
 /// [channels]: trait.Channel.html
+/// [Cmyk]: struct.Cmyk.html
+/// [D65]: white/struct.D65.html
+/// [Display P3]: gamut/struct.DisplayP3.html
+/// [full]: video/struct.Full.html
 /// [gray]: struct.Gray.html
+/// [Hsl]: struct.Hsl.html
+/// [Hsv]: struct.Hsv.html
+/// [Hwb]: struct.Hwb.html
+/// [Lab]: struct.Lab.html
+/// [Lch]: struct.Lch.html
 /// [linear]: gamma/struct.Linear.html
 /// [Mask]: struct.Mask.html
+/// [Oklab]: struct.Oklab.html
+/// [Oklch]: struct.Oklch.html
+/// [PackedRgb]: struct.PackedRgb.html
 /// [premultiplied]: alpha/struct.Premultiplied.html
+/// [Rec. 2020]: gamut/struct.Rec2020.html
+/// [Rec. 709]: gamma/struct.Rec709.html
 /// [Rgb]: struct.Rgb.html
 /// [sRGB]: gamma/struct.Srgb.html
+/// [sRGB primaries]: gamut/struct.Srgb.html
 /// [straight]: alpha/struct.Straight.html
+/// [Xyz]: struct.Xyz.html
+/// [YCbCr]: struct.YCbCr.html
 ///
-/// ### Type Aliases
+/// ### Type Alias Examples
 ///
-/// * Opaque, linear gamma:
-///   [Gray8](type.Gray8.html),
-///   [Gray16](type.Gray16.html),
-///   [Gray32](type.Gray32.html),
-///   [Rgb8](type.Rgb8.html),
-///   [Rgb16](type.Rgb16.html),
-///   [Rgb32](type.Rgb32.html)
-/// * Opaque, sRGB gamma:
-///   [SGray8](type.SGray8.html),
-///   [SGray16](type.SGray16.html),
-///   [SGray32](type.SGray32.html),
-///   [SRgb8](type.SRgb8.html),
-///   [SRgb16](type.SRgb16.html),
-///   [SRgb32](type.SRgb32.html)
-/// * Translucent (straight alpha), linear gamma:
-///   [GrayAlpha8](type.GrayAlpha8.html),
-///   [GrayAlpha16](type.GrayAlpha16.html),
-///   [GrayAlpha32](type.GrayAlpha32.html)
-///   [Rgba8](type.Rgba8.html),
-///   [Rgba16](type.Rgba16.html),
-///   [Rgba32](type.Rgba32.html)
-/// * Translucent (premultiplied alpha), linear gamma:
-///   [GrayAlpha8p](type.GrayAlpha8p.html),
-///   [GrayAlpha16p](type.GrayAlpha16p.html),
-///   [GrayAlpha32p](type.GrayAlpha32p.html)
-///   [Rgba8p](type.Rgba8p.html),
-///   [Rgba16p](type.Rgba16p.html),
-///   [Rgba32p](type.Rgba32p.html)
-/// * Translucent (straight alpha), sRGB gamma:
-///   [SGrayAlpha8](type.SGrayAlpha8.html),
-///   [SGrayAlpha16](type.SGrayAlpha16.html),
-///   [SGrayAlpha32](type.SGrayAlpha32.html)
-///   [SRgba8](type.SRgba8.html),
-///   [SRgba16](type.SRgba16.html),
-///   [SRgba32](type.SRgba32.html)
-/// * Translucent (premultiplied alpha), sRGB gamma:
-///   [SGrayAlpha8p](type.SGrayAlpha8p.html),
-///   [SGrayAlpha16p](type.SGrayAlpha16p.html),
-///   [SGrayAlpha32p](type.SGrayAlpha32p.html)
-///   [SRgba8p](type.SRgba8p.html),
-///   [SRgba16p](type.SRgba16p.html),
-///   [SRgba32p](type.SRgba32p.html)
-/// * Alpha mask:
-///   [Mask8](type.Mask8.html),
-///   [Mask16](type.Mask16.html),
-///   [Mask32](type.Mask32.html)
+/// * [Rgb8](type.Rgb8.html): opaque, linear gamma, 8-bit [Rgb]
+/// * [SGrayAlpha16p](type.SGrayAlpha16p.html): translucent (premultiplied
+///   alpha), sRGB gamma, 16-bit [Gray]
+/// * [P3Rgba16](type.P3Rgba16.html): translucent (straight alpha), sRGB
+///   gamma, [Display P3] primaries, 16-bit [Rgb]
+/// * [Lab32](type.Lab32.html): opaque, [D65] white point, 32-bit
+///   floating-point [Lab]
+/// * [Mask8](type.Mask8.html): 8-bit alpha [Mask]
 ///
 /// This trait is *sealed*, and cannot be implemented outside of this crate.
-pub trait Pixel: Any + Clone + Copy + Default + PartialEq + ColorModel {
+pub trait Pixel:
+    Any + Clone + Copy + Default + PartialEq + Send + Sync + ColorModel
+{
 
     /// Alpha mode
     type Alpha: alpha::Mode;
@@ -93,15 +103,102 @@ pub trait Pixel: Any + Clone + Copy + Default + PartialEq + ColorModel {
     /// Gamma mode
     type Gamma: gamma::Mode;
 
+    /// RGB primaries
+    type Primaries: gamut::Primaries;
+
     /// Convert a pixel to another format
     ///
     /// * `D` Destination format.
+    ///
+    /// This uses the default [Conversion] settings.
+    ///
+    /// [Conversion]: struct.Conversion.html
     fn convert<D>(self) -> D
     where
         D: Pixel,
         D::Chan: From<Self::Chan>,
     {
-        let rgba = self.to_rgba();
+        Conversion::default().convert(self)
+    }
+}
+
+/// Conversion settings for [Pixel] formats.
+///
+/// * *Gamut mapping* adjusts colors outside of the destination gamut.
+///   Default: [clip].
+/// * *Chromatic adaptation* moves colors between the white points of RGB
+///   [primaries].  Default: [Bradford].
+///
+/// [Bradford]: white/enum.Adaptation.html#variant.Bradford
+/// [clip]: gamut/enum.Mapping.html#variant.Clip
+/// [Pixel]: trait.Pixel.html
+/// [primaries]: gamut/trait.Primaries.html
+///
+/// ### Adapt from ACEScg to sRGB with CAT02
+/// ```
+/// # use pix::*;
+/// # use pix::gamut::Mapping;
+/// # use pix::white::Adaptation;
+/// let conv = Conversion::default()
+///     .with_mapping(Mapping::Compress)
+///     .with_adaptation(Adaptation::Cat02);
+/// let p: SRgb8 = conv.convert(AcesCgRgb32::new(0.18, 0.18, 0.18));
+/// assert_eq!(p, SRgb8::new(0x76, 0x76, 0x76));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Conversion {
+    mapping: Mapping,
+    adaptation: Adaptation,
+}
+
+impl Conversion {
+    /// Set the gamut mapping.
+    pub fn with_mapping(mut self, mapping: Mapping) -> Self {
+        self.mapping = mapping;
+        self
+    }
+    /// Set the chromatic adaptation transform.
+    pub fn with_adaptation(mut self, adaptation: Adaptation) -> Self {
+        self.adaptation = adaptation;
+        self
+    }
+    /// Get the gamut mapping.
+    pub fn mapping(&self) -> Mapping {
+        self.mapping
+    }
+    /// Get the chromatic adaptation transform.
+    pub fn adaptation(&self) -> Adaptation {
+        self.adaptation
+    }
+    /// Convert a pixel to another format.
+    ///
+    /// * `S` `Pixel` format of source.
+    /// * `D` Destination format.
+    ///
+    /// ### Compress out-of-gamut colors
+    /// ```
+    /// # use pix::*;
+    /// # use pix::gamut::Mapping;
+    /// let p = P3Rgb8::new(0x00, 0xFF, 0x00);
+    /// let c: SRgb8 = p.convert();
+    /// assert_eq!(c, SRgb8::new(0x00, 0xFF, 0x00));
+    /// let conv = Conversion::default().with_mapping(Mapping::Compress);
+    /// let c: SRgb8 = conv.convert(p);
+    /// assert_eq!(c, SRgb8::new(0x00, 0xFA, 0x5D));
+    /// ```
+    pub fn convert<S, D>(&self, src: S) -> D
+    where
+        S: Pixel,
+        D: Pixel,
+        D::Chan: From<S::Chan>,
+    {
+        if TypeId::of::<S::Primaries>() != TypeId::of::<D::Primaries>()
+            || is_tristimulus(S::MODEL)
+            || is_tristimulus(D::MODEL)
+        {
+            return self.convert_linear(src);
+        }
+        let rgba = src.to_rgba();
         // Convert to destination bit depth
         let mut rgba = [
             D::Chan::from(rgba[0]),
@@ -109,34 +206,144 @@ pub trait Pixel: Any + Clone + Copy + Default + PartialEq + ColorModel {
             D::Chan::from(rgba[2]),
             D::Chan::from(rgba[3]),
         ];
-        if TypeId::of::<Self::Alpha>() != TypeId::of::<D::Alpha>() ||
-           TypeId::of::<Self::Gamma>() != TypeId::of::<D::Gamma>()
+        if TypeId::of::<S::Alpha>() != TypeId::of::<D::Alpha>() ||
+           TypeId::of::<S::Gamma>() != TypeId::of::<D::Gamma>()
         {
-            let (mut components, alpha) = rgba.split_at_mut(3);
-            convert_alpha_gamma::<Self, D>(&mut components, alpha[0]);
+            let (components, alpha) = rgba.split_at_mut(3);
+            self.convert_alpha_gamma::<S, D, _>(components, alpha[0]);
         }
         D::with_rgba(rgba)
     }
-}
-
-/// Convert alpha/gamma between two pixel formats
-fn convert_alpha_gamma<S, D>(components: &mut [D::Chan], alpha: D::Chan)
-where
-    S: Pixel,
-    D: Pixel,
-{
-    // Convert to linear gamma
-    components.iter_mut().for_each(|c| *c = S::Gamma::to_linear(*c));
-    if TypeId::of::<S::Alpha>() != TypeId::of::<D::Alpha>() {
-        for c in components.iter_mut() {
-            // Decode source alpha
-            *c = S::Alpha::decode(*c, alpha);
-            // Encode destination alpha
-            *c = D::Alpha::encode(*c, alpha);
+    /// Convert a slice of pixels to another format.
+    ///
+    /// The results are identical to calling [convert](#method.convert) on
+    /// each pixel.  Between formats with the same color model, channel order
+    /// and primaries, whole slices are converted using SIMD instructions
+    /// detected at runtime (SSE2, AVX2 or NEON).  These cover:
+    ///
+    /// * Channel depth: [Ch8](struct.Ch8.html), [Ch16](struct.Ch16.html) and
+    ///   [Ch32](struct.Ch32.html)
+    /// * Alpha mode: [straight] to [premultiplied], and back
+    /// * [sRGB] gamma: `Ch32`
+    ///
+    /// sRGB gamma uses look-up tables for 8- and 16-bit channels.
+    ///
+    /// [premultiplied]: alpha/struct.Premultiplied.html
+    /// [sRGB]: gamma/struct.Srgb.html
+    /// [straight]: alpha/struct.Straight.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` and `dst` have different lengths.
+    ///
+    /// ### Convert a row of pixels
+    /// ```
+    /// # use pix::*;
+    /// let src = [SRgba8::with_alpha(0xFF, 0x80, 0x00, 0x80); 100];
+    /// let mut dst = [Rgba16p::default(); 100];
+    /// Conversion::default().convert_slice(&src, &mut dst);
+    /// assert_eq!(dst[99], src[99].convert());
+    /// ```
+    pub fn convert_slice<S, D>(&self, src: &[S], dst: &mut [D])
+    where
+        S: Pixel,
+        D: Pixel,
+        D::Chan: From<S::Chan>,
+    {
+        assert_eq!(src.len(), dst.len());
+        if !simd::convert_slice(src, dst) {
+            for (s, d) in src.iter().zip(dst.iter_mut()) {
+                *d = self.convert(*s);
+            }
+        }
+    }
+    /// Convert a `Raster` to another format.
+    ///
+    /// * `S` `Pixel` format of source `Raster`.
+    /// * `D` `Pixel` format of destination `Raster`.
+    ///
+    /// ### Convert an `SRgb8` raster to Display P3
+    /// ```
+    /// # use pix::*;
+    /// let r0 = RasterBuilder::<SRgb8>::new().with_clear(50, 50);
+    /// let r1: Raster<P3Rgb8> = Conversion::default().convert_raster(&r0);
+    /// ```
+    pub fn convert_raster<S, D>(&self, src: &Raster<S>) -> Raster<D>
+    where
+        S: Pixel,
+        D: Pixel,
+        D::Chan: From<S::Chan>,
+    {
+        RasterBuilder::new().with_rows(src.width(), src.height(), |y, row| {
+            self.convert_slice(src.as_slice_row(y), row)
+        })
+    }
+    /// Convert a pixel in linear light, with no loss of precision until
+    /// the destination `Channel` is set
+    fn convert_linear<S, D>(&self, src: S) -> D
+    where
+        S: Pixel,
+        D: Pixel,
+    {
+        let rgba = src.to_rgba_f64();
+        let alpha = rgba[3];
+        // Convert to linear gamma
+        let mut rgb = [
+            S::Gamma::to_linear_f64(rgba[0]),
+            S::Gamma::to_linear_f64(rgba[1]),
+            S::Gamma::to_linear_f64(rgba[2]),
+        ];
+        if TypeId::of::<S::Alpha>() != TypeId::of::<D::Alpha>() {
+            for c in rgb.iter_mut() {
+                // Decode source alpha
+                *c = S::Alpha::decode_f64(*c, alpha);
+                // Encode destination alpha
+                *c = D::Alpha::encode_f64(*c, alpha);
+            }
+        }
+        if TypeId::of::<S::Primaries>() != TypeId::of::<D::Primaries>() {
+            rgb = gamut::convert::<S::Primaries, D::Primaries>(
+                rgb,
+                self.mapping,
+                self.adaptation,
+            );
         }
+        // Convert to destination gamma
+        D::with_rgba_f64([
+            D::Gamma::from_linear_f64(rgb[0]),
+            D::Gamma::from_linear_f64(rgb[1]),
+            D::Gamma::from_linear_f64(rgb[2]),
+            alpha,
+        ])
     }
-    // Convert to destination gamma
-    components.iter_mut().for_each(|c| *c = D::Gamma::from_linear(*c));
+    /// Convert alpha/gamma between two pixel formats
+    fn convert_alpha_gamma<S, D, C>(&self, components: &mut [C], alpha: C)
+    where
+        S: Pixel,
+        D: Pixel,
+        C: Channel,
+    {
+        // Convert to linear gamma
+        components.iter_mut().for_each(|c| *c = S::Gamma::to_linear(*c));
+        if TypeId::of::<S::Alpha>() != TypeId::of::<D::Alpha>() {
+            for c in components.iter_mut() {
+                // Decode source alpha
+                *c = S::Alpha::decode(*c, alpha);
+                // Encode destination alpha
+                *c = D::Alpha::encode(*c, alpha);
+            }
+        }
+        // Convert to destination gamma
+        components.iter_mut().for_each(|c| *c = D::Gamma::from_linear(*c));
+    }
+}
+
+/// Check if a color model is derived from linear light tristimulus values
+fn is_tristimulus(model: Model) -> bool {
+    matches!(
+        model,
+        Model::Xyz | Model::Lab | Model::Lch | Model::Oklab | Model::Oklch
+    )
 }
 
 #[cfg(test)]
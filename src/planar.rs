@@ -0,0 +1,635 @@
+// planar.rs    Planar raster images.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{Opaque, Straight};
+use crate::{video, Ch16, Ch8, Channel, Error, Pixel};
+use crate::{Raster, RasterBuilder, YCbCr};
+use std::marker::PhantomData;
+
+/// Layout of the chroma planes in a [PlanarRaster].
+///
+/// [PlanarRaster]: struct.PlanarRaster.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlanarLayout {
+    /// 4:4:4 *Cb* and *Cr* planes at full resolution (I444)
+    Yuv444,
+    /// 4:2:2 *Cb* and *Cr* planes at half horizontal resolution (I422)
+    Yuv422,
+    /// 4:2:0 *Cb* and *Cr* planes at half horizontal and vertical
+    /// resolution (I420)
+    Yuv420,
+    /// 4:2:0 interleaved *CbCr* plane at half horizontal and vertical
+    /// resolution (NV12)
+    Nv12,
+}
+
+/// Filter for upsampling chroma planes.
+///
+/// Chroma samples are sited at the center of the luma samples they cover.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChromaFilter {
+    /// Nearest chroma sample
+    Nearest,
+    /// Bilinear interpolation of the nearest four chroma samples
+    Bilinear,
+}
+
+/// Builder for [PlanarRaster](struct.PlanarRaster.html) images.
+///
+/// The pixel format must be an opaque, straight alpha [YCbCr] format.
+///
+/// After creating a `PlanarRasterBuilder`, finish building a `PlanarRaster`
+/// using one of the *with_* methods:
+/// * [with_clear](struct.PlanarRasterBuilder.html#method.with_clear)
+/// * [with_raster](struct.PlanarRasterBuilder.html#method.with_raster)
+/// * [with_u8_buffer](struct.PlanarRasterBuilder.html#method.with_u8_buffer)
+/// * [with_u16_buffer](struct.PlanarRasterBuilder.html#method.with_u16_buffer)
+///
+/// [YCbCr]: struct.YCbCr.html
+///
+/// ### Create a `PlanarRaster`
+/// ```
+/// # use pix::*;
+/// let r = PlanarRasterBuilder::<YCbCr8>::new()
+///     .with_clear(64, 48, PlanarLayout::Yuv420);
+/// ```
+pub struct PlanarRasterBuilder<P: Pixel> {
+    _pixel: PhantomData<P>,
+}
+
+/// Image with separate *luma* and *chroma* planes, for video frames.
+///
+/// Chroma planes may be subsampled, as described by a [PlanarLayout].
+/// Convert to an interleaved [Raster] using
+/// `RasterBuilder`::[with_planar].
+///
+/// [PlanarLayout]: enum.PlanarLayout.html
+/// [Raster]: struct.Raster.html
+/// [with_planar]: struct.RasterBuilder.html#method.with_planar
+///
+/// ### Convert an I420 frame to `SRgb8`
+/// ```
+/// # use pix::*;
+/// let mut buf = vec![0x80; 4 * 4];
+/// buf.extend_from_slice(&[0x60; 2 * 2 * 2]);
+/// let frame = PlanarRasterBuilder::<YCbCr8>::new()
+///     .with_u8_buffer(4, 4, PlanarLayout::Yuv420, buf);
+/// let rgb = RasterBuilder::<SRgb8>::new()
+///     .with_planar(&frame, ChromaFilter::Bilinear);
+/// ```
+pub struct PlanarRaster<P: Pixel> {
+    width: u32,
+    height: u32,
+    layout: PlanarLayout,
+    luma: Box<[P::Chan]>,
+    cb: Box<[P::Chan]>,
+    cr: Box<[P::Chan]>,
+}
+
+impl PlanarLayout {
+    /// Get the horizontal and vertical chroma subsampling factors.
+    pub fn subsampling(self) -> (u32, u32) {
+        match self {
+            PlanarLayout::Yuv444 => (1, 1),
+            PlanarLayout::Yuv422 => (2, 1),
+            PlanarLayout::Yuv420 | PlanarLayout::Nv12 => (2, 2),
+        }
+    }
+    /// Get the width and height of the chroma planes.
+    ///
+    /// * `width` Width of luma plane.
+    /// * `height` Height of luma plane.
+    pub fn chroma_size(self, width: u32, height: u32) -> (u32, u32) {
+        let (sx, sy) = self.subsampling();
+        let w = width / sx + u32::from(width % sx != 0);
+        let h = height / sy + u32::from(height % sy != 0);
+        (w, h)
+    }
+    /// Get the number of samples in the luma plane and in each chroma plane,
+    /// or `None` on overflow.
+    fn plane_lens(self, width: u32, height: u32) -> Option<(usize, usize)> {
+        let (cw, ch) = self.chroma_size(width, height);
+        let luma = (width as usize).checked_mul(height as usize)?;
+        let chroma = (cw as usize).checked_mul(ch as usize)?;
+        Some((luma, chroma))
+    }
+    /// Get the total number of samples in all planes, or `None` on overflow.
+    fn len(self, width: u32, height: u32) -> Option<usize> {
+        let (luma, chroma) = self.plane_lens(width, height)?;
+        chroma.checked_mul(2)?.checked_add(luma)
+    }
+}
+
+/// `PlanarRaster` of opaque `YCbCr` pixels
+type OpaquePlanar<C, K, R> = PlanarRaster<YCbCr<C, Opaque<C>, Straight, K, R>>;
+
+/// Get the number of samples in the luma plane and each chroma plane.
+///
+/// The number of pixels (`width` * `height`) must fit in a `u32`, as with
+/// [Raster](struct.Raster.html).
+fn sample_lens(
+    layout: PlanarLayout,
+    width: u32,
+    height: u32,
+) -> Result<(usize, usize), Error> {
+    width.checked_mul(height).ok_or(Error::SizeOverflow)?;
+    layout.plane_lens(width, height).ok_or(Error::SizeOverflow)
+}
+
+impl<P: Pixel> Default for PlanarRasterBuilder<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Pixel> PlanarRasterBuilder<P> {
+    /// Create a new planar raster builder.
+    ///
+    /// * `P` [Pixel](trait.Pixel.html) format.
+    pub fn new() -> Self {
+        let _pixel = PhantomData;
+        PlanarRasterBuilder { _pixel }
+    }
+}
+
+impl<C, K, R> PlanarRasterBuilder<YCbCr<C, Opaque<C>, Straight, K, R>>
+where
+    C: Channel,
+    K: video::Matrix,
+    R: video::Range,
+{
+    /// Build a `PlanarRaster` with all samples set to the default value.
+    ///
+    /// * `width` Width of luma plane.
+    /// * `height` Height of luma plane.
+    /// * `layout` Layout of chroma planes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` * `height` overflows.
+    pub fn with_clear(
+        self,
+        width: u32,
+        height: u32,
+        layout: PlanarLayout,
+    ) -> PlanarRaster<YCbCr<C, Opaque<C>, Straight, K, R>> {
+        self.try_with_clear(width, height, layout)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Try to build a `PlanarRaster` with all samples set to the default
+    /// value.
+    ///
+    /// Returns [Error::SizeOverflow] if `width` * `height` overflows.
+    ///
+    /// [Error::SizeOverflow]: enum.Error.html#variant.SizeOverflow
+    ///
+    /// ## Example
+    /// ```
+    /// # use pix::*;
+    /// let r = PlanarRasterBuilder::<YCbCr8>::new().try_with_clear(
+    ///     1 << 16,
+    ///     1 << 16,
+    ///     PlanarLayout::Yuv420,
+    /// );
+    /// assert_eq!(r.err(), Some(Error::SizeOverflow));
+    /// ```
+    pub fn try_with_clear(
+        self,
+        width: u32,
+        height: u32,
+        layout: PlanarLayout,
+    ) -> Result<OpaquePlanar<C, K, R>, Error> {
+        let clr = YCbCr::<C, Opaque<C>, Straight, K, R>::default();
+        let (luma, chroma) = sample_lens(layout, width, height)?;
+        Ok(PlanarRaster {
+            width,
+            height,
+            layout,
+            luma: vec![clr.y(); luma].into_boxed_slice(),
+            cb: vec![clr.cb(); chroma].into_boxed_slice(),
+            cr: vec![clr.cr(); chroma].into_boxed_slice(),
+        })
+    }
+    /// Build a `PlanarRaster` by copying another `Raster`.
+    ///
+    /// Subsampled chroma is the average of the samples it covers.
+    ///
+    /// * `S` `Pixel` format of source `Raster`.
+    /// * `layout` Layout of chroma planes.
+    ///
+    /// ### Convert from SRgb8 to I420
+    /// ```
+    /// # use pix::*;
+    /// let r0 = RasterBuilder::<SRgb8>::new().with_clear(64, 48);
+    /// let r1 = PlanarRasterBuilder::<YCbCr8>::new()
+    ///     .with_raster(&r0, PlanarLayout::Yuv420);
+    /// ```
+    pub fn with_raster<S>(
+        self,
+        src: &Raster<S>,
+        layout: PlanarLayout,
+    ) -> PlanarRaster<YCbCr<C, Opaque<C>, Straight, K, R>>
+    where
+        S: Pixel,
+        C: From<S::Chan>,
+    {
+        let (width, height) = (src.width(), src.height());
+        let mut r = self.with_clear(width, height, layout);
+        let (sx, sy) = layout.subsampling();
+        let (cw, ch) = layout.chroma_size(width, height);
+        for (i, p) in src.as_slice().iter().enumerate() {
+            let p: YCbCr<C, Opaque<C>, Straight, K, R> = p.convert();
+            r.luma[i] = p.y();
+        }
+        for cy in 0..ch {
+            for cx in 0..cw {
+                let (mut cb, mut cr, mut n) = (0.0, 0.0, 0.0);
+                for y in cy * sy..((cy + 1) * sy).min(height) {
+                    for x in cx * sx..((cx + 1) * sx).min(width) {
+                        let p: YCbCr<C, Opaque<C>, Straight, K, R> =
+                            src.pixel(x, y).convert();
+                        cb += Into::<f32>::into(p.cb());
+                        cr += Into::<f32>::into(p.cr());
+                        n += 1.0;
+                    }
+                }
+                let i = cy as usize * cw as usize + cx as usize;
+                r.cb[i] = <C as From<f32>>::from(cb / n);
+                r.cr[i] = <C as From<f32>>::from(cr / n);
+            }
+        }
+        r
+    }
+}
+
+impl<K, R> PlanarRasterBuilder<YCbCr<Ch8, Opaque<Ch8>, Straight, K, R>>
+where
+    K: video::Matrix,
+    R: video::Range,
+{
+    /// Build a `PlanarRaster` from a `u8` buffer.
+    ///
+    /// * `B` Owned buffer type (`Vec` or boxed slice).
+    /// * `width` Width of luma plane.
+    /// * `height` Height of luma plane.
+    /// * `layout` Layout of chroma planes.
+    /// * `buffer` Buffer of sample data: the luma plane, followed by the
+    ///   chroma planes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` * `height` overflows, or if `buffer` length is not
+    /// equal to the total number of samples in all planes.
+    pub fn with_u8_buffer<B>(
+        self,
+        width: u32,
+        height: u32,
+        layout: PlanarLayout,
+        buffer: B,
+    ) -> PlanarRaster<YCbCr<Ch8, Opaque<Ch8>, Straight, K, R>>
+    where
+        B: Into<Box<[u8]>>,
+    {
+        self.try_with_u8_buffer(width, height, layout, buffer)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Try to build a `PlanarRaster` from a `u8` buffer.
+    ///
+    /// This is the same as [with_u8_buffer](#method.with_u8_buffer), but
+    /// returns an [Error](enum.Error.html) instead of panicking.
+    pub fn try_with_u8_buffer<B>(
+        self,
+        width: u32,
+        height: u32,
+        layout: PlanarLayout,
+        buffer: B,
+    ) -> Result<OpaquePlanar<Ch8, K, R>, Error>
+    where
+        B: Into<Box<[u8]>>,
+    {
+        let buffer: Box<[u8]> = buffer.into();
+        let samples: Vec<Ch8> = buffer.iter().map(|v| Ch8::new(*v)).collect();
+        PlanarRaster::try_with_samples(width, height, layout, &samples)
+    }
+}
+
+impl<K, R> PlanarRasterBuilder<YCbCr<Ch16, Opaque<Ch16>, Straight, K, R>>
+where
+    K: video::Matrix,
+    R: video::Range,
+{
+    /// Build a `PlanarRaster` from a `u16` buffer.
+    ///
+    /// * `B` Owned buffer type (`Vec` or boxed slice).
+    /// * `width` Width of luma plane.
+    /// * `height` Height of luma plane.
+    /// * `layout` Layout of chroma planes.
+    /// * `buffer` Buffer of sample data: the luma plane, followed by the
+    ///   chroma planes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` * `height` overflows, or if `buffer` length is not
+    /// equal to the total number of samples in all planes.
+    pub fn with_u16_buffer<B>(
+        self,
+        width: u32,
+        height: u32,
+        layout: PlanarLayout,
+        buffer: B,
+    ) -> PlanarRaster<YCbCr<Ch16, Opaque<Ch16>, Straight, K, R>>
+    where
+        B: Into<Box<[u16]>>,
+    {
+        self.try_with_u16_buffer(width, height, layout, buffer)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Try to build a `PlanarRaster` from a `u16` buffer.
+    ///
+    /// This is the same as [with_u16_buffer](#method.with_u16_buffer), but
+    /// returns an [Error](enum.Error.html) instead of panicking.
+    pub fn try_with_u16_buffer<B>(
+        self,
+        width: u32,
+        height: u32,
+        layout: PlanarLayout,
+        buffer: B,
+    ) -> Result<OpaquePlanar<Ch16, K, R>, Error>
+    where
+        B: Into<Box<[u16]>>,
+    {
+        let buffer: Box<[u16]> = buffer.into();
+        let samples: Vec<Ch16> = buffer.iter().map(|v| Ch16::new(*v)).collect();
+        PlanarRaster::try_with_samples(width, height, layout, &samples)
+    }
+}
+
+impl<P: Pixel> PlanarRaster<P> {
+    /// Try to create a `PlanarRaster` from a slice of samples.
+    fn try_with_samples(
+        width: u32,
+        height: u32,
+        layout: PlanarLayout,
+        samples: &[P::Chan],
+    ) -> Result<Self, Error> {
+        let (luma, _) = sample_lens(layout, width, height)?;
+        let len = layout.len(width, height).ok_or(Error::SizeOverflow)?;
+        if samples.len() != len {
+            return Err(Error::BufferLength {
+                expected: len,
+                actual: samples.len(),
+            });
+        }
+        let (luma, chroma) = samples.split_at(luma);
+        let (cb, cr): (Vec<_>, Vec<_>) = match layout {
+            PlanarLayout::Nv12 => {
+                chroma.chunks_exact(2).map(|c| (c[0], c[1])).unzip()
+            }
+            _ => {
+                let (cb, cr) = chroma.split_at(chroma.len() / 2);
+                (cb.to_vec(), cr.to_vec())
+            }
+        };
+        Ok(PlanarRaster {
+            width,
+            height,
+            layout,
+            luma: luma.into(),
+            cb: cb.into(),
+            cr: cr.into(),
+        })
+    }
+    /// Get width of `PlanarRaster` (luma plane).
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    /// Get height of `PlanarRaster` (luma plane).
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    /// Get layout of chroma planes.
+    pub fn layout(&self) -> PlanarLayout {
+        self.layout
+    }
+    /// Get view of the luma plane as a slice.
+    pub fn luma_plane(&self) -> &[P::Chan] {
+        &self.luma
+    }
+    /// Get view of the *Cb* chroma plane as a slice.
+    pub fn cb_plane(&self) -> &[P::Chan] {
+        &self.cb
+    }
+    /// Get view of the *Cr* chroma plane as a slice.
+    pub fn cr_plane(&self) -> &[P::Chan] {
+        &self.cr
+    }
+    /// Sample one chroma plane.
+    fn chroma(
+        &self,
+        plane: &[P::Chan],
+        x: u32,
+        y: u32,
+        filter: ChromaFilter,
+    ) -> P::Chan {
+        let (sx, sy) = self.layout.subsampling();
+        let (cw, ch) = self.layout.chroma_size(self.width, self.height);
+        match filter {
+            ChromaFilter::Nearest => {
+                plane[(y / sy) as usize * cw as usize + (x / sx) as usize]
+            }
+            ChromaFilter::Bilinear => {
+                let fx = ((x as f32 + 0.5) / sx as f32 - 0.5).max(0.0);
+                let fy = ((y as f32 + 0.5) / sy as f32 - 0.5).max(0.0);
+                let (x0, y0) = (fx as u32, fy as u32);
+                let (x1, y1) = ((x0 + 1).min(cw - 1), (y0 + 1).min(ch - 1));
+                let (tx, ty) = (fx - x0 as f32, fy - y0 as f32);
+                let s = |x: u32, y: u32| -> f32 {
+                    plane[y as usize * cw as usize + x as usize].into()
+                };
+                let top = s(x0, y0) * (1.0 - tx) + s(x1, y0) * tx;
+                let bottom = s(x0, y1) * (1.0 - tx) + s(x1, y1) * tx;
+                <P::Chan as From<f32>>::from(top * (1.0 - ty) + bottom * ty)
+            }
+        }
+    }
+}
+
+impl<C, K, R> PlanarRaster<YCbCr<C, Opaque<C>, Straight, K, R>>
+where
+    C: Channel,
+    K: video::Matrix,
+    R: video::Range,
+{
+    /// Get one pixel, upsampling chroma with a filter.
+    pub fn pixel(
+        &self,
+        x: u32,
+        y: u32,
+        filter: ChromaFilter,
+    ) -> YCbCr<C, Opaque<C>, Straight, K, R> {
+        assert!(x < self.width && y < self.height);
+        let luma = self.luma[y as usize * self.width as usize + x as usize];
+        let cb = self.chroma(&self.cb, x, y, filter);
+        let cr = self.chroma(&self.cr, x, y, filter);
+        YCbCr::new(luma, cb, cr)
+    }
+}
+
+impl<P: Pixel> RasterBuilder<P> {
+    /// Build a `Raster` by converting a
+    /// [PlanarRaster](struct.PlanarRaster.html).
+    ///
+    /// * `filter` Chroma upsampling filter.
+    ///
+    /// ### Convert from NV12 to SRgb8
+    /// ```
+    /// # use pix::*;
+    /// let frame = PlanarRasterBuilder::<YCbCr8>::new()
+    ///     .with_clear(32, 32, PlanarLayout::Nv12);
+    /// let rgb = RasterBuilder::<SRgb8>::new()
+    ///     .with_planar(&frame, ChromaFilter::Nearest);
+    /// ```
+    pub fn with_planar<C, K, R>(
+        self,
+        src: &PlanarRaster<YCbCr<C, Opaque<C>, Straight, K, R>>,
+        filter: ChromaFilter,
+    ) -> Raster<P>
+    where
+        C: Channel,
+        K: video::Matrix,
+        R: video::Range,
+        P::Chan: From<C>,
+    {
+        self.with_rows(src.width(), src.height(), |y, row| {
+            for (x, p) in row.iter_mut().enumerate() {
+                *p = src.pixel(x as u32, y, filter).convert();
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+    use super::*;
+
+    #[test]
+    fn layout_sizes() {
+        assert_eq!(PlanarLayout::Yuv444.chroma_size(5, 3), (5, 3));
+        assert_eq!(PlanarLayout::Yuv422.chroma_size(5, 3), (3, 3));
+        assert_eq!(PlanarLayout::Yuv420.chroma_size(5, 3), (3, 2));
+        assert_eq!(PlanarLayout::Nv12.chroma_size(4, 4), (2, 2));
+        assert_eq!(PlanarLayout::Yuv420.len(4, 4), Some(24));
+        assert_eq!(PlanarLayout::Yuv444.len(u32::MAX, u32::MAX), None);
+    }
+
+    #[test]
+    fn i420_nv12() {
+        let luma = [16, 32, 48, 64, 80, 96, 112, 128];
+        let mut i420 = luma.to_vec();
+        i420.extend_from_slice(&[10, 20, 30, 40]);
+        let mut nv12 = luma.to_vec();
+        nv12.extend_from_slice(&[10, 30, 20, 40]);
+        let a = PlanarRasterBuilder::<YCbCr8>::new().with_u8_buffer(
+            4,
+            2,
+            PlanarLayout::Yuv420,
+            i420,
+        );
+        let b = PlanarRasterBuilder::<YCbCr8>::new().with_u8_buffer(
+            4,
+            2,
+            PlanarLayout::Nv12,
+            nv12,
+        );
+        assert_eq!(a.cb_plane(), b.cb_plane());
+        assert_eq!(a.cr_plane(), b.cr_plane());
+        let p = a.pixel(3, 1, ChromaFilter::Nearest);
+        assert_eq!(p, YCbCr8::new(128, 20, 40));
+        assert_eq!(p, b.pixel(3, 1, ChromaFilter::Nearest));
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        let r = PlanarRasterBuilder::<YCbCr8>::new().with_u8_buffer(
+            2,
+            2,
+            PlanarLayout::Yuv444,
+            vec![0x80; 12],
+        );
+        let _ = r.pixel(2, 0, ChromaFilter::Nearest);
+    }
+
+    #[test]
+    fn try_build() {
+        let r = PlanarRasterBuilder::<YCbCr8>::new().try_with_u8_buffer(
+            4,
+            2,
+            PlanarLayout::Yuv420,
+            vec![0; 11],
+        );
+        assert_eq!(
+            r.err(),
+            Some(Error::BufferLength {
+                expected: 12,
+                actual: 11
+            })
+        );
+        let r = PlanarRasterBuilder::<YCbCr16>::new().try_with_u16_buffer(
+            1 << 16,
+            1 << 16,
+            PlanarLayout::Yuv444,
+            vec![0; 12],
+        );
+        assert_eq!(r.err(), Some(Error::SizeOverflow));
+    }
+
+    #[test]
+    fn bilinear() {
+        let mut buf = vec![0x80; 4];
+        buf.extend_from_slice(&[0, 200, 100, 100]);
+        let r = PlanarRasterBuilder::<YCbCr8>::new().with_u8_buffer(
+            4,
+            1,
+            PlanarLayout::Yuv422,
+            buf,
+        );
+        let cb: Vec<u8> = (0..4)
+            .map(|x| r.pixel(x, 0, ChromaFilter::Bilinear).cb().into())
+            .collect();
+        assert_eq!(cb, [0, 50, 150, 200]);
+        let cb: Vec<u8> = (0..4)
+            .map(|x| r.pixel(x, 0, ChromaFilter::Nearest).cb().into())
+            .collect();
+        assert_eq!(cb, [0, 0, 200, 200]);
+    }
+
+    #[test]
+    fn u16_buffer() {
+        let buf = vec![0xFFFF, 0x8000, 0x8000];
+        let r = PlanarRasterBuilder::<YCbCr16>::new().with_u16_buffer(
+            1,
+            1,
+            PlanarLayout::Yuv420,
+            buf,
+        );
+        let rgb = RasterBuilder::<SRgb16>::new()
+            .with_planar(&r, ChromaFilter::Bilinear);
+        assert_eq!(rgb.pixel(0, 0), SRgb16::new(0xFFFF, 0xFFFF, 0xFFFF));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut r = RasterBuilder::<SRgb8>::new().with_clear(6, 4);
+        r.set_region((0, 0, 2, 2), SRgb8::new(0xFF, 0x00, 0x00));
+        r.set_region((2, 0, 4, 4), SRgb8::new(0x20, 0x40, 0xA0));
+        r.set_region((0, 2, 2, 2), SRgb8::new(0xFF, 0xFF, 0xFF));
+        let p = PlanarRasterBuilder::<YCbCr16>::new()
+            .with_raster(&r, PlanarLayout::Yuv420);
+        let r2 = RasterBuilder::<SRgb8>::new()
+            .with_planar(&p, ChromaFilter::Nearest);
+        assert_eq!(r.as_slice(), r2.as_slice());
+    }
+}
@@ -0,0 +1,110 @@
+// pod.rs       Plain old data (bytemuck) impls.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+//! `Zeroable` is implemented for all channels, and `Pod` only for integer
+//! channels, since every bit pattern is a valid `Ch8` or `Ch16`.  Floating
+//! point channels may not be NaN or out of range.
+//!
+//! Pixel types are `#[repr(C)]`, with components and alpha of one channel
+//! type (or zero-sized), so they have no padding.
+use crate::alpha::{self, AChannel, Opaque, Translucent};
+use crate::packing::Layout;
+use crate::{
+    gamma, gamut, video, white, Argb, Bgr, Ch16, Ch16f, Ch32, Ch32h, Ch64, Ch8,
+    Channel, Cmyk, Gray, Hsl, Hsv, Hwb, Lab, Lch, Mask, Oklab, Oklch,
+    PackedRgb, Rgb, Xyz, YCbCr,
+};
+use bytemuck::{Pod, Zeroable};
+
+unsafe impl Zeroable for Ch8 {}
+unsafe impl Pod for Ch8 {}
+unsafe impl Zeroable for Ch16 {}
+unsafe impl Pod for Ch16 {}
+unsafe impl Zeroable for Ch16f {}
+unsafe impl Zeroable for Ch32 {}
+unsafe impl Zeroable for Ch32h {}
+unsafe impl Zeroable for Ch64 {}
+
+unsafe impl<C: Channel> Zeroable for Opaque<C> {}
+unsafe impl<C: Channel> Pod for Opaque<C> {}
+unsafe impl<C: Channel + Zeroable> Zeroable for Translucent<C> {}
+unsafe impl<C: Channel + Pod> Pod for Translucent<C> {}
+
+/// Implement `Zeroable` and `Pod` for a pixel type
+macro_rules! pod_pixel {
+    ($px:ident<$($p:ident: $bound:path),*>) => {
+        unsafe impl<C, A, M, $($p),*> Zeroable for $px<C, A, M, $($p),*>
+        where
+            C: Channel + Zeroable,
+            A: AChannel<Chan = C> + Zeroable,
+            M: alpha::Mode,
+            $($p: $bound),*
+        {
+        }
+        unsafe impl<C, A, M, $($p),*> Pod for $px<C, A, M, $($p),*>
+        where
+            C: Channel + Pod,
+            A: AChannel<Chan = C> + Pod,
+            M: alpha::Mode,
+            $($p: $bound),*
+        {
+        }
+    };
+}
+
+pod_pixel!(Argb<G: gamma::Mode>);
+pod_pixel!(Bgr<G: gamma::Mode>);
+pod_pixel!(Cmyk<G: gamma::Mode>);
+pod_pixel!(Gray<G: gamma::Mode>);
+pod_pixel!(Hsl<G: gamma::Mode>);
+pod_pixel!(Hsv<G: gamma::Mode>);
+pod_pixel!(Hwb<G: gamma::Mode>);
+pod_pixel!(Lab<W: white::Point>);
+pod_pixel!(Lch<W: white::Point>);
+pod_pixel!(Oklab<>);
+pod_pixel!(Oklch<>);
+pod_pixel!(Rgb<G: gamma::Mode, P: gamut::Primaries>);
+pod_pixel!(Xyz<W: white::Point>);
+pod_pixel!(YCbCr<K: video::Matrix, R: video::Range>);
+
+unsafe impl<C: Channel + Zeroable> Zeroable for Mask<C> {}
+unsafe impl<C: Channel + Pod> Pod for Mask<C> {}
+
+unsafe impl<L, G> Zeroable for PackedRgb<L, G>
+where
+    L: Layout,
+    L::Word: Zeroable,
+    G: gamma::Mode,
+{
+}
+
+unsafe impl<L, G> Pod for PackedRgb<L, G>
+where
+    L: Layout,
+    L::Word: Pod,
+    G: gamma::Mode,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn casts() {
+        let p = [
+            SRgba8::with_alpha(1, 2, 3, 4),
+            SRgba8::with_alpha(5, 6, 7, 8),
+        ];
+        assert_eq!(bytemuck::cast_slice::<_, u8>(&p), [1, 2, 3, 4, 5, 6, 7, 8]);
+        let p: SGray16 = bytemuck::cast(0x1234_u16);
+        assert_eq!(p, SGray16::new(0x1234));
+        let p: Rgb8 = bytemuck::cast([1_u8, 2, 3]);
+        assert_eq!(p, Rgb8::new(1, 2, 3));
+        let p: SRgb565 = bytemuck::cast([0x00_u8, 0xFC]);
+        assert_eq!(p.to_bits(), 0xFC00);
+        let p: Rgba32p = bytemuck::Zeroable::zeroed();
+        assert_eq!(p, Rgba32p::default());
+    }
+}
@@ -4,7 +4,14 @@
 //
 use crate::alpha::{self, AChannel, Opaque, Translucent};
 use crate::gamma;
-use crate::{Channel, Ch16, Ch32, Ch8, Gray, Mask, Rgb};
+use crate::gamut;
+use crate::packing;
+use crate::video;
+use crate::white;
+use crate::{
+    Argb, Bgr, Ch16, Ch16f, Ch32, Ch32h, Ch64, Ch8, Channel, Cmyk, Gray, Hsl,
+    Hsv, Hwb, Lab, Lch, Mask, Oklab, Oklch, PackedRgb, Rgb, Xyz, YCbCr,
+};
 
 /// Sealed trait to prevent outside crates from implementing traits
 pub trait Sealed {}
@@ -21,12 +28,70 @@ impl Sealed for gamma::Linear {}
 
 impl Sealed for gamma::Srgb {}
 
+impl Sealed for gamma::Rec709 {}
+
+impl<const N: u32, const D: u32> Sealed for gamma::PowerLaw<N, D> {}
+
+impl<const NITS: u32> Sealed for gamma::Pq<NITS> {}
+
+impl<const PEAK: u32, const NITS: u32> Sealed for gamma::Hlg<PEAK, NITS> {}
+
+impl Sealed for gamut::Srgb {}
+
+impl Sealed for gamut::DisplayP3 {}
+
+impl Sealed for gamut::Rec2020 {}
+
+impl Sealed for gamut::AdobeRgb {}
+
+impl Sealed for gamut::ProPhotoRgb {}
+
+impl Sealed for gamut::AcesCg {}
+
+impl<W: white::Point> Sealed for gamut::CieXyz<W> {}
+
+impl Sealed for video::Bt601 {}
+
+impl Sealed for video::Bt709 {}
+
+impl Sealed for video::Bt2020 {}
+
+impl Sealed for video::Full {}
+
+impl Sealed for video::Limited {}
+
+impl Sealed for packing::R5G6B5 {}
+
+impl Sealed for packing::X1R5G5B5 {}
+
+impl Sealed for packing::R4G4B4A4 {}
+
+impl Sealed for packing::A2B10G10R10 {}
+
+impl Sealed for white::D50 {}
+
+impl Sealed for white::D55 {}
+
+impl Sealed for white::D60 {}
+
+impl Sealed for white::D65 {}
+
+impl Sealed for white::D75 {}
+
+impl Sealed for white::E {}
+
 impl Sealed for Ch8 {}
 
 impl Sealed for Ch16 {}
 
+impl Sealed for Ch16f {}
+
 impl Sealed for Ch32 {}
 
+impl Sealed for Ch32h {}
+
+impl Sealed for Ch64 {}
+
 impl Sealed for u8 {}
 
 impl Sealed for u16 {}
@@ -45,10 +110,112 @@ where
     G: gamma::Mode,
 {}
 
-impl<C, A, M, G> Sealed for Rgb<C, A, M, G>
+impl<C, A, M, G, P> Sealed for Rgb<C, A, M, G, P>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+    P: gamut::Primaries,
+{}
+
+impl<L, G> Sealed for PackedRgb<L, G>
+where
+    L: packing::Layout,
+    G: gamma::Mode,
+{}
+
+impl<C, A, M, G> Sealed for Bgr<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{}
+
+impl<C, A, M, G> Sealed for Argb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{}
+
+impl<C, A, M, G> Sealed for Cmyk<C, A, M, G>
 where
     C: Channel,
     A: AChannel<Chan = C>,
     M: alpha::Mode,
     G: gamma::Mode,
 {}
+
+impl<C, A, M, G> Sealed for Hsl<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{}
+
+impl<C, A, M, G> Sealed for Hsv<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{}
+
+impl<C, A, M, G> Sealed for Hwb<C, A, M, G>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{}
+
+impl<C, A, M, W> Sealed for Lab<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    W: white::Point,
+{}
+
+impl<C, A, M, W> Sealed for Lch<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    W: white::Point,
+{}
+
+impl<C, A, M> Sealed for Oklab<C, A, M>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+{}
+
+impl<C, A, M> Sealed for Oklch<C, A, M>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+{}
+
+impl<C, A, M, W> Sealed for Xyz<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    W: white::Point,
+{}
+
+impl<C, A, M, K, R> Sealed for YCbCr<C, A, M, K, R>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    K: video::Matrix,
+    R: video::Range,
+{}
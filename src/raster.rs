@@ -3,9 +3,10 @@
 // Copyright (c) 2017-2020  Douglas P Lau
 // Copyright (c) 2019-2020  Jeron Aldaron Lau
 //
-use crate::{Ch16, Ch8, Pixel};
+use crate::{Ch16, Ch8, Conversion, Error, Pixel, RasterView, RasterViewMut};
 use std::convert::TryFrom;
 use std::marker::PhantomData;
+use std::mem::{align_of, size_of};
 
 /// Builder for [Raster](struct.Raster.html) images.
 ///
@@ -14,10 +15,16 @@ use std::marker::PhantomData;
 /// * [with_clear](struct.RasterBuilder.html#method.with_clear)
 /// * [with_color](struct.RasterBuilder.html#method.with_color)
 /// * [with_raster](struct.RasterBuilder.html#method.with_raster)
+/// * [with_view](struct.RasterBuilder.html#method.with_view)
 /// * [with_pixels](struct.RasterBuilder.html#method.with_pixels)
+/// * [with_planar](struct.RasterBuilder.html#method.with_planar)
+/// * [with_packed](struct.RasterBuilder.html#method.with_packed)
 /// * [with_u8_buffer](struct.RasterBuilder.html#method.with_u8_buffer)
 /// * [with_u16_buffer](struct.RasterBuilder.html#method.with_u16_buffer)
 ///
+/// Methods which panic on invalid sizes or buffers have `try_with_*` variants,
+/// returning an [Error](enum.Error.html) instead.
+///
 /// ### Create a `Raster`
 /// ```
 /// # use pix::*;
@@ -41,9 +48,11 @@ pub struct Raster<P: Pixel> {
     pixels: Box<[P]>,
 }
 
-/// `Iterator` for pixels within a [Raster](struct.Raster.html).
+/// `Iterator` for pixels within a [Raster](struct.Raster.html) or
+/// [RasterView](struct.RasterView.html).
 ///
-/// Use `Raster`::[region_iter](struct.Raster.html#method.region_iter) to
+/// Use `Raster`::[region_iter](struct.Raster.html#method.region_iter) or
+/// `RasterView`::[region_iter](struct.RasterView.html#method.region_iter) to
 /// create.
 ///
 /// ### All pixels in a `Raster`
@@ -63,7 +72,7 @@ pub struct Raster<P: Pixel> {
 /// let it = gray.region_iter(region);
 /// ```
 pub struct RasterIter<'a, P: Pixel> {
-    raster: &'a Raster<P>,
+    view: RasterView<'a, P>,
     left: u32,
     right: u32,
     bottom: u32,
@@ -86,24 +95,39 @@ pub struct RasterIter<'a, P: Pixel> {
 /// let reg = r.region(); // (0, 0, 100, 100)
 /// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Region {
-    x: i32,
-    y: i32,
-    width: u32,
-    height: u32,
+    pub(crate) x: i32,
+    pub(crate) y: i32,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
 }
 
-impl<P: Pixel> Into<Box<[P]>> for Raster<P> {
+impl<P: Pixel> From<Raster<P>> for Box<[P]> {
     /// Get internal pixel data as boxed slice.
-    fn into(self) -> Box<[P]> {
-        self.pixels
+    fn from(raster: Raster<P>) -> Self {
+        raster.pixels
     }
 }
 
-impl<P: Pixel> Into<Vec<P>> for Raster<P> {
+impl<P: Pixel> From<Raster<P>> for Vec<P> {
     /// Get internal pixel data as `Vec` of pixels.
-    fn into(self) -> Vec<P> {
-        self.pixels.into()
+    fn from(raster: Raster<P>) -> Self {
+        raster.pixels.into()
+    }
+}
+
+/// Get the number of pixels in a `Raster`.
+///
+/// The size in bytes must also fit in an `isize`.
+pub(crate) fn raster_len<P: Pixel>(
+    width: u32,
+    height: u32,
+) -> Result<usize, Error> {
+    let len = width.checked_mul(height).ok_or(Error::SizeOverflow)? as usize;
+    match len.checked_mul(size_of::<P>()) {
+        Some(n) if n <= isize::MAX as usize => Ok(len),
+        _ => Err(Error::SizeOverflow),
     }
 }
 
@@ -131,9 +155,32 @@ impl<P: Pixel> RasterBuilder<P> {
     /// let r3 = RasterBuilder::<SRgb16>::new().with_clear(10, 10);
     /// let r4 = RasterBuilder::<SGrayAlpha32>::new().with_clear(100, 250);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` * `height` overflows.
     pub fn with_clear(self, width: u32, height: u32) -> Raster<P> {
         self.with_color(width, height, P::default())
     }
+    /// Try to build a `Raster` with all pixels set to the default value.
+    ///
+    /// Returns [Error::SizeOverflow] if `width` * `height` overflows.
+    ///
+    /// [Error::SizeOverflow]: enum.Error.html#variant.SizeOverflow
+    ///
+    /// ## Example
+    /// ```
+    /// # use pix::*;
+    /// let r = RasterBuilder::<SRgb8>::new().try_with_clear(1 << 16, 1 << 16);
+    /// assert_eq!(r.err(), Some(Error::SizeOverflow));
+    /// ```
+    pub fn try_with_clear(
+        self,
+        width: u32,
+        height: u32,
+    ) -> Result<Raster<P>, Error> {
+        self.try_with_color(width, height, P::default())
+    }
     /// Build a `Raster` with all pixels set to one color.
     ///
     /// ## Example
@@ -142,17 +189,39 @@ impl<P: Pixel> RasterBuilder<P> {
     /// let clr = SRgb8::new(0x40, 0xAA, 0xBB);
     /// let r = RasterBuilder::<SRgb8>::new().with_color(15, 15, clr);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` * `height` overflows.
     pub fn with_color(self, width: u32, height: u32, clr: P) -> Raster<P> {
-        let len = (width * height) as usize;
+        self.try_with_color(width, height, clr)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Try to build a `Raster` with all pixels set to one color.
+    ///
+    /// Returns [Error::SizeOverflow] if `width` * `height` overflows.
+    ///
+    /// [Error::SizeOverflow]: enum.Error.html#variant.SizeOverflow
+    pub fn try_with_color(
+        self,
+        width: u32,
+        height: u32,
+        clr: P,
+    ) -> Result<Raster<P>, Error> {
+        let len = raster_len::<P>(width, height)?;
         let pixels = vec![clr; len].into_boxed_slice();
-        Raster {
+        Ok(Raster {
             width,
             height,
             pixels,
-        }
+        })
     }
     /// Build a `Raster` by copying another `Raster`.
     ///
+    /// Gamma and alpha are only converted if the modes differ, so copying
+    /// between channel orders (such as [Rgb](struct.Rgb.html) and
+    /// [Bgr](struct.Bgr.html)) is a swizzle.
+    ///
     /// * `S` `Pixel` format of source `Raster`.
     ///
     /// ### Convert from Rgb8 to Rgba16
@@ -167,11 +236,54 @@ impl<P: Pixel> RasterBuilder<P> {
         S: Pixel,
         P::Chan: From<S::Chan>,
     {
-        let mut r = RasterBuilder::new().with_clear(src.width(), src.height());
-        let reg = src.region();
-        r.set_region(reg, src.region_iter(reg));
+        self.with_view(&src.into())
+    }
+    /// Build a `Raster` by copying a `RasterView`.
+    ///
+    /// This has the same semantics as
+    /// [with_raster](struct.RasterBuilder.html#method.with_raster).
+    ///
+    /// * `S` `Pixel` format of source `RasterView`.
+    ///
+    /// ### Copy a padded buffer
+    /// ```
+    /// # use pix::*;
+    /// let buf = vec![0; 32 * 16];
+    /// let v = RasterView::<SGray8>::from_u8_slice(30, 16, 32, &buf);
+    /// let r = RasterBuilder::<SGray16>::new().with_view(&v);
+    /// ```
+    pub fn with_view<S>(self, src: &RasterView<S>) -> Raster<P>
+    where
+        S: Pixel,
+        P::Chan: From<S::Chan>,
+    {
+        self.with_rows(src.width(), src.height(), |y, row| {
+            Conversion::default().convert_slice(src.as_slice_row(y), row)
+        })
+    }
+    /// Build a `Raster` by filling each row of pixels.
+    ///
+    /// Rows are filled in parallel with the `rayon` feature.
+    pub(crate) fn with_rows<F>(self, width: u32, height: u32, f: F) -> Raster<P>
+    where
+        F: Fn(u32, &mut [P]) + Send + Sync,
+    {
+        let mut r = self.with_clear(width, height);
+        RasterViewMut::from(&mut r).for_each_row(f);
         r
     }
+    /// Build a `Raster` by mapping each pixel of another `Raster`.
+    pub(crate) fn with_map<S, F>(self, src: &Raster<S>, f: F) -> Raster<P>
+    where
+        S: Pixel,
+        F: Fn(S) -> P + Send + Sync,
+    {
+        self.with_rows(src.width(), src.height(), |y, row| {
+            for (p, s) in row.iter_mut().zip(src.as_slice_row(y)) {
+                *p = f(*s);
+            }
+        })
+    }
     /// Build a `Raster` with owned pixel data.  You can get ownership of the
     /// pixel data back from the `Raster` as either a `Vec<P>` or a `Box<[P]>`
     /// by calling `into()`.
@@ -183,7 +295,8 @@ impl<P: Pixel> RasterBuilder<P> {
     ///
     /// # Panics
     ///
-    /// Panics if `pixels` length is not equal to `width` * `height`.
+    /// Panics if `width` * `height` overflows, or if `pixels` length is not
+    /// equal to `width` * `height`.
     ///
     /// ## Example
     /// ```
@@ -199,14 +312,36 @@ impl<P: Pixel> RasterBuilder<P> {
     where
         B: Into<Box<[P]>>,
     {
-        let len = (width * height) as usize;
+        self.try_with_pixels(width, height, pixels)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Try to build a `Raster` with owned pixel data.
+    ///
+    /// This is the same as
+    /// [with_pixels](struct.RasterBuilder.html#method.with_pixels), but
+    /// returns an [Error](enum.Error.html) instead of panicking.
+    pub fn try_with_pixels<B>(
+        self,
+        width: u32,
+        height: u32,
+        pixels: B,
+    ) -> Result<Raster<P>, Error>
+    where
+        B: Into<Box<[P]>>,
+    {
+        let len = raster_len::<P>(width, height)?;
         let pixels = pixels.into();
-        assert_eq!(len, pixels.len());
-        Raster {
+        if pixels.len() != len {
+            return Err(Error::BufferLength {
+                expected: len,
+                actual: pixels.len(),
+            });
+        }
+        Ok(Raster {
             width,
             height,
             pixels,
-        }
+        })
     }
     /// Build a `Raster` from a `u8` buffer.
     ///
@@ -217,8 +352,8 @@ impl<P: Pixel> RasterBuilder<P> {
     ///
     /// # Panics
     ///
-    /// Panics if `buffer` length is not equal to `width` * `height` *
-    /// `std::mem::size_of::<P>()`.
+    /// Panics if `width` * `height` overflows, or if `buffer` length is not
+    /// equal to `width` * `height` * `std::mem::size_of::<P>()`.
     pub fn with_u8_buffer<B>(
         self,
         width: u32,
@@ -229,21 +364,32 @@ impl<P: Pixel> RasterBuilder<P> {
         B: Into<Box<[u8]>>,
         P: Pixel<Chan = Ch8>,
     {
-        let len = (width * height) as usize;
+        self.try_with_u8_buffer(width, height, buffer)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Try to build a `Raster` from a `u8` buffer.
+    ///
+    /// This is the same as
+    /// [with_u8_buffer](struct.RasterBuilder.html#method.with_u8_buffer),
+    /// but returns an [Error](enum.Error.html) instead of panicking.
+    pub fn try_with_u8_buffer<B>(
+        self,
+        width: u32,
+        height: u32,
+        buffer: B,
+    ) -> Result<Raster<P>, Error>
+    where
+        B: Into<Box<[u8]>>,
+        P: Pixel<Chan = Ch8>,
+    {
+        let len = raster_len::<P>(width, height)?;
         let buffer: Box<[u8]> = buffer.into();
-        let capacity = buffer.len();
-        assert_eq!(len * std::mem::size_of::<P>(), capacity);
-        let slice = std::boxed::Box::<[u8]>::into_raw(buffer);
-        let pixels: Box<[P]> = unsafe {
-            let ptr = (*slice).as_mut_ptr() as *mut P;
-            let slice = std::slice::from_raw_parts_mut(ptr, len);
-            Box::from_raw(slice)
-        };
-        Raster {
+        let pixels = box_pixels(buffer, len)?;
+        Ok(Raster {
             width,
             height,
             pixels,
-        }
+        })
     }
     /// Build a `Raster` from a `u16` buffer.
     ///
@@ -254,8 +400,14 @@ impl<P: Pixel> RasterBuilder<P> {
     ///
     /// # Panics
     ///
-    /// Panics if `buffer` length is not equal to `width` * `height` *
-    /// `std::mem::size_of::<P>()`.
+    /// Panics if `width` * `height` overflows, or if `buffer` length is not
+    /// equal to `width` * `height` * `std::mem::size_of::<P>()` / 2.
+    ///
+    /// [PackedRgb] formats are stored as bytes, and always panic here; use
+    /// [with_word_buffer] instead.
+    ///
+    /// [PackedRgb]: struct.PackedRgb.html
+    /// [with_word_buffer]: struct.RasterBuilder.html#method.with_word_buffer
     pub fn with_u16_buffer<B>(
         self,
         width: u32,
@@ -266,25 +418,66 @@ impl<P: Pixel> RasterBuilder<P> {
         B: Into<Box<[u16]>>,
         P: Pixel<Chan = Ch16>,
     {
-        let len = (width * height) as usize;
+        self.try_with_u16_buffer(width, height, buffer)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Try to build a `Raster` from a `u16` buffer.
+    ///
+    /// This is the same as
+    /// [with_u16_buffer](struct.RasterBuilder.html#method.with_u16_buffer),
+    /// but returns an [Error](enum.Error.html) instead of panicking.
+    pub fn try_with_u16_buffer<B>(
+        self,
+        width: u32,
+        height: u32,
+        buffer: B,
+    ) -> Result<Raster<P>, Error>
+    where
+        B: Into<Box<[u16]>>,
+        P: Pixel<Chan = Ch16>,
+    {
+        let len = raster_len::<P>(width, height)?;
         let buffer: Box<[u16]> = buffer.into();
-        let capacity = buffer.len();
-        assert_eq!(
-            len * std::mem::size_of::<P>(),
-            capacity * std::mem::size_of::<u16>()
-        );
-        let slice = std::boxed::Box::<[u16]>::into_raw(buffer);
-        let pixels: Box<[P]> = unsafe {
-            let ptr = (*slice).as_mut_ptr() as *mut P;
-            let slice = std::slice::from_raw_parts_mut(ptr, len);
-            Box::from_raw(slice)
-        };
-        Raster {
+        let pixels = box_pixels(buffer, len)?;
+        Ok(Raster {
             width,
             height,
             pixels,
-        }
+        })
+    }
+}
+
+/// Convert a boxed buffer into boxed pixels, without copying.
+///
+/// Every bit pattern of the buffer must be a valid pixel.
+///
+/// * `buffer` Buffer of pixel data.
+/// * `len` Number of pixels.
+pub(crate) fn box_pixels<T, P>(
+    buffer: Box<[T]>,
+    len: usize,
+) -> Result<Box<[P]>, Error>
+where
+    P: Pixel,
+{
+    // The allocation is freed with the layout of `P`, so the alignment and
+    // size in bytes must match exactly
+    if align_of::<P>() != align_of::<T>() {
+        return Err(Error::Misaligned);
     }
+    let bytes = len * size_of::<P>();
+    if buffer.len() * size_of::<T>() != bytes {
+        return Err(Error::BufferLength {
+            expected: bytes / size_of::<T>(),
+            actual: buffer.len(),
+        });
+    }
+    let slice = Box::<[T]>::into_raw(buffer);
+    Ok(unsafe {
+        let ptr = (*slice).as_mut_ptr() as *mut P;
+        let slice = std::slice::from_raw_parts_mut(ptr, len);
+        Box::from_raw(slice)
+    })
 }
 
 impl<P: Pixel> Raster<P> {
@@ -311,22 +504,80 @@ impl<P: Pixel> Raster<P> {
     }
     /// Clear all pixels to format default.
     pub fn clear(&mut self) {
-        for p in self.pixels.iter_mut() {
-            *p = P::default();
-        }
+        RasterViewMut::from(self).clear();
     }
     /// Get `Region` of entire `Raster`.
     pub fn region(&self) -> Region {
         Region::new(0, 0, self.width(), self.height())
     }
+    /// Get a view of a `Region`, sharing pixels with the `Raster`.
+    ///
+    /// * `reg` Region within `Raster`, clipped to its bounds.
+    ///
+    /// ### Copy a tile into another `Raster`
+    /// ```
+    /// # use pix::*;
+    /// let r0 = RasterBuilder::<SRgb8>::new().with_clear(100, 100);
+    /// let tile = r0.view((64, 64, 64, 64));
+    /// assert_eq!(tile.width(), 36);
+    /// let r1 = RasterBuilder::<SRgba16>::new().with_view(&tile);
+    /// ```
+    pub fn view<R>(&self, reg: R) -> RasterView<'_, P>
+    where
+        R: Into<Region>,
+    {
+        RasterView::from(self).view(reg)
+    }
+    /// Get a mutable view of a `Region`, sharing pixels with the `Raster`.
+    ///
+    /// * `reg` Region within `Raster`, clipped to its bounds.
+    pub fn view_mut<R>(&mut self, reg: R) -> RasterViewMut<'_, P>
+    where
+        R: Into<Region>,
+    {
+        RasterViewMut::from(self).into_view_mut(reg.into())
+    }
+    /// Split into disjoint mutable tiles.
+    ///
+    /// * `width` Width of each tile.
+    /// * `height` Height of each tile.
+    ///
+    /// Tiles are in row-major order, each with its `Region` within the
+    /// `Raster`.  Tiles at the right and bottom edges are clipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is zero.
+    ///
+    /// ### Process tiles on separate threads
+    /// ```
+    /// # use pix::*;
+    /// let mut r = RasterBuilder::<SGray8>::new().with_clear(100, 100);
+    /// std::thread::scope(|s| {
+    ///     for (reg, mut tile) in r.tiles_mut(64, 64) {
+    ///         s.spawn(move || {
+    ///             let v = (reg.x() + reg.y()) as u8;
+    ///             tile.set_region(tile.region(), SGray8::new(v));
+    ///         });
+    ///     }
+    /// });
+    /// assert_eq!(r.pixel(99, 99), SGray8::new(128));
+    /// ```
+    pub fn tiles_mut(
+        &mut self,
+        width: u32,
+        height: u32,
+    ) -> Vec<(Region, RasterViewMut<'_, P>)> {
+        RasterViewMut::from(self).into_tiles(width, height)
+    }
     /// Get an `Iterator` of pixels within a `Region`.
     ///
     /// * `reg` Region within `Raster`.
-    pub fn region_iter<R>(&self, reg: R) -> RasterIter<P>
+    pub fn region_iter<R>(&self, reg: R) -> RasterIter<'_, P>
     where
         R: Into<Region>,
     {
-        RasterIter::new(self, reg.into())
+        RasterIter::new(self.into(), reg.into())
     }
     /// Set a `Region` using a pixel `Iterator`.
     ///
@@ -356,37 +607,14 @@ impl<P: Pixel> Raster<P> {
     /// // Regions must have the same shape!
     /// rgb.set_region(dst, gray.region_iter(src));
     /// ```
-    pub fn set_region<R, S, I>(&mut self, reg: R, mut it: I)
+    pub fn set_region<R, S, I>(&mut self, reg: R, it: I)
     where
         R: Into<Region>,
         S: Pixel,
         P::Chan: From<S::Chan>,
         I: Iterator<Item = S>,
     {
-        let reg = reg.into();
-        let x0 = if reg.x >= 0 {
-            reg.x as u32
-        } else {
-            self.width()
-        };
-        let x1 = self.width().min(x0 + reg.width);
-        let (x0, x1) = (x0 as usize, x1 as usize);
-        let y0 = if reg.y >= 0 {
-            reg.y as u32
-        } else {
-            self.height()
-        };
-        let y1 = self.height().min(y0 + reg.height);
-        if y0 < y1 && x0 < x1 {
-            for yi in y0..y1 {
-                let row = self.as_slice_row_mut(yi);
-                for x in x0..x1 {
-                    if let Some(p) = it.next() {
-                        row[x] = p.convert();
-                    }
-                }
-            }
-        }
+        RasterViewMut::from(self).set_region(reg, it)
     }
     /// Get view of pixels as a slice.
     pub fn as_slice(&self) -> &[P] {
@@ -435,18 +663,71 @@ impl<P: Pixel> Raster<P> {
     }
 }
 
+#[cfg(feature = "bytemuck")]
+impl<P: Pixel + bytemuck::Pod> Raster<P> {
+    /// Create a `Raster` by copying a byte slice.
+    ///
+    /// * `width` Width of `Raster`.
+    /// * `height` Height of `Raster`.
+    /// * `bytes` Pixel data (in native-endian byte order).
+    ///
+    /// Since the pixel data is copied, `bytes` need not be aligned for `P`.
+    ///
+    /// Returns an [Error](enum.Error.html) if `width` * `height` overflows,
+    /// or if `bytes` length is not equal to `width` * `height` *
+    /// `std::mem::size_of::<P>()`.
+    ///
+    /// ## Example
+    /// ```
+    /// # use pix::*;
+    /// let bytes = [0x00, 0x10, 0x00, 0x20, 0x00, 0x30];
+    /// let r = Raster::<Gray16>::from_bytes(3, 1, &bytes).unwrap();
+    /// assert_eq!(r.as_bytes(), &bytes);
+    /// ```
+    pub fn from_bytes(
+        width: u32,
+        height: u32,
+        bytes: &[u8],
+    ) -> Result<Self, Error> {
+        let len = raster_len::<P>(width, height)?;
+        let expected = len * size_of::<P>();
+        if bytes.len() != expected {
+            return Err(Error::BufferLength {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+        let mut pixels = vec![P::zeroed(); len].into_boxed_slice();
+        bytemuck::cast_slice_mut(&mut pixels[..]).copy_from_slice(bytes);
+        Ok(Raster {
+            width,
+            height,
+            pixels,
+        })
+    }
+    /// Get view of pixels as a byte slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.pixels)
+    }
+    /// Get view of pixels as a mutable byte slice.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        bytemuck::cast_slice_mut(&mut self.pixels)
+    }
+}
+
 impl<'a, P: Pixel> RasterIter<'a, P> {
     /// Create a new `Raster` pixel `Iterator`.
     ///
+    /// * `view` View of pixels.
     /// * `region` Region of pixels to iterate.
-    fn new(raster: &'a Raster<P>, region: Region) -> Self {
+    pub(crate) fn new(view: RasterView<'a, P>, region: Region) -> Self {
         let y = u32::try_from(region.y).unwrap_or(0);
         let bottom = u32::try_from(region.bottom()).unwrap_or(0);
         let x = u32::try_from(region.x).unwrap_or(0);
         let right = u32::try_from(region.right()).unwrap_or(0);
         let left = x;
         RasterIter {
-            raster,
+            view,
             left,
             right,
             bottom,
@@ -467,7 +748,7 @@ impl<'a, P: Pixel> Iterator for RasterIter<'a, P> {
                 return None;
             }
         }
-        let p = self.raster.pixel(self.x, self.y);
+        let p = self.view.pixel(self.x, self.y);
         self.x += 1;
         Some(p)
     }
@@ -489,6 +770,22 @@ impl Region {
             height,
         }
     }
+    /// Get left side
+    pub fn x(self) -> i32 {
+        self.x
+    }
+    /// Get top side
+    pub fn y(self) -> i32 {
+        self.y
+    }
+    /// Get width
+    pub fn width(self) -> u32 {
+        self.width
+    }
+    /// Get height
+    pub fn height(self) -> u32 {
+        self.height
+    }
     /// Get intersection with another `Region`
     pub fn intersection<R>(self, rhs: R) -> Self
     where
@@ -497,25 +794,25 @@ impl Region {
         let rhs = rhs.into();
         let x0 = self.x.max(rhs.x);
         let x1 = self.right().min(rhs.right());
-        let w = (x1 - x0) as u32;
+        let w = (x1 - x0).max(0) as u32;
         let y0 = self.y.max(rhs.y);
         let y1 = self.bottom().min(rhs.bottom());
-        let h = (y1 - y0) as u32;
+        let h = (y1 - y0).max(0) as u32;
         Region::new(x0, y0, w, h)
     }
     /// Get right side
-    fn right(self) -> i32 {
+    pub(crate) fn right(self) -> i32 {
         let x = i64::from(self.x) + i64::from(self.width);
-        if x < std::i32::MAX.into() {
+        if x < i32::MAX.into() {
             x as i32
         } else {
             self.x
         }
     }
     /// Get bottom side
-    fn bottom(self) -> i32 {
+    pub(crate) fn bottom(self) -> i32 {
         let y = i64::from(self.y) + i64::from(self.height);
-        if y < std::i32::MAX.into() {
+        if y < i32::MAX.into() {
             y as i32
         } else {
             self.y
@@ -642,6 +939,74 @@ mod test {
         assert_eq!(r.as_u8_slice(), &v[..]);
     }
     #[test]
+    fn try_buffers() {
+        let r = RasterBuilder::<Mask8>::new().try_with_clear(1 << 16, 1 << 16);
+        assert_eq!(r.err(), Some(Error::SizeOverflow));
+        let r = RasterBuilder::<Mask8>::new().try_with_pixels(
+            u32::MAX,
+            2,
+            vec![],
+        );
+        assert_eq!(r.err(), Some(Error::SizeOverflow));
+        let r = RasterBuilder::<SGray8>::new().try_with_pixels(
+            3,
+            3,
+            vec![SGray8::new(0); 8],
+        );
+        let e = Error::BufferLength {
+            expected: 9,
+            actual: 8,
+        };
+        assert_eq!(r.err(), Some(e));
+        let r = RasterBuilder::<SRgb8>::new().try_with_u8_buffer(
+            2,
+            2,
+            vec![0; 13],
+        );
+        let e = Error::BufferLength {
+            expected: 12,
+            actual: 13,
+        };
+        assert_eq!(r.err(), Some(e));
+        let r = RasterBuilder::<SGrayAlpha16>::new().try_with_u16_buffer(
+            3,
+            1,
+            vec![0; 5],
+        );
+        let e = Error::BufferLength {
+            expected: 6,
+            actual: 5,
+        };
+        assert_eq!(r.err(), Some(e));
+        let r = RasterBuilder::<SGrayAlpha16>::new().try_with_u16_buffer(
+            3,
+            1,
+            vec![0x8000; 6],
+        );
+        let p = SGrayAlpha16::with_alpha(0x8000, 0x8000);
+        assert_eq!(r.unwrap().pixel(2, 0), p);
+    }
+    #[test]
+    #[cfg(feature = "bytemuck")]
+    fn from_bytes() {
+        let b = [0xFF, 0x01, 0x10, 0x02, 0x20, 0x03, 0x30];
+        let mut r = Raster::<SGray16>::from_bytes(3, 1, &b[1..]).unwrap();
+        assert_eq!(r.pixel(1, 0), SGray16::new(u16::from_ne_bytes([2, 32])));
+        r.as_bytes_mut()[..2].copy_from_slice(&[0x40, 0x50]);
+        assert_eq!(r.as_bytes(), &[0x40, 0x50, 0x02, 0x20, 0x03, 0x30]);
+        let r = Raster::<SGray16>::from_bytes(3, 1, &b);
+        let e = Error::BufferLength {
+            expected: 6,
+            actual: 7,
+        };
+        assert_eq!(r.err(), Some(e));
+    }
+    #[test]
+    #[should_panic]
+    fn overflow() {
+        let _ = RasterBuilder::<Mask8>::new().with_clear(1 << 16, 1 << 16);
+    }
+    #[test]
     fn gray_to_rgb() {
         let mut r = RasterBuilder::<SGray8>::new().with_clear(3, 3);
         r.set_region((2, 0, 4, 2), SGray8::new(0x45));
@@ -690,6 +1055,22 @@ mod test {
         assert_eq!(r.as_u8_slice(), &v[..]);
     }
     #[test]
+    fn rgb_to_hsv() {
+        let mut r = RasterBuilder::<SRgb8>::new().with_clear(2, 2);
+        r.set_region((0, 0, 1, 2), SRgb8::new(0xFF, 0x00, 0x00));
+        r.set_region((1, 1, 1, 1), SRgb8::new(0x00, 0x00, 0x80));
+        let r = RasterBuilder::<SHsv32>::new().with_raster(&r);
+        let v = [
+            SHsv32::new(0.0, 1.0, 1.0),
+            SHsv32::new(0.0, 0.0, 0.0),
+            SHsv32::new(0.0, 1.0, 1.0),
+            SHsv32::new(2.0 / 3.0, 1.0, 128.0 / 255.0),
+        ];
+        assert_eq!(r.as_slice(), &v[..]);
+        let r = RasterBuilder::<SRgb8>::new().with_raster(&r);
+        assert_eq!(r.pixel(1, 1), SRgb8::new(0x00, 0x00, 0x80));
+    }
+    #[test]
     fn copy_region_gray() {
         let mut g0 = RasterBuilder::<SGray16>::new().with_clear(3, 3);
         let mut g1 = RasterBuilder::<Gray16>::new().with_clear(3, 3);
@@ -717,9 +1098,57 @@ mod test {
         let _ = RasterBuilder::<SGrayAlpha8>::new().with_raster(&r);
         let _ = RasterBuilder::<SGrayAlpha16>::new().with_raster(&r);
         let _ = RasterBuilder::<SGrayAlpha32>::new().with_raster(&r);
+        let _ = RasterBuilder::<Rec709Rgb8>::new().with_raster(&r);
+        let _ = RasterBuilder::<Rec709Gray16>::new().with_raster(&r);
+        let _ = RasterBuilder::<G18Rgba8>::new().with_raster(&r);
+        let _ = RasterBuilder::<G22GrayAlpha16p>::new().with_raster(&r);
+        let _ = RasterBuilder::<P3Rgb8>::new().with_raster(&r);
+        let _ = RasterBuilder::<Rec2020Rgba16>::new().with_raster(&r);
+        let _ = RasterBuilder::<AdobeRgba8p>::new().with_raster(&r);
+        let _ = RasterBuilder::<AcesCgRgb32>::new().with_raster(&r);
         let _ = RasterBuilder::<Mask8>::new().with_raster(&r);
         let _ = RasterBuilder::<Mask16>::new().with_raster(&r);
         let _ = RasterBuilder::<Mask32>::new().with_raster(&r);
+        let _ = RasterBuilder::<SHsv8>::new().with_raster(&r);
+        let _ = RasterBuilder::<SHsva16>::new().with_raster(&r);
+        let _ = RasterBuilder::<Hsv32>::new().with_raster(&r);
+        let _ = RasterBuilder::<SHsl8>::new().with_raster(&r);
+        let _ = RasterBuilder::<SHsla16p>::new().with_raster(&r);
+        let _ = RasterBuilder::<Hsl32>::new().with_raster(&r);
+        let _ = RasterBuilder::<SHwb8>::new().with_raster(&r);
+        let _ = RasterBuilder::<SHwba16>::new().with_raster(&r);
+        let _ = RasterBuilder::<Hwb32>::new().with_raster(&r);
+        let _ = RasterBuilder::<Xyz8>::new().with_raster(&r);
+        let _ = RasterBuilder::<Xyza16>::new().with_raster(&r);
+        let _ = RasterBuilder::<Lab16>::new().with_raster(&r);
+        let _ = RasterBuilder::<Laba32p>::new().with_raster(&r);
+        let _ = RasterBuilder::<Lch8>::new().with_raster(&r);
+        let _ = RasterBuilder::<Lcha32>::new().with_raster(&r);
+        let _ = RasterBuilder::<Oklab16>::new().with_raster(&r);
+        let _ = RasterBuilder::<Oklaba8p>::new().with_raster(&r);
+        let _ = RasterBuilder::<Oklch32>::new().with_raster(&r);
+        let _ = RasterBuilder::<Oklcha16>::new().with_raster(&r);
+        let _ = RasterBuilder::<SCmyk8>::new().with_raster(&r);
+        let _ = RasterBuilder::<SCmyka16p>::new().with_raster(&r);
+        let _ = RasterBuilder::<Cmyk32>::new().with_raster(&r);
+        let _ = RasterBuilder::<YCbCr8>::new().with_raster(&r);
+        let _ = RasterBuilder::<YCbCra16p>::new().with_raster(&r);
+        let _ = RasterBuilder::<YCbCr32>::new().with_raster(&r);
+        let _ = RasterBuilder::<Rgba16f>::new().with_raster(&r);
+        let _ = RasterBuilder::<SRgba16fp>::new().with_raster(&r);
+        let _ = RasterBuilder::<SGray16f>::new().with_raster(&r);
+        let _ = RasterBuilder::<Mask16f>::new().with_raster(&r);
+        let _ = RasterBuilder::<Rgba32hp>::new().with_raster(&r);
+        let _ = RasterBuilder::<GrayAlpha32h>::new().with_raster(&r);
+        let _ = RasterBuilder::<SRgba64p>::new().with_raster(&r);
+        let _ = RasterBuilder::<Gray64>::new().with_raster(&r);
+        let _ = RasterBuilder::<Mask64>::new().with_raster(&r);
+        let _ = RasterBuilder::<SRgb565>::new().with_raster(&r);
+        let _ = RasterBuilder::<SRgba4444>::new().with_raster(&r);
+        let _ = RasterBuilder::<Rgb10a2>::new().with_raster(&r);
+        let _ = RasterBuilder::<SBgr8>::new().with_raster(&r);
+        let _ = RasterBuilder::<SBgra8p>::new().with_raster(&r);
+        let _ = RasterBuilder::<Argb16>::new().with_raster(&r);
     }
     #[test]
     fn from_mask8() {
@@ -762,6 +1191,8 @@ mod test {
             Region::new(2, 1, 3, 1),
             r.intersection(Region::new(2, 1, 100, 1))
         );
+        let e = r.intersection(Region::new(6, -4, 2, 2));
+        assert_eq!((e.width(), e.height()), (0, 0));
         Ok(())
     }
 }
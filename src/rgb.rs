@@ -6,41 +6,50 @@
 use crate::alpha::{
     self, AChannel, Mode as _, Opaque, Premultiplied, Straight, Translucent,
 };
+use crate::format::Model;
 use crate::gamma::{self, Linear};
-use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel};
+use crate::gamut;
+use crate::{Ch16, Ch16f, Ch32, Ch32h, Ch64, Ch8, Channel, ColorModel, Pixel};
 use std::marker::PhantomData;
 use std::ops::Mul;
 
 /// RGB additive [color model].
 ///
 /// The components are *red*, *green* and *blue*, with optional *[alpha]*.
+/// The [primaries] default to sRGB.
 ///
 /// [alpha]: alpha/trait.AChannel.html
 /// [channel]: trait.Channel.html
 /// [color model]: trait.ColorModel.html
+/// [primaries]: gamut/trait.Primaries.html
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 #[repr(C)]
-pub struct Rgb<C, A, M, G>
+pub struct Rgb<C, A, M, G, P = gamut::Srgb>
 where
     C: Channel,
     A: AChannel<Chan = C>,
     M: alpha::Mode,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
     components: [C; 3],
     alpha: A,
     mode: PhantomData<M>,
     gamma: PhantomData<G>,
+    primaries: PhantomData<P>,
 }
 
-impl<C, A, M, G> ColorModel for Rgb<C, A, M, G>
+impl<C, A, M, G, P> ColorModel for Rgb<C, A, M, G, P>
 where
     C: Channel,
     A: AChannel<Chan = C> + From<C>,
     M: alpha::Mode,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
     type Chan = C;
+    const MODEL: Model = Model::Rgb;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
 
     /// Get all non-alpha components
     fn components(&self) -> &[Self::Chan] {
@@ -100,23 +109,26 @@ where
     }
 }
 
-impl<C, A, M, G> Pixel for Rgb<C, A, M, G>
+impl<C, A, M, G, P> Pixel for Rgb<C, A, M, G, P>
 where
     C: Channel,
     A: AChannel<Chan = C> + From<C>,
     M: alpha::Mode,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
     type Alpha = M;
     type Gamma = G;
+    type Primaries = P;
 }
 
-impl<C, A, M, G> Iterator for Rgb<C, A, M, G>
+impl<C, A, M, G, P> Iterator for Rgb<C, A, M, G, P>
 where
     C: Channel,
     A: AChannel<Chan = C>,
     M: alpha::Mode,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
     type Item = Self;
 
@@ -125,35 +137,41 @@ where
     }
 }
 
-impl<C, M, G> From<Rgb<C, Translucent<C>, M, G>> for Rgb<C, Opaque<C>, M, G>
+impl<C, M, G, P> From<Rgb<C, Translucent<C>, M, G, P>>
+    for Rgb<C, Opaque<C>, M, G, P>
 where
     C: Channel,
     M: alpha::Mode,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
-    fn from(c: Rgb<C, Translucent<C>, M, G>) -> Self {
+    fn from(c: Rgb<C, Translucent<C>, M, G, P>) -> Self {
         Rgb::new(c.red(), c.green(), c.blue())
     }
 }
 
-impl<C, M, G> From<Rgb<C, Opaque<C>, M, G>> for Rgb<C, Translucent<C>, M, G>
+impl<C, M, G, P> From<Rgb<C, Opaque<C>, M, G, P>>
+    for Rgb<C, Translucent<C>, M, G, P>
 where
     C: Channel,
     M: alpha::Mode,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
-    fn from(c: Rgb<C, Opaque<C>, M, G>) -> Self {
+    fn from(c: Rgb<C, Opaque<C>, M, G, P>) -> Self {
         Rgb::with_alpha(c.red(), c.green(), c.blue(), C::MAX)
     }
 }
 
-impl<C, A, G> From<Rgb<C, A, Straight, G>> for Rgb<C, A, Premultiplied, G>
+impl<C, A, G, P> From<Rgb<C, A, Straight, G, P>>
+    for Rgb<C, A, Premultiplied, G, P>
 where
     C: Channel,
     A: AChannel<Chan = C> + From<C>,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
-    fn from(c: Rgb<C, A, Straight, G>) -> Self {
+    fn from(c: Rgb<C, A, Straight, G, P>) -> Self {
         let red = Premultiplied::encode(c.red(), c.alpha());
         let green = Premultiplied::encode(c.green(), c.alpha());
         let blue = Premultiplied::encode(c.blue(), c.alpha());
@@ -161,13 +179,15 @@ where
     }
 }
 
-impl<C, A, G> From<Rgb<C, A, Premultiplied, G>> for Rgb<C, A, Straight, G>
+impl<C, A, G, P> From<Rgb<C, A, Premultiplied, G, P>>
+    for Rgb<C, A, Straight, G, P>
 where
     C: Channel,
     A: AChannel<Chan = C> + From<C>,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
-    fn from(c: Rgb<C, A, Premultiplied, G>) -> Self {
+    fn from(c: Rgb<C, A, Premultiplied, G, P>) -> Self {
         let red = Premultiplied::decode(c.red(), c.alpha());
         let green = Premultiplied::decode(c.green(), c.alpha());
         let blue = Premultiplied::decode(c.blue(), c.alpha());
@@ -175,12 +195,13 @@ where
     }
 }
 
-impl<C, A, M, G> From<i32> for Rgb<C, A, M, G>
+impl<C, A, M, G, P> From<i32> for Rgb<C, A, M, G, P>
 where
     C: Channel + From<Ch8>,
     A: AChannel<Chan = C> + From<Translucent<Ch8>>,
     M: alpha::Mode,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
     /// Get an `Rgb` from an `i32`
     fn from(c: i32) -> Self {
@@ -192,16 +213,17 @@ where
     }
 }
 
-impl<C, A, M, G> From<Rgb<C, A, M, G>> for i32
+impl<C, A, M, G, P> From<Rgb<C, A, M, G, P>> for i32
 where
     C: Channel,
     Ch8: From<C>,
     A: AChannel<Chan = C> + From<C>,
     M: alpha::Mode,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
     /// Get an `i32` from an `Rgb`
-    fn from(c: Rgb<C, A, M, G>) -> i32 {
+    fn from(c: Rgb<C, A, M, G, P>) -> i32 {
         let red: u8 = Ch8::from(c.red()).into();
         let red = i32::from(red);
         let green: u8 = Ch8::from(c.green()).into();
@@ -214,11 +236,12 @@ where
     }
 }
 
-impl<C, A, G> Mul<Self> for Rgb<C, A, Straight, G>
+impl<C, A, G, P> Mul<Self> for Rgb<C, A, Straight, G, P>
 where
     C: Channel,
     A: AChannel<Chan = C>,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
     type Output = Self;
     fn mul(self, rhs: Self) -> Self::Output {
@@ -232,31 +255,34 @@ where
             alpha,
             mode: PhantomData,
             gamma: PhantomData,
+            primaries: PhantomData,
         }
     }
 }
 
-impl<C, A, G> Mul<Self> for Rgb<C, A, Premultiplied, G>
+impl<C, A, G, P> Mul<Self> for Rgb<C, A, Premultiplied, G, P>
 where
     C: Channel,
     A: AChannel<Chan = C> + From<C>,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
     type Output = Self;
     fn mul(self, rhs: Self) -> Self::Output {
-        let this: Rgb<C, A, Straight, G> = self.into();
-        let other: Rgb<C, A, Straight, G> = rhs.into();
+        let this: Rgb<C, A, Straight, G, P> = self.into();
+        let other: Rgb<C, A, Straight, G, P> = rhs.into();
 
         (this * other).into()
     }
 }
 
-impl<C, A, M, G> Rgb<C, A, M, G>
+impl<C, A, M, G, P> Rgb<C, A, M, G, P>
 where
     C: Channel,
     A: AChannel<Chan = C>,
     M: alpha::Mode,
     G: gamma::Mode,
+    P: gamut::Primaries,
 {
     /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying *red*,
     /// *green* and *blue* values.
@@ -284,6 +310,7 @@ where
             alpha,
             mode: PhantomData,
             gamma: PhantomData,
+            primaries: PhantomData,
         }
     }
     /// Get the red component.
@@ -309,6 +336,19 @@ pub type Rgb16 = Rgb<Ch16, Opaque<Ch16>, Straight, Linear>;
 /// [Rgb](struct.Rgb.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
 /// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
 pub type Rgb32 = Rgb<Ch32, Opaque<Ch32>, Straight, Linear>;
+/// [Rgb](struct.Rgb.html) 16-bit floating-point
+/// [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rgb16f = Rgb<Ch16f, Opaque<Ch16f>, Straight, Linear>;
+/// [Rgb](struct.Rgb.html) 64-bit floating-point
+/// [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rgb64 = Rgb<Ch64, Opaque<Ch64>, Straight, Linear>;
+/// [Rgb](struct.Rgb.html) 32-bit floating-point
+/// [high dynamic range](struct.Ch32h.html) [opaque](alpha/struct.Opaque.html)
+/// (no alpha) [linear](gamma/struct.Linear.html) gamma
+/// [pixel](trait.Pixel.html) format.
+pub type Rgb32h = Rgb<Ch32h, Opaque<Ch32h>, Straight, Linear>;
 
 /// [Rgb](struct.Rgb.html) 8-bit [straight](alpha/struct.Straight.html) alpha
 /// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
@@ -319,6 +359,19 @@ pub type Rgba16 = Rgb<Ch16, Translucent<Ch16>, Straight, Linear>;
 /// [Rgb](struct.Rgb.html) 32-bit [straight](alpha/struct.Straight.html) alpha
 /// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
 pub type Rgba32 = Rgb<Ch32, Translucent<Ch32>, Straight, Linear>;
+/// [Rgb](struct.Rgb.html) 16-bit floating-point
+/// [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rgba16f = Rgb<Ch16f, Translucent<Ch16f>, Straight, Linear>;
+/// [Rgb](struct.Rgb.html) 64-bit floating-point
+/// [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rgba64 = Rgb<Ch64, Translucent<Ch64>, Straight, Linear>;
+/// [Rgb](struct.Rgb.html) 32-bit floating-point
+/// [high dynamic range](struct.Ch32h.html)
+/// [straight](alpha/struct.Straight.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rgba32h = Rgb<Ch32h, Translucent<Ch32h>, Straight, Linear>;
 
 /// [Rgb](struct.Rgb.html) 8-bit
 /// [premultiplied](alpha/struct.Premultiplied.html) alpha
@@ -332,6 +385,19 @@ pub type Rgba16p = Rgb<Ch16, Translucent<Ch16>, Premultiplied, Linear>;
 /// [premultiplied](alpha/struct.Premultiplied.html) alpha
 /// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
 pub type Rgba32p = Rgb<Ch32, Translucent<Ch32>, Premultiplied, Linear>;
+/// [Rgb](struct.Rgb.html) 16-bit floating-point
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rgba16fp = Rgb<Ch16f, Translucent<Ch16f>, Premultiplied, Linear>;
+/// [Rgb](struct.Rgb.html) 64-bit floating-point
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rgba64p = Rgb<Ch64, Translucent<Ch64>, Premultiplied, Linear>;
+/// [Rgb](struct.Rgb.html) 32-bit floating-point
+/// [high dynamic range](struct.Ch32h.html)
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rgba32hp = Rgb<Ch32h, Translucent<Ch32h>, Premultiplied, Linear>;
 
 type SRgb<C, A> = Rgb<C, A, Straight, gamma::Srgb>;
 /// [Rgb](struct.Rgb.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
@@ -343,6 +409,14 @@ pub type SRgb16 = SRgb<Ch16, Opaque<Ch16>>;
 /// [Rgb](struct.Rgb.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
 /// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
 pub type SRgb32 = SRgb<Ch32, Opaque<Ch32>>;
+/// [Rgb](struct.Rgb.html) 16-bit floating-point
+/// [opaque](alpha/struct.Opaque.html) (no alpha) [sRGB](gamma/struct.Srgb.html)
+/// gamma [pixel](trait.Pixel.html) format.
+pub type SRgb16f = SRgb<Ch16f, Opaque<Ch16f>>;
+/// [Rgb](struct.Rgb.html) 64-bit floating-point
+/// [opaque](alpha/struct.Opaque.html) (no alpha) [sRGB](gamma/struct.Srgb.html)
+/// gamma [pixel](trait.Pixel.html) format.
+pub type SRgb64 = SRgb<Ch64, Opaque<Ch64>>;
 
 type SRgba<C, A> = Rgb<C, A, Straight, gamma::Srgb>;
 /// [Rgb](struct.Rgb.html) 8-bit [straight](alpha/struct.Straight.html) alpha
@@ -354,6 +428,14 @@ pub type SRgba16 = SRgba<Ch16, Translucent<Ch16>>;
 /// [Rgb](struct.Rgb.html) 32-bit [straight](alpha/struct.Straight.html) alpha
 /// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
 pub type SRgba32 = SRgba<Ch32, Translucent<Ch32>>;
+/// [Rgb](struct.Rgb.html) 16-bit floating-point
+/// [straight](alpha/struct.Straight.html) alpha [sRGB](gamma/struct.Srgb.html)
+/// gamma [pixel](trait.Pixel.html) format.
+pub type SRgba16f = SRgba<Ch16f, Translucent<Ch16f>>;
+/// [Rgb](struct.Rgb.html) 64-bit floating-point
+/// [straight](alpha/struct.Straight.html) alpha [sRGB](gamma/struct.Srgb.html)
+/// gamma [pixel](trait.Pixel.html) format.
+pub type SRgba64 = SRgba<Ch64, Translucent<Ch64>>;
 
 type SRgbap<C, A> = Rgb<C, A, Premultiplied, gamma::Srgb>;
 /// [Rgb](struct.Rgb.html) 8-bit
@@ -368,10 +450,244 @@ pub type SRgba16p = SRgbap<Ch16, Translucent<Ch16>>;
 /// [premultiplied](alpha/struct.Premultiplied.html) alpha
 /// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
 pub type SRgba32p = SRgbap<Ch32, Translucent<Ch32>>;
+/// [Rgb](struct.Rgb.html) 16-bit floating-point
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SRgba16fp = SRgbap<Ch16f, Translucent<Ch16f>>;
+/// [Rgb](struct.Rgb.html) 64-bit floating-point
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type SRgba64p = SRgbap<Ch64, Translucent<Ch64>>;
+
+type Rec709Rgb<C, A> = Rgb<C, A, Straight, gamma::Rec709>;
+/// [Rgb](struct.Rgb.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [Rec. 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec709Rgb8 = Rec709Rgb<Ch8, Opaque<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [Rec. 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec709Rgb16 = Rec709Rgb<Ch16, Opaque<Ch16>>;
+
+type Rec709Rgba<C, A> = Rgb<C, A, Straight, gamma::Rec709>;
+/// [Rgb](struct.Rgb.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [Rec. 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec709Rgba8 = Rec709Rgba<Ch8, Translucent<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [Rec. 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec709Rgba16 = Rec709Rgba<Ch16, Translucent<Ch16>>;
+
+type Rec709Rgbap<C, A> = Rgb<C, A, Premultiplied, gamma::Rec709>;
+/// [Rgb](struct.Rgb.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [Rec. 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec709Rgba8p = Rec709Rgbap<Ch8, Translucent<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [Rec. 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec709Rgba16p = Rec709Rgbap<Ch16, Translucent<Ch16>>;
+
+type G18Rgb<C, A> = Rgb<C, A, Straight, gamma::Gamma18>;
+/// [Rgb](struct.Rgb.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [1.8 power law](gamma/type.Gamma18.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G18Rgb8 = G18Rgb<Ch8, Opaque<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [1.8 power law](gamma/type.Gamma18.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G18Rgb16 = G18Rgb<Ch16, Opaque<Ch16>>;
+
+type G18Rgba<C, A> = Rgb<C, A, Straight, gamma::Gamma18>;
+/// [Rgb](struct.Rgb.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [1.8 power law](gamma/type.Gamma18.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G18Rgba8 = G18Rgba<Ch8, Translucent<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [1.8 power law](gamma/type.Gamma18.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G18Rgba16 = G18Rgba<Ch16, Translucent<Ch16>>;
+
+type G18Rgbap<C, A> = Rgb<C, A, Premultiplied, gamma::Gamma18>;
+/// [Rgb](struct.Rgb.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [1.8 power law](gamma/type.Gamma18.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G18Rgba8p = G18Rgbap<Ch8, Translucent<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [1.8 power law](gamma/type.Gamma18.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G18Rgba16p = G18Rgbap<Ch16, Translucent<Ch16>>;
+
+type G22Rgb<C, A> = Rgb<C, A, Straight, gamma::Gamma22>;
+/// [Rgb](struct.Rgb.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [2.2 power law](gamma/type.Gamma22.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G22Rgb8 = G22Rgb<Ch8, Opaque<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [2.2 power law](gamma/type.Gamma22.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G22Rgb16 = G22Rgb<Ch16, Opaque<Ch16>>;
+
+type G22Rgba<C, A> = Rgb<C, A, Straight, gamma::Gamma22>;
+/// [Rgb](struct.Rgb.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [2.2 power law](gamma/type.Gamma22.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G22Rgba8 = G22Rgba<Ch8, Translucent<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [2.2 power law](gamma/type.Gamma22.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G22Rgba16 = G22Rgba<Ch16, Translucent<Ch16>>;
+
+type G22Rgbap<C, A> = Rgb<C, A, Premultiplied, gamma::Gamma22>;
+/// [Rgb](struct.Rgb.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [2.2 power law](gamma/type.Gamma22.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G22Rgba8p = G22Rgbap<Ch8, Translucent<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [2.2 power law](gamma/type.Gamma22.html) gamma [pixel](trait.Pixel.html)
+/// format.
+pub type G22Rgba16p = G22Rgbap<Ch16, Translucent<Ch16>>;
+
+type P3Rgb<C, A> = Rgb<C, A, Straight, gamma::Srgb, gamut::DisplayP3>;
+/// [Rgb](struct.Rgb.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [Display P3](gamut/struct.DisplayP3.html) primaries
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type P3Rgb8 = P3Rgb<Ch8, Opaque<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [Display P3](gamut/struct.DisplayP3.html) primaries
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type P3Rgb16 = P3Rgb<Ch16, Opaque<Ch16>>;
+
+type P3Rgba<C, A> = Rgb<C, A, Straight, gamma::Srgb, gamut::DisplayP3>;
+/// [Rgb](struct.Rgb.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [Display P3](gamut/struct.DisplayP3.html) primaries
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type P3Rgba8 = P3Rgba<Ch8, Translucent<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [Display P3](gamut/struct.DisplayP3.html) primaries
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type P3Rgba16 = P3Rgba<Ch16, Translucent<Ch16>>;
+
+type P3Rgbap<C, A> = Rgb<C, A, Premultiplied, gamma::Srgb, gamut::DisplayP3>;
+/// [Rgb](struct.Rgb.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [Display P3](gamut/struct.DisplayP3.html) primaries
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type P3Rgba8p = P3Rgbap<Ch8, Translucent<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [Display P3](gamut/struct.DisplayP3.html) primaries
+/// [sRGB](gamma/struct.Srgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type P3Rgba16p = P3Rgbap<Ch16, Translucent<Ch16>>;
+
+type Rec2020Rgb<C, A> = Rgb<C, A, Straight, gamma::Rec709, gamut::Rec2020>;
+/// [Rgb](struct.Rgb.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [Rec. 2020](gamut/struct.Rec2020.html) primaries [Rec.
+/// 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec2020Rgb8 = Rec2020Rgb<Ch8, Opaque<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [Rec. 2020](gamut/struct.Rec2020.html) primaries [Rec.
+/// 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec2020Rgb16 = Rec2020Rgb<Ch16, Opaque<Ch16>>;
+
+type Rec2020Rgba<C, A> = Rgb<C, A, Straight, gamma::Rec709, gamut::Rec2020>;
+/// [Rgb](struct.Rgb.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [Rec. 2020](gamut/struct.Rec2020.html) primaries [Rec.
+/// 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec2020Rgba8 = Rec2020Rgba<Ch8, Translucent<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [Rec. 2020](gamut/struct.Rec2020.html) primaries [Rec.
+/// 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec2020Rgba16 = Rec2020Rgba<Ch16, Translucent<Ch16>>;
+
+type Rec2020Rgbap<C, A> =
+    Rgb<C, A, Premultiplied, gamma::Rec709, gamut::Rec2020>;
+/// [Rgb](struct.Rgb.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [Rec. 2020](gamut/struct.Rec2020.html) primaries [Rec.
+/// 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec2020Rgba8p = Rec2020Rgbap<Ch8, Translucent<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [Rec. 2020](gamut/struct.Rec2020.html) primaries [Rec.
+/// 709](gamma/struct.Rec709.html) gamma [pixel](trait.Pixel.html) format.
+pub type Rec2020Rgba16p = Rec2020Rgbap<Ch16, Translucent<Ch16>>;
+
+type AdobeRgb<C, A> = Rgb<C, A, Straight, gamma::AdobeRgb, gamut::AdobeRgb>;
+/// [Rgb](struct.Rgb.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [Adobe RGB](gamut/struct.AdobeRgb.html) primaries [Adobe
+/// RGB](gamma/type.AdobeRgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type AdobeRgb8 = AdobeRgb<Ch8, Opaque<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [Adobe RGB](gamut/struct.AdobeRgb.html) primaries [Adobe
+/// RGB](gamma/type.AdobeRgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type AdobeRgb16 = AdobeRgb<Ch16, Opaque<Ch16>>;
+
+type AdobeRgba<C, A> = Rgb<C, A, Straight, gamma::AdobeRgb, gamut::AdobeRgb>;
+/// [Rgb](struct.Rgb.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [Adobe RGB](gamut/struct.AdobeRgb.html) primaries [Adobe
+/// RGB](gamma/type.AdobeRgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type AdobeRgba8 = AdobeRgba<Ch8, Translucent<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [Adobe RGB](gamut/struct.AdobeRgb.html) primaries [Adobe
+/// RGB](gamma/type.AdobeRgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type AdobeRgba16 = AdobeRgba<Ch16, Translucent<Ch16>>;
+
+type AdobeRgbap<C, A> =
+    Rgb<C, A, Premultiplied, gamma::AdobeRgb, gamut::AdobeRgb>;
+/// [Rgb](struct.Rgb.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [Adobe RGB](gamut/struct.AdobeRgb.html) primaries [Adobe
+/// RGB](gamma/type.AdobeRgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type AdobeRgba8p = AdobeRgbap<Ch8, Translucent<Ch8>>;
+/// [Rgb](struct.Rgb.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [Adobe RGB](gamut/struct.AdobeRgb.html) primaries [Adobe
+/// RGB](gamma/type.AdobeRgb.html) gamma [pixel](trait.Pixel.html) format.
+pub type AdobeRgba16p = AdobeRgbap<Ch16, Translucent<Ch16>>;
+
+type AcesCgRgb<C, A> = Rgb<C, A, Straight, Linear, gamut::AcesCg>;
+/// [Rgb](struct.Rgb.html) 16-bit floating-point
+/// [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [ACEScg](gamut/struct.AcesCg.html) primaries
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type AcesCgRgb16f = AcesCgRgb<Ch16f, Opaque<Ch16f>>;
+/// [Rgb](struct.Rgb.html) 32-bit floating-point
+/// [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [ACEScg](gamut/struct.AcesCg.html) primaries
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type AcesCgRgb32 = AcesCgRgb<Ch32, Opaque<Ch32>>;
+
+type AcesCgRgba<C, A> = Rgb<C, A, Straight, Linear, gamut::AcesCg>;
+/// [Rgb](struct.Rgb.html) 16-bit floating-point
+/// [straight](alpha/struct.Straight.html) alpha
+/// [ACEScg](gamut/struct.AcesCg.html) primaries
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type AcesCgRgba16f = AcesCgRgba<Ch16f, Translucent<Ch16f>>;
+/// [Rgb](struct.Rgb.html) 32-bit floating-point
+/// [straight](alpha/struct.Straight.html) alpha
+/// [ACEScg](gamut/struct.AcesCg.html) primaries
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type AcesCgRgba32 = AcesCgRgba<Ch32, Translucent<Ch32>>;
+
+type AcesCgRgbap<C, A> = Rgb<C, A, Premultiplied, Linear, gamut::AcesCg>;
+/// [Rgb](struct.Rgb.html) 16-bit floating-point
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [ACEScg](gamut/struct.AcesCg.html) primaries
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type AcesCgRgba16fp = AcesCgRgbap<Ch16f, Translucent<Ch16f>>;
+/// [Rgb](struct.Rgb.html) 32-bit floating-point
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [ACEScg](gamut/struct.AcesCg.html) primaries
+/// [linear](gamma/struct.Linear.html) gamma [pixel](trait.Pixel.html) format.
+pub type AcesCgRgba32p = AcesCgRgbap<Ch32, Translucent<Ch32>>;
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::gamut::Mapping;
+    use crate::Conversion;
 
     #[test]
     fn check_sizes() {
@@ -381,6 +697,46 @@ mod test {
         assert_eq!(std::mem::size_of::<SRgba8>(), 4);
         assert_eq!(std::mem::size_of::<SRgba16>(), 8);
         assert_eq!(std::mem::size_of::<SRgba32>(), 16);
+        assert_eq!(std::mem::size_of::<SRgb16f>(), 6);
+        assert_eq!(std::mem::size_of::<SRgba16f>(), 8);
+        assert_eq!(std::mem::size_of::<SRgb64>(), 24);
+        assert_eq!(std::mem::size_of::<SRgba64>(), 32);
+    }
+
+    #[test]
+    fn rgba16f() {
+        let p: Rgba16f = SRgba8::with_alpha(0xFF, 0x80, 0x00, 0x80).convert();
+        assert_eq!(p.red(), Ch16f::MAX);
+        assert_eq!(p.green().to_bits(), 0x32E8);
+        assert_eq!(p.alpha(), Ch16f::new(128.0 / 255.0));
+        let s: SRgba16f = p.convert();
+        assert_eq!(SRgba8::with_alpha(0xFF, 0x80, 0x00, 0x80), s.convert());
+    }
+
+    #[test]
+    fn rgba64() {
+        let p: Rgba64 = SRgba8::with_alpha(0xFF, 0x80, 0x00, 0x80).convert();
+        assert_eq!(p.red(), Ch64::MAX);
+        assert_eq!(p.alpha(), Ch64::new(128.0 / 255.0));
+        let s: SRgba64 = p.convert();
+        assert_eq!(SRgba8::with_alpha(0xFF, 0x80, 0x00, 0x80), s.convert());
+        let s: SRgb64 = SRgb16::new(1, 2, 3).convert();
+        assert_eq!(f64::from(s.red()), 1.0 / 65535.0);
+    }
+
+    #[test]
+    fn rgb32h() {
+        let p = Rgb32h::new(4.0, 0.5, -0.25);
+        assert_eq!(p.red(), Ch32h::new(4.0));
+        assert_eq!(p.blue(), Ch32h::new(-0.25));
+        let s: SRgb8 = p.convert();
+        assert_eq!(s, SRgb8::new(0xFF, 0xBC, 0x00));
+        let p: Rgba32hp = Rgba32h::with_alpha(2.0, 1.0, 0.0, 0.5).convert();
+        assert_eq!(p, Rgba32hp::with_alpha(1.0, 0.5, 0.0, 0.5));
+        let p: Rgba32h = p.convert();
+        assert_eq!(p.red(), Ch32h::new(2.0));
+        let p = Rgba32h::with_alpha(2.0, 1.0, 0.0, 3.0);
+        assert_eq!(p.alpha(), Ch32h::MAX);
     }
 
     #[test]
@@ -401,4 +757,44 @@ mod test {
         let b = SRgba8::with_alpha(0xFF, 0xFF, 0xFF, 0x10);
         assert_eq!(a * b, SRgba8::with_alpha(0xFF, 0x00, 0x80, 0x10));
     }
+
+    #[test]
+    fn rec709() {
+        let p: Rec709Rgb8 = SRgb8::new(0xFF, 0x80, 0x00).convert();
+        assert_eq!(p, Rec709Rgb8::new(0xFF, 0x73, 0x00));
+        let p: G22Rgb16 = Rgb16::new(0xFFFF, 0x8000, 0).convert();
+        assert_eq!(p, G22Rgb16::new(0xFFFF, 0xBAD0, 0));
+    }
+
+    #[test]
+    fn primaries() {
+        let p: SRgb16 = P3Rgb16::new(0x8000, 0x8000, 0x8000).convert();
+        assert_eq!(p, SRgb16::new(0x8000, 0x8000, 0x8000));
+        let p: AdobeRgb8 = SRgb8::new(0x00, 0xFF, 0x00).convert();
+        assert_eq!(p, AdobeRgb8::new(0x90, 0xFF, 0x3C));
+        let p: Rgb32 = AcesCgRgb32::new(1.0, 1.0, 1.0).convert();
+        for c in p.components() {
+            assert!((f32::from(*c) - 1.0).abs() < 1e-5);
+        }
+        let p = Rec2020Rgb16::new(0, 0xFFFF, 0);
+        let c: Rgb32h = p.convert();
+        assert_eq!(c.red(), Ch32h::new(0.0));
+        assert!(f32::from(c.green()) > 1.0);
+        let conv = Conversion::default().with_mapping(Mapping::Compress);
+        let c: Rgb32h = conv.convert(p);
+        assert!(c.components().iter().all(|v| f32::from(*v) >= 0.0));
+        assert!(f32::from(c.red()) + f32::from(c.blue()) > 0.0);
+    }
+
+    #[test]
+    fn primaries_shadows() {
+        let p: Vec<P3Rgb8> = [1, 4, 8, 12, 16]
+            .iter()
+            .map(|r| SRgb8::new(*r, 0, 0).convert())
+            .collect();
+        assert_eq!(p[0].red(), Ch8::new(1));
+        for (a, b) in p.iter().zip(p.iter().skip(1)) {
+            assert!(a.red() < b.red());
+        }
+    }
 }
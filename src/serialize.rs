@@ -0,0 +1,546 @@
+// serialize.rs     Serde impls.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+//! Channels are serialized as their primitive value, and pixels as a tuple
+//! of channels, with *alpha* last if translucent.
+//!
+//! A `Raster` is serialized as a struct with `width`, `height` and `data`,
+//! a blob of pixel bytes with each channel in little-endian byte order.
+use crate::alpha::{self, AChannel};
+use crate::format::Model;
+use crate::{
+    gamma, gamut, Ch16, Ch16f, Ch32, Ch32h, Ch64, Ch8, Channel, ColorModel,
+    Error, Gray, Mask, Palette, Pixel, Raster, RasterBuilder, Rgb,
+};
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::{size_of, size_of_val};
+
+/// Implement `Serialize` and `Deserialize` for a channel
+macro_rules! serde_channel {
+    ($ch:ty, $prim:ty) => {
+        impl Serialize for $ch {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                <$prim>::from(*self).serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ch {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                <$prim>::deserialize(deserializer).map(<$ch>::from)
+            }
+        }
+    };
+}
+
+serde_channel!(Ch8, u8);
+serde_channel!(Ch16, u16);
+serde_channel!(Ch16f, f32);
+serde_channel!(Ch32, f32);
+serde_channel!(Ch32h, f32);
+serde_channel!(Ch64, f64);
+
+/// Serialize pixel channels as a tuple
+fn serialize_channels<S, C>(
+    serializer: S,
+    channels: &[C],
+    alpha: Option<C>,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    C: Channel + Serialize,
+{
+    let len = channels.len() + usize::from(alpha.is_some());
+    let mut tup = serializer.serialize_tuple(len)?;
+    for c in channels.iter().chain(alpha.iter()) {
+        tup.serialize_element(c)?;
+    }
+    tup.end()
+}
+
+/// Visitor for a tuple of pixel channels
+struct ChannelsVisitor<C, const N: usize>(PhantomData<C>);
+
+impl<'de, C, const N: usize> Visitor<'de> for ChannelsVisitor<C, N>
+where
+    C: Channel + Deserialize<'de>,
+{
+    type Value = [C; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a tuple of {} channels", N)
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut channels = [C::MIN; N];
+        for (i, c) in channels.iter_mut().enumerate() {
+            *c = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        Ok(channels)
+    }
+}
+
+/// Deserialize a tuple of pixel channels
+fn deserialize_channels<'de, D, C, const N: usize>(
+    deserializer: D,
+) -> Result<[C; N], D::Error>
+where
+    D: Deserializer<'de>,
+    C: Channel + Deserialize<'de>,
+{
+    deserializer.deserialize_tuple(N, ChannelsVisitor(PhantomData))
+}
+
+impl<C, A, M, G, P> Serialize for Rgb<C, A, M, G, P>
+where
+    C: Channel + Serialize,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+    P: gamut::Primaries,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let alpha = if A::TRANSLUCENT {
+            Some(ColorModel::alpha(*self))
+        } else {
+            None
+        };
+        serialize_channels(serializer, self.components(), alpha)
+    }
+}
+
+impl<'de, C, A, M, G, P> Deserialize<'de> for Rgb<C, A, M, G, P>
+where
+    C: Channel + Deserialize<'de>,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+    P: gamut::Primaries,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let [r, g, b, a] = if A::TRANSLUCENT {
+            deserialize_channels(deserializer)?
+        } else {
+            let [r, g, b] = deserialize_channels(deserializer)?;
+            [r, g, b, C::MAX]
+        };
+        Ok(Rgb::with_alpha(r, g, b, a))
+    }
+}
+
+impl<C, A, M, G> Serialize for Gray<C, A, M, G>
+where
+    C: Channel + Serialize,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let alpha = if A::TRANSLUCENT {
+            Some(Gray::alpha(*self))
+        } else {
+            None
+        };
+        serialize_channels(serializer, self.components(), alpha)
+    }
+}
+
+impl<'de, C, A, M, G> Deserialize<'de> for Gray<C, A, M, G>
+where
+    C: Channel + Deserialize<'de>,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    G: gamma::Mode,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let [v, a] = if A::TRANSLUCENT {
+            deserialize_channels(deserializer)?
+        } else {
+            let [v] = deserialize_channels(deserializer)?;
+            [v, C::MAX]
+        };
+        Ok(Gray::with_alpha(v, a))
+    }
+}
+
+impl<C: Channel + Serialize> Serialize for Mask<C> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_channels(serializer, &[], Some(self.alpha()))
+    }
+}
+
+impl<'de, C: Channel + Deserialize<'de>> Deserialize<'de> for Mask<C> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let [a]: [C; 1] = deserialize_channels(deserializer)?;
+        Ok(Mask::new(a))
+    }
+}
+
+/// Serialized `Palette` (borrowed)
+#[derive(serde::Serialize)]
+#[serde(rename = "Palette")]
+struct PaletteRef<'a, P> {
+    capacity: usize,
+    entries: &'a [P],
+}
+
+/// Serialized `Palette`
+#[derive(serde::Deserialize)]
+#[serde(rename = "Palette")]
+struct PaletteData<P> {
+    capacity: usize,
+    entries: Vec<P>,
+}
+
+impl<P> Serialize for Palette<P>
+where
+    P: Pixel<Chan = Ch8> + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        PaletteRef {
+            capacity: self.table.capacity(),
+            entries: &self.table,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, P> Deserialize<'de> for Palette<P>
+where
+    P: Pixel<Chan = Ch8> + Deserialize<'de>,
+{
+    /// Deserialize a `Palette`.
+    ///
+    /// The threshold function is reset to the default.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = PaletteData::<P>::deserialize(deserializer)?;
+        if data.entries.len() > data.capacity {
+            return Err(de::Error::invalid_length(
+                data.entries.len(),
+                &"no more entries than capacity",
+            ));
+        }
+        let mut palette = Palette::new(0);
+        palette
+            .table
+            .try_reserve_exact(data.capacity)
+            .map_err(de::Error::custom)?;
+        palette.table.extend(data.entries);
+        Ok(palette)
+    }
+}
+
+/// Pixel bytes (borrowed if already little-endian)
+struct Bytes<'a>(Cow<'a, [u8]>);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+/// Pixel bytes
+struct ByteBuf(Vec<u8>);
+
+/// Visitor for pixel bytes
+struct ByteBufVisitor;
+
+impl<'de> Visitor<'de> for ByteBufVisitor {
+    type Value = ByteBuf;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "pixel bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<ByteBuf, E> {
+        Ok(ByteBuf(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+        Ok(ByteBuf(v))
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<ByteBuf, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut v = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(b) = seq.next_element()? {
+            v.push(b);
+        }
+        Ok(ByteBuf(v))
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_byte_buf(ByteBufVisitor)
+    }
+}
+
+/// Serialized `Raster` (borrowed)
+#[derive(serde::Serialize)]
+#[serde(rename = "Raster")]
+struct RasterRef<'a> {
+    width: u32,
+    height: u32,
+    data: Bytes<'a>,
+}
+
+/// Serialized `Raster`
+#[derive(serde::Deserialize)]
+#[serde(rename = "Raster")]
+struct RasterData {
+    width: u32,
+    height: u32,
+    data: ByteBuf,
+}
+
+impl<P: Pixel> Serialize for Raster<P> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let size = word_size::<P>();
+        let data = if cfg!(target_endian = "big") && size > 1 {
+            let mut data = self.as_u8_slice().to_vec();
+            swap_little_endian(&mut data, size);
+            Cow::Owned(data)
+        } else {
+            Cow::Borrowed(self.as_u8_slice())
+        };
+        RasterRef {
+            width: self.width(),
+            height: self.height(),
+            data: Bytes(data),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, P: Pixel> Deserialize<'de> for Raster<P> {
+    /// Deserialize a `Raster`.
+    ///
+    /// The length of `data` is checked before allocating the `Raster`.
+    /// Floating-point channels which are `NaN` or out of range are clamped,
+    /// as by the `Channel` constructors.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let RasterData {
+            width,
+            height,
+            data,
+        } = RasterData::deserialize(deserializer)?;
+        let expected = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|n| n.checked_mul(size_of::<P>()))
+            .ok_or_else(|| de::Error::custom(Error::SizeOverflow))?;
+        if data.0.len() != expected {
+            return Err(de::Error::custom(Error::BufferLength {
+                expected,
+                actual: data.0.len(),
+            }));
+        }
+        let mut raster = RasterBuilder::new()
+            .try_with_clear(width, height)
+            .map_err(de::Error::custom)?;
+        raster.as_u8_slice_mut().copy_from_slice(&data.0);
+        swap_little_endian(raster.as_u8_slice_mut(), word_size::<P>());
+        clamp_channels(raster.as_slice_mut());
+        Ok(raster)
+    }
+}
+
+/// Get the size of each little-endian word of a pixel, in bytes
+///
+/// `PackedRgb` words are always stored in little-endian byte order.
+fn word_size<P: Pixel>() -> usize {
+    if P::MODEL == Model::PackedRgb {
+        1
+    } else {
+        size_of::<P::Chan>()
+    }
+}
+
+/// Swap bytes between native and little-endian byte order
+///
+/// * `bytes` Pixel bytes.
+/// * `size` Size of each word, in bytes.
+fn swap_little_endian(bytes: &mut [u8], size: usize) {
+    if cfg!(target_endian = "big") && size > 1 {
+        bytes.chunks_exact_mut(size).for_each(|w| w.reverse());
+    }
+}
+
+/// Clamp floating-point channels of pixels
+fn clamp_channels<P: Pixel>(pixels: &mut [P]) {
+    let id = TypeId::of::<P::Chan>();
+    if id == TypeId::of::<Ch16f>() {
+        clamp_each(pixels, |c: Ch16f| Ch16f::from_bits(c.to_bits()));
+    } else if id == TypeId::of::<Ch32>() {
+        clamp_each(pixels, |c: Ch32| Ch32::new(f32::from(c)));
+    } else if id == TypeId::of::<Ch32h>() {
+        clamp_each(pixels, |c: Ch32h| Ch32h::new(f32::from(c)));
+    } else if id == TypeId::of::<Ch64>() {
+        clamp_each(pixels, |c: Ch64| Ch64::new(f64::from(c)));
+    }
+}
+
+/// Clamp each channel of pixels
+///
+/// Pixels with floating-point channels are arrays of one channel type.
+fn clamp_each<P, C, F>(pixels: &mut [P], f: F)
+where
+    P: Pixel,
+    C: Channel,
+    F: Fn(C) -> C,
+{
+    debug_assert_eq!(TypeId::of::<P::Chan>(), TypeId::of::<C>());
+    debug_assert_eq!(size_of::<P>() % size_of::<C>(), 0);
+    let len = size_of_val(pixels) / size_of::<C>();
+    let channels = unsafe {
+        std::slice::from_raw_parts_mut(pixels.as_mut_ptr() as *mut C, len)
+    };
+    for c in channels {
+        *c = f(*c);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn channels() {
+        assert_eq!(serde_json::to_string(&Ch8::new(0x80)).unwrap(), "128");
+        assert_eq!(serde_json::to_string(&Ch16::new(0x100)).unwrap(), "256");
+        assert_eq!(serde_json::to_string(&Ch32::new(0.5)).unwrap(), "0.5");
+        let c: Ch32 = serde_json::from_str("2.5").unwrap();
+        assert_eq!(c, Ch32::MAX);
+        let c: Ch16f = serde_json::from_str("0.25").unwrap();
+        assert_eq!(c, Ch16f::new(0.25));
+    }
+
+    #[test]
+    fn pixels() {
+        let p = SRgb8::new(1, 2, 3);
+        assert_eq!(serde_json::to_string(&p).unwrap(), "[1,2,3]");
+        let p = Rgba16p::with_alpha(1, 2, 3, 4);
+        let s = serde_json::to_string(&p).unwrap();
+        assert_eq!(s, "[1,2,3,4]");
+        assert_eq!(serde_json::from_str::<Rgba16p>(&s).unwrap(), p);
+        let p = SGrayAlpha32::with_alpha(0.25, 0.5);
+        let s = serde_json::to_string(&p).unwrap();
+        assert_eq!(s, "[0.25,0.5]");
+        assert_eq!(serde_json::from_str::<SGrayAlpha32>(&s).unwrap(), p);
+        let p: Gray8 = serde_json::from_str("[7]").unwrap();
+        assert_eq!(p, Gray8::new(7));
+        let p: Mask8 = serde_json::from_str("[255]").unwrap();
+        assert_eq!(p, Mask8::new(255));
+        assert!(serde_json::from_str::<SRgb8>("[1,2]").is_err());
+        assert!(serde_json::from_str::<SRgba8>("[1,2,3]").is_err());
+    }
+
+    #[test]
+    fn palette() {
+        let mut p = Palette::new(4);
+        p.set_entry(SRgb8::new(1, 2, 3));
+        p.set_entry(SRgb8::new(4, 5, 6));
+        let s = serde_json::to_string(&p).unwrap();
+        assert_eq!(s, r#"{"capacity":4,"entries":[[1,2,3],[4,5,6]]}"#);
+        let p: Palette<SRgb8> = serde_json::from_str(&s).unwrap();
+        assert_eq!(p.len(), 2);
+        assert_eq!(p.entry(1), Some(SRgb8::new(4, 5, 6)));
+        let s = r#"{"capacity":1,"entries":[[1,2,3],[4,5,6]]}"#;
+        assert!(serde_json::from_str::<Palette<SRgb8>>(s).is_err());
+    }
+
+    #[test]
+    fn region() {
+        let r = Region::new(-1, 2, 3, 4);
+        let s = serde_json::to_string(&r).unwrap();
+        assert_eq!(s, r#"{"x":-1,"y":2,"width":3,"height":4}"#);
+        assert_eq!(serde_json::from_str::<Region>(&s).unwrap(), r);
+    }
+
+    #[test]
+    fn raster() {
+        let mut r = RasterBuilder::<SRgb8>::new().with_clear(2, 1);
+        r.set_pixel(1, 0, SRgb8::new(4, 5, 6));
+        let s = serde_json::to_string(&r).unwrap();
+        assert_eq!(s, r#"{"width":2,"height":1,"data":[0,0,0,4,5,6]}"#);
+        let r2: Raster<SRgb8> = serde_json::from_str(&s).unwrap();
+        assert_eq!(r2.as_slice(), r.as_slice());
+        let s = r#"{"width":2,"height":2,"data":[0,0,0,4,5,6]}"#;
+        assert!(serde_json::from_str::<Raster<SRgb8>>(s).is_err());
+        let s = r#"{"width":65536,"height":65536,"data":[]}"#;
+        assert!(serde_json::from_str::<Raster<SRgb8>>(s).is_err());
+        let mut r = RasterBuilder::<Gray32>::new().with_clear(3, 1);
+        r.as_u8_slice_mut()[..8].copy_from_slice(&[0xFF; 8]);
+        r.as_u8_slice_mut()[8..].copy_from_slice(&2.0_f32.to_ne_bytes());
+        let s = serde_json::to_string(&r).unwrap();
+        let r: Raster<Gray32> = serde_json::from_str(&s).unwrap();
+        assert_eq!(r.pixel(0, 0), Gray32::new(0.0));
+        assert_eq!(r.pixel(2, 0), Gray32::new(1.0));
+    }
+
+    #[test]
+    fn raster_little_endian() {
+        let mut r = RasterBuilder::<Gray16>::new().with_clear(2, 1);
+        r.set_pixel(0, 0, Gray16::new(0x0102));
+        r.set_pixel(1, 0, Gray16::new(0xA0B0));
+        let s = serde_json::to_string(&r).unwrap();
+        assert_eq!(s, r#"{"width":2,"height":1,"data":[2,1,176,160]}"#);
+        let r: Raster<Gray16> = serde_json::from_str(&s).unwrap();
+        assert_eq!(r.pixel(0, 0), Gray16::new(0x0102));
+        assert_eq!(r.pixel(1, 0), Gray16::new(0xA0B0));
+    }
+}
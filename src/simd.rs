@@ -0,0 +1,1709 @@
+// simd.rs      Slice conversion with SIMD kernels.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{Premultiplied, Straight};
+use crate::format::Model;
+use crate::gamma::{Linear, Srgb, SrgbValue};
+use crate::{alpha, gamma, Ch16, Ch32, Ch8, Channel, Pixel};
+use std::any::TypeId;
+use std::mem::size_of;
+use std::sync::OnceLock;
+
+/// Number of pixels in each block of a slice conversion
+const BLOCK: usize = 256;
+
+/// Call a kernel for the best instruction set detected at runtime.
+///
+/// Evaluates to the number of leading channels converted, which is zero if
+/// no instruction set is supported.
+macro_rules! dispatch {
+    ($kernel:ident($src:expr, $dst:expr)) => {{
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        let done = if is_x86_feature_detected!("avx2") {
+            unsafe { avx2::$kernel($src, $dst) }
+        } else if is_x86_feature_detected!("sse2") {
+            unsafe { sse2::$kernel($src, $dst) }
+        } else {
+            0
+        };
+        #[cfg(target_arch = "aarch64")]
+        let done = if std::arch::is_aarch64_feature_detected!("neon") {
+            unsafe { neon::$kernel($src, $dst) }
+        } else {
+            0
+        };
+        #[cfg(not(any(
+            target_arch = "x86",
+            target_arch = "x86_64",
+            target_arch = "aarch64"
+        )))]
+        let done = 0;
+        done
+    }};
+}
+
+/// Define kernels which finish any remaining channels with scalar code.
+macro_rules! kernels {
+    ($(
+        $(#[$doc:meta])*
+        $kernel:ident($s:ident: $st:ty, $d:ident: $dt:ty) => $scalar:expr;
+    )*) => {
+        $(
+            $(#[$doc])*
+            fn $kernel(src: &[$st], dst: &mut [$dt]) {
+                let i = dispatch!($kernel(src, dst));
+                for ($s, $d) in src[i..].iter().zip(dst[i..].iter_mut()) {
+                    *$d = $scalar;
+                }
+            }
+        )*
+    };
+}
+
+kernels! {
+    /// Widen 8-bit channels to 16 bits
+    widen_8_16(s: Ch8, d: Ch16) => Ch16::from(*s);
+    /// Narrow 16-bit channels to 8 bits
+    narrow_16_8(s: Ch16, d: Ch8) => Ch8::from(*s);
+    /// Convert 8-bit channels to floating-point
+    float_8(s: Ch8, d: Ch32) => Ch32::from(*s);
+    /// Convert 16-bit channels to floating-point
+    float_16(s: Ch16, d: Ch32) => Ch32::from(*s);
+    /// Convert floating-point channels to 8 bits
+    fixed_8(s: Ch32, d: Ch8) => Ch8::from(*s);
+    /// Convert floating-point channels to 16 bits
+    fixed_16(s: Ch32, d: Ch16) => Ch16::from(*s);
+    /// Multiply 8-bit channels (`dst`) by alpha (`src`)
+    mul_8(s: Ch8, d: Ch8) => *d * *s;
+    /// Divide 8-bit channels (`dst`) by alpha (`src`)
+    div_8(s: Ch8, d: Ch8) => *d / *s;
+    /// Multiply 16-bit channels (`dst`) by alpha (`src`)
+    mul_16(s: Ch16, d: Ch16) => *d * *s;
+    /// Divide 16-bit channels (`dst`) by alpha (`src`)
+    div_16(s: Ch16, d: Ch16) => *d / *s;
+    /// Multiply floating-point channels (`dst`) by alpha (`src`)
+    mul_32(s: Ch32, d: Ch32) => *d * *s;
+    /// Divide floating-point channels (`dst`) by alpha (`src`)
+    div_32(s: Ch32, d: Ch32) => *d / *s;
+    /// Decode sRGB floating-point channels to linear
+    decode_srgb_32(s: Ch32, d: Ch32) => s.decode_srgb();
+    /// Encode linear floating-point channels to sRGB
+    encode_srgb_32(s: Ch32, d: Ch32) => s.encode_srgb();
+}
+
+/// Check if two types are the same
+fn is<A: 'static, B: 'static>() -> bool {
+    TypeId::of::<A>() == TypeId::of::<B>()
+}
+
+/// Cast a slice to another type with the same representation.
+///
+/// # Safety
+///
+/// `A` and `B` must be the same type.
+unsafe fn cast<A, B>(s: &[A]) -> &[B] {
+    std::slice::from_raw_parts(s.as_ptr() as *const B, s.len())
+}
+
+/// Cast a mutable slice to another type with the same representation.
+///
+/// # Safety
+///
+/// `A` and `B` must be the same type.
+unsafe fn cast_mut<A, B>(s: &mut [A]) -> &mut [B] {
+    std::slice::from_raw_parts_mut(s.as_mut_ptr() as *mut B, s.len())
+}
+
+/// Get the channel layout of a `Pixel` format.
+///
+/// Returns the number of channels in each pixel and the index of the
+/// *alpha* channel, or `None` unless each channel converts independently.
+fn layout<P: Pixel>() -> Option<(usize, Option<usize>)> {
+    let n = P::default().components().len() + usize::from(P::HAS_ALPHA);
+    if n > 4 || n * size_of::<P::Chan>() != size_of::<P>() {
+        return None;
+    }
+    let alpha = match P::MODEL {
+        Model::Bgr | Model::Gray | Model::Rgb => n - 1,
+        Model::Argb | Model::Mask => 0,
+        _ => return None,
+    };
+    Some((n, if P::HAS_ALPHA { Some(alpha) } else { None }))
+}
+
+/// Convert a slice of pixels, one block of channels at a time.
+///
+/// Returns `false` if the formats have different color models, channel
+/// orders or primaries, which must be converted one pixel at a time.
+pub(crate) fn convert_slice<S, D>(src: &[S], dst: &mut [D]) -> bool
+where
+    S: Pixel,
+    D: Pixel,
+    D::Chan: From<S::Chan>,
+{
+    debug_assert_eq!(src.len(), dst.len());
+    let (n, alpha) = match (layout::<S>(), layout::<D>()) {
+        (Some(s), Some(d)) if s == d && S::MODEL == D::MODEL => s,
+        _ => return false,
+    };
+    if !is::<S::Primaries, D::Primaries>() {
+        return false;
+    }
+    // Safety: both formats are arrays of `n` channels
+    let src = unsafe {
+        let ptr = src.as_ptr() as *const S::Chan;
+        std::slice::from_raw_parts(ptr, src.len() * n)
+    };
+    let dst = unsafe {
+        let ptr = dst.as_mut_ptr() as *mut D::Chan;
+        std::slice::from_raw_parts_mut(ptr, dst.len() * n)
+    };
+    let alpha_mode = !is::<S::Alpha, D::Alpha>();
+    let gamma_mode = !is::<S::Gamma, D::Gamma>();
+    let opaque = D::Chan::from(S::Chan::MAX);
+    for (s, d) in src.chunks(BLOCK * n).zip(dst.chunks_mut(BLOCK * n)) {
+        convert_depth(s, d);
+        if alpha_mode || gamma_mode {
+            to_linear::<S::Gamma, _>(d, n, alpha);
+            if alpha_mode {
+                convert_alpha::<S::Alpha, D::Alpha, _>(d, n, alpha, opaque);
+            }
+            from_linear::<D::Gamma, _>(d, n, alpha);
+        }
+    }
+    true
+}
+
+/// Convert channels to another bit depth
+fn convert_depth<S, D>(src: &[S], dst: &mut [D])
+where
+    S: Channel,
+    D: Channel + From<S>,
+{
+    // Safety: channel types are checked before casting
+    unsafe {
+        if is::<S, Ch8>() && is::<D, Ch16>() {
+            return widen_8_16(cast(src), cast_mut(dst));
+        }
+        if is::<S, Ch16>() && is::<D, Ch8>() {
+            return narrow_16_8(cast(src), cast_mut(dst));
+        }
+        if is::<S, Ch8>() && is::<D, Ch32>() {
+            return float_8(cast(src), cast_mut(dst));
+        }
+        if is::<S, Ch16>() && is::<D, Ch32>() {
+            return float_16(cast(src), cast_mut(dst));
+        }
+        if is::<S, Ch32>() && is::<D, Ch8>() {
+            return fixed_8(cast(src), cast_mut(dst));
+        }
+        if is::<S, Ch32>() && is::<D, Ch16>() {
+            return fixed_16(cast(src), cast_mut(dst));
+        }
+    }
+    for (s, d) in src.iter().zip(dst.iter_mut()) {
+        *d = D::from(*s);
+    }
+}
+
+/// Call a function with each color (non-alpha) channel
+fn for_colors<C, F>(chans: &mut [C], n: usize, alpha: Option<usize>, mut f: F)
+where
+    F: FnMut(&mut C),
+{
+    for px in chans.chunks_exact_mut(n) {
+        for (i, c) in px.iter_mut().enumerate() {
+            if Some(i) != alpha {
+                f(c);
+            }
+        }
+    }
+}
+
+/// Get sRGB decode and encode tables for 16-bit channels
+fn srgb_tables_16() -> &'static [Box<[u16]>; 2] {
+    static TABLES: OnceLock<[Box<[u16]>; 2]> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        [
+            (0..=u16::MAX).map(|v| v.decode_srgb()).collect(),
+            (0..=u16::MAX).map(|v| v.encode_srgb()).collect(),
+        ]
+    })
+}
+
+/// Look up 16-bit channels in a table
+fn look_up_16(chans: &mut [Ch16], n: usize, a: Option<usize>, table: &[u16]) {
+    for_colors(chans, n, a, |c| {
+        *c = Ch16::new(table[usize::from(u16::from(*c))]);
+    });
+}
+
+/// Convert sRGB floating-point color channels with a kernel
+fn srgb_32<F>(chans: &mut [Ch32], n: usize, alpha: Option<usize>, kernel: F)
+where
+    F: Fn(&[Ch32], &mut [Ch32]),
+{
+    let mut buf = [Ch32::MIN; BLOCK * 4];
+    let src = &mut buf[..chans.len()];
+    src.copy_from_slice(chans);
+    kernel(src, chans);
+    // Kernels also changed the alpha channels; put them back
+    if let Some(a) = alpha {
+        for (px, s) in chans.chunks_exact_mut(n).zip(src.chunks_exact(n)) {
+            px[a] = s[a];
+        }
+    }
+}
+
+/// Convert color channels to linear gamma
+fn to_linear<G, C>(chans: &mut [C], n: usize, alpha: Option<usize>)
+where
+    G: gamma::Mode,
+    C: Channel,
+{
+    if is::<G, Linear>() {
+        return;
+    }
+    if is::<G, Srgb>() && is::<C, Ch16>() {
+        // Safety: channel type was checked
+        let chans = unsafe { cast_mut(chans) };
+        return look_up_16(chans, n, alpha, &srgb_tables_16()[0]);
+    }
+    if is::<G, Srgb>() && is::<C, Ch32>() {
+        // Safety: channel type was checked
+        let chans = unsafe { cast_mut(chans) };
+        return srgb_32(chans, n, alpha, decode_srgb_32);
+    }
+    for_colors(chans, n, alpha, |c| *c = G::to_linear(*c));
+}
+
+/// Convert color channels from linear gamma
+fn from_linear<G, C>(chans: &mut [C], n: usize, alpha: Option<usize>)
+where
+    G: gamma::Mode,
+    C: Channel,
+{
+    if is::<G, Linear>() {
+        return;
+    }
+    if is::<G, Srgb>() && is::<C, Ch16>() {
+        // Safety: channel type was checked
+        let chans = unsafe { cast_mut(chans) };
+        return look_up_16(chans, n, alpha, &srgb_tables_16()[1]);
+    }
+    if is::<G, Srgb>() && is::<C, Ch32>() {
+        // Safety: channel type was checked
+        let chans = unsafe { cast_mut(chans) };
+        return srgb_32(chans, n, alpha, encode_srgb_32);
+    }
+    for_colors(chans, n, alpha, |c| *c = G::from_linear(*c));
+}
+
+/// Convert color channels to another alpha mode.
+///
+/// * `opaque` Alpha value of formats without an *alpha* channel.
+fn convert_alpha<S, D, C>(
+    chans: &mut [C],
+    n: usize,
+    alpha: Option<usize>,
+    opaque: C,
+) where
+    S: alpha::Mode,
+    D: alpha::Mode,
+    C: Channel,
+{
+    // Spread alpha over each channel, so that kernels work on flat slices
+    let mut buf = [opaque; BLOCK * 4];
+    let alphas = &mut buf[..chans.len()];
+    if let Some(a) = alpha {
+        for (px, al) in chans.chunks_exact(n).zip(alphas.chunks_exact_mut(n)) {
+            al.iter_mut().for_each(|v| *v = px[a]);
+        }
+    }
+    let premultiply = is::<S, Straight>() && is::<D, Premultiplied>();
+    let straighten = is::<S, Premultiplied>() && is::<D, Straight>();
+    // Safety: channel types are checked before casting
+    unsafe {
+        if premultiply && is::<C, Ch8>() {
+            mul_8(cast(alphas), cast_mut(chans));
+        } else if premultiply && is::<C, Ch16>() {
+            mul_16(cast(alphas), cast_mut(chans));
+        } else if premultiply && is::<C, Ch32>() {
+            mul_32(cast(alphas), cast_mut(chans));
+        } else if straighten && is::<C, Ch8>() {
+            div_8(cast(alphas), cast_mut(chans));
+        } else if straighten && is::<C, Ch16>() {
+            div_16(cast(alphas), cast_mut(chans));
+        } else if straighten && is::<C, Ch32>() {
+            div_32(cast(alphas), cast_mut(chans));
+        } else {
+            for (c, a) in chans.iter_mut().zip(alphas.iter()) {
+                *c = D::encode(S::decode(*c, *a), *a);
+            }
+        }
+    }
+    // Kernels also changed the alpha channels; put them back
+    if let Some(a) = alpha {
+        for (px, al) in chans.chunks_exact_mut(n).zip(alphas.chunks_exact(n)) {
+            px[a] = al[a];
+        }
+    }
+}
+
+/// SSE2 kernels, converting 16 channels at a time
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod sse2 {
+    use crate::gamma::{EXP2_COEF, LOG2_COEF, SQRT_HALF_BITS};
+    use crate::{Ch16, Ch32, Ch8};
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    /// Scale and round lanes to integers, with halves away from zero
+    #[target_feature(enable = "sse2")]
+    unsafe fn round_scaled(v: __m128, max: f32) -> __m128i {
+        let x = _mm_mul_ps(v, _mm_set1_ps(max));
+        // NaN becomes zero, as with `as` casts
+        let x = _mm_max_ps(x, _mm_setzero_ps());
+        let x = _mm_min_ps(x, _mm_set1_ps(max));
+        let t = _mm_cvttps_epi32(x);
+        // Subtracting the truncated value is exact
+        let f = _mm_sub_ps(x, _mm_cvtepi32_ps(t));
+        let up = _mm_castps_si128(_mm_cmpge_ps(f, _mm_set1_ps(0.5)));
+        _mm_sub_epi32(t, up)
+    }
+
+    /// Pack 32-bit lanes (no greater than 0xFFFF) to 16 bits
+    #[target_feature(enable = "sse2")]
+    unsafe fn pack_u32(a: __m128i, b: __m128i) -> __m128i {
+        let bias = _mm_set1_epi32(0x8000);
+        let a = _mm_sub_epi32(a, bias);
+        let b = _mm_sub_epi32(b, bias);
+        _mm_xor_si128(_mm_packs_epi32(a, b), _mm_set1_epi16(i16::MIN))
+    }
+
+    /// Multiply 8-bit values in 16-bit lanes, as `Ch8` does
+    #[target_feature(enable = "sse2")]
+    unsafe fn mul_lanes_8(l: __m128i, r: __m128i) -> __m128i {
+        let l = _mm_or_si128(_mm_slli_epi16(l, 4), _mm_srli_epi16(l, 4));
+        let r = _mm_or_si128(_mm_slli_epi16(r, 4), _mm_srli_epi16(r, 4));
+        _mm_mulhi_epu16(l, r)
+    }
+
+    /// Divide 8-bit values in 32-bit lanes, as `Ch8` does
+    #[target_feature(enable = "sse2")]
+    unsafe fn div_lanes_8(c: __m128i, a: __m128i) -> __m128i {
+        let n = _mm_cvtepi32_ps(_mm_slli_epi32(c, 8));
+        let q = _mm_div_ps(n, _mm_cvtepi32_ps(a));
+        let q = _mm_cvttps_epi32(_mm_min_ps(q, _mm_set1_ps(255.0)));
+        _mm_andnot_si128(_mm_cmpeq_epi32(a, _mm_setzero_si128()), q)
+    }
+
+    /// Multiply 16-bit values in 32-bit lanes, as `Ch16` does
+    #[target_feature(enable = "sse2")]
+    unsafe fn mul_lanes_16(l: __m128i, r: __m128i) -> __m128i {
+        let l = _mm_or_si128(_mm_slli_epi32(l, 8), _mm_srli_epi32(l, 8));
+        let r = _mm_or_si128(_mm_slli_epi32(r, 8), _mm_srli_epi32(r, 8));
+        let even = _mm_srli_epi64(_mm_mul_epu32(l, r), 32);
+        let l = _mm_srli_epi64(l, 32);
+        let r = _mm_srli_epi64(r, 32);
+        let odd = _mm_mul_epu32(l, r);
+        let mask = _mm_set_epi32(-1, 0, -1, 0);
+        _mm_or_si128(even, _mm_and_si128(odd, mask))
+    }
+
+    /// Divide two 16-bit values in 32-bit lanes, as `Ch16` does
+    #[target_feature(enable = "sse2")]
+    unsafe fn div_pair_16(c: __m128i, a: __m128i) -> __m128i {
+        let n = _mm_mul_pd(_mm_cvtepi32_pd(c), _mm_set1_pd(65536.0));
+        let q = _mm_div_pd(n, _mm_cvtepi32_pd(a));
+        _mm_cvttpd_epi32(_mm_min_pd(q, _mm_set1_pd(65535.0)))
+    }
+
+    /// Divide 16-bit values in 32-bit lanes, as `Ch16` does
+    #[target_feature(enable = "sse2")]
+    unsafe fn div_lanes_16(c: __m128i, a: __m128i) -> __m128i {
+        let lo = div_pair_16(c, a);
+        let hi = div_pair_16(_mm_srli_si128(c, 8), _mm_srli_si128(a, 8));
+        let q = _mm_unpacklo_epi64(lo, hi);
+        _mm_andnot_si128(_mm_cmpeq_epi32(a, _mm_setzero_si128()), q)
+    }
+
+    /// Select lanes from `a` where `m` is set, otherwise from `b`
+    #[target_feature(enable = "sse2")]
+    unsafe fn select(m: __m128, a: __m128, b: __m128) -> __m128 {
+        _mm_or_ps(_mm_and_ps(m, a), _mm_andnot_ps(m, b))
+    }
+
+    /// Evaluate a polynomial with Horner's method, as `gamma` does
+    #[target_feature(enable = "sse2")]
+    unsafe fn horner(x: __m128d, coef: &[f64]) -> __m128d {
+        let (last, rest) = coef.split_last().expect("no coefficients");
+        let mut acc = _mm_set1_pd(*last);
+        for c in rest.iter().rev() {
+            acc = _mm_add_pd(_mm_mul_pd(acc, x), _mm_set1_pd(*c));
+        }
+        acc
+    }
+
+    /// Raise two mantissas, scaled by 2^`e`, to a power
+    #[target_feature(enable = "sse2")]
+    unsafe fn pow_pair(m: __m128d, e: __m128d, g: f64) -> __m128 {
+        let one = _mm_set1_pd(1.0);
+        let t = _mm_div_pd(_mm_sub_pd(m, one), _mm_add_pd(m, one));
+        let l = _mm_mul_pd(t, horner(_mm_mul_pd(t, t), &LOG2_COEF));
+        let y = _mm_mul_pd(_mm_add_pd(l, e), _mm_set1_pd(g));
+        let n = _mm_cvttpd_epi32(_mm_sub_pd(y, _mm_set1_pd(0.5)));
+        let f = _mm_sub_pd(y, _mm_cvtepi32_pd(n));
+        let p = _mm_castps_si128(_mm_cvtpd_ps(horner(f, &EXP2_COEF)));
+        _mm_castsi128_ps(_mm_add_epi32(p, _mm_slli_epi32(n, 23)))
+    }
+
+    /// Raise lanes to a power, as `srgb_powf` does
+    #[target_feature(enable = "sse2")]
+    unsafe fn pow_lanes(v: __m128, g: f64) -> __m128 {
+        let bits = _mm_castps_si128(v);
+        let e = _mm_sub_epi32(bits, _mm_set1_epi32(SQRT_HALF_BITS));
+        let e = _mm_srai_epi32(e, 23);
+        let m = _mm_castsi128_ps(_mm_sub_epi32(bits, _mm_slli_epi32(e, 23)));
+        let lo = pow_pair(_mm_cvtps_pd(m), _mm_cvtepi32_pd(e), g);
+        let m = _mm_movehl_ps(m, m);
+        let e = _mm_srli_si128(e, 8);
+        let hi = pow_pair(_mm_cvtps_pd(m), _mm_cvtepi32_pd(e), g);
+        _mm_movelh_ps(lo, hi)
+    }
+
+    /// Finish sRGB lanes, as `SrgbValue` for `Ch32` does.
+    ///
+    /// * `x` Input lanes.
+    /// * `lin` Linear segment, used below `knee`.
+    /// * `pw` Power segment, used below 1.
+    #[target_feature(enable = "sse2")]
+    unsafe fn srgb_lanes(
+        x: __m128,
+        lin: __m128,
+        pw: __m128,
+        knee: f32,
+    ) -> __m128 {
+        let one = _mm_set1_ps(1.0);
+        let z = _mm_setzero_ps();
+        // NaN lanes compare false, and become 1 before clamping
+        let r = select(_mm_cmplt_ps(x, one), pw, one);
+        let r = select(_mm_cmplt_ps(x, _mm_set1_ps(knee)), lin, r);
+        let r = select(_mm_cmple_ps(x, z), z, r);
+        _mm_min_ps(r, one)
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn widen_8_16(src: &[Ch8], dst: &mut [Ch16]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        for i in 0..len / 16 {
+            let v = _mm_loadu_si128(s.add(i));
+            _mm_storeu_si128(d.add(i * 2), _mm_unpacklo_epi8(v, v));
+            _mm_storeu_si128(d.add(i * 2 + 1), _mm_unpackhi_epi8(v, v));
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn narrow_16_8(src: &[Ch16], dst: &mut [Ch8]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        for i in 0..len / 16 {
+            let a = _mm_srli_epi16(_mm_loadu_si128(s.add(i * 2)), 8);
+            let b = _mm_srli_epi16(_mm_loadu_si128(s.add(i * 2 + 1)), 8);
+            _mm_storeu_si128(d.add(i), _mm_packus_epi16(a, b));
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn float_8(src: &[Ch8], dst: &mut [Ch32]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut f32;
+        let z = _mm_setzero_si128();
+        let k = _mm_set1_ps(255.0);
+        for i in 0..len / 16 {
+            let v = _mm_loadu_si128(s.add(i));
+            let lo = _mm_unpacklo_epi8(v, z);
+            let hi = _mm_unpackhi_epi8(v, z);
+            let w = [
+                _mm_unpacklo_epi16(lo, z),
+                _mm_unpackhi_epi16(lo, z),
+                _mm_unpacklo_epi16(hi, z),
+                _mm_unpackhi_epi16(hi, z),
+            ];
+            for (j, w) in w.iter().enumerate() {
+                let f = _mm_div_ps(_mm_cvtepi32_ps(*w), k);
+                _mm_storeu_ps(d.add(i * 16 + j * 4), f);
+            }
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn float_16(src: &[Ch16], dst: &mut [Ch32]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut f32;
+        let z = _mm_setzero_si128();
+        let k = _mm_set1_ps(65535.0);
+        for i in 0..len / 8 {
+            let v = _mm_loadu_si128(s.add(i));
+            let lo = _mm_cvtepi32_ps(_mm_unpacklo_epi16(v, z));
+            let hi = _mm_cvtepi32_ps(_mm_unpackhi_epi16(v, z));
+            _mm_storeu_ps(d.add(i * 8), _mm_div_ps(lo, k));
+            _mm_storeu_ps(d.add(i * 8 + 4), _mm_div_ps(hi, k));
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn fixed_8(src: &[Ch32], dst: &mut [Ch8]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        for i in 0..len / 16 {
+            let s = s.add(i * 16);
+            let r0 = round_scaled(_mm_loadu_ps(s), 255.0);
+            let r1 = round_scaled(_mm_loadu_ps(s.add(4)), 255.0);
+            let r2 = round_scaled(_mm_loadu_ps(s.add(8)), 255.0);
+            let r3 = round_scaled(_mm_loadu_ps(s.add(12)), 255.0);
+            let a = _mm_packs_epi32(r0, r1);
+            let b = _mm_packs_epi32(r2, r3);
+            _mm_storeu_si128(d.add(i), _mm_packus_epi16(a, b));
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn fixed_16(src: &[Ch32], dst: &mut [Ch16]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        for i in 0..len / 8 {
+            let s = s.add(i * 8);
+            let r0 = round_scaled(_mm_loadu_ps(s), 65535.0);
+            let r1 = round_scaled(_mm_loadu_ps(s.add(4)), 65535.0);
+            _mm_storeu_si128(d.add(i), pack_u32(r0, r1));
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn mul_8(src: &[Ch8], dst: &mut [Ch8]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        let z = _mm_setzero_si128();
+        for i in 0..len / 16 {
+            let c = _mm_loadu_si128(d.add(i));
+            let a = _mm_loadu_si128(s.add(i));
+            let lo =
+                mul_lanes_8(_mm_unpacklo_epi8(c, z), _mm_unpacklo_epi8(a, z));
+            let hi =
+                mul_lanes_8(_mm_unpackhi_epi8(c, z), _mm_unpackhi_epi8(a, z));
+            _mm_storeu_si128(d.add(i), _mm_packus_epi16(lo, hi));
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn div_8(src: &[Ch8], dst: &mut [Ch8]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        let z = _mm_setzero_si128();
+        for i in 0..len / 16 {
+            let c = _mm_loadu_si128(d.add(i));
+            let a = _mm_loadu_si128(s.add(i));
+            let (cl, ch) = (_mm_unpacklo_epi8(c, z), _mm_unpackhi_epi8(c, z));
+            let (al, ah) = (_mm_unpacklo_epi8(a, z), _mm_unpackhi_epi8(a, z));
+            let q0 = div_lanes_8(
+                _mm_unpacklo_epi16(cl, z),
+                _mm_unpacklo_epi16(al, z),
+            );
+            let q1 = div_lanes_8(
+                _mm_unpackhi_epi16(cl, z),
+                _mm_unpackhi_epi16(al, z),
+            );
+            let q2 = div_lanes_8(
+                _mm_unpacklo_epi16(ch, z),
+                _mm_unpacklo_epi16(ah, z),
+            );
+            let q3 = div_lanes_8(
+                _mm_unpackhi_epi16(ch, z),
+                _mm_unpackhi_epi16(ah, z),
+            );
+            let lo = _mm_packs_epi32(q0, q1);
+            let hi = _mm_packs_epi32(q2, q3);
+            _mm_storeu_si128(d.add(i), _mm_packus_epi16(lo, hi));
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn mul_16(src: &[Ch16], dst: &mut [Ch16]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        let z = _mm_setzero_si128();
+        for i in 0..len / 8 {
+            let c = _mm_loadu_si128(d.add(i));
+            let a = _mm_loadu_si128(s.add(i));
+            let lo = mul_lanes_16(
+                _mm_unpacklo_epi16(c, z),
+                _mm_unpacklo_epi16(a, z),
+            );
+            let hi = mul_lanes_16(
+                _mm_unpackhi_epi16(c, z),
+                _mm_unpackhi_epi16(a, z),
+            );
+            _mm_storeu_si128(d.add(i), pack_u32(lo, hi));
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn div_16(src: &[Ch16], dst: &mut [Ch16]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        let z = _mm_setzero_si128();
+        for i in 0..len / 8 {
+            let c = _mm_loadu_si128(d.add(i));
+            let a = _mm_loadu_si128(s.add(i));
+            let lo = div_lanes_16(
+                _mm_unpacklo_epi16(c, z),
+                _mm_unpacklo_epi16(a, z),
+            );
+            let hi = div_lanes_16(
+                _mm_unpackhi_epi16(c, z),
+                _mm_unpackhi_epi16(a, z),
+            );
+            _mm_storeu_si128(d.add(i), pack_u32(lo, hi));
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn mul_32(src: &[Ch32], dst: &mut [Ch32]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        for i in (0..len).step_by(4) {
+            let c = _mm_loadu_ps(d.add(i));
+            let a = _mm_loadu_ps(s.add(i));
+            _mm_storeu_ps(d.add(i), _mm_mul_ps(c, a));
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn div_32(src: &[Ch32], dst: &mut [Ch32]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        for i in (0..len).step_by(4) {
+            let c = _mm_loadu_ps(d.add(i));
+            let a = _mm_loadu_ps(s.add(i));
+            let q = _mm_min_ps(_mm_div_ps(c, a), _mm_set1_ps(1.0));
+            let m = _mm_cmpgt_ps(a, _mm_setzero_ps());
+            _mm_storeu_ps(d.add(i), _mm_and_ps(m, q));
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn decode_srgb_32(
+        src: &[Ch32],
+        dst: &mut [Ch32],
+    ) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        for i in (0..len).step_by(4) {
+            let x = _mm_loadu_ps(s.add(i));
+            let lin = _mm_div_ps(x, _mm_set1_ps(12.92));
+            let v = _mm_add_ps(x, _mm_set1_ps(0.055));
+            let v = _mm_div_ps(v, _mm_set1_ps(1.055));
+            let pw = pow_lanes(v, 2.4);
+            _mm_storeu_ps(d.add(i), srgb_lanes(x, lin, pw, 0.04045));
+        }
+        len
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn encode_srgb_32(
+        src: &[Ch32],
+        dst: &mut [Ch32],
+    ) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        for i in (0..len).step_by(4) {
+            let x = _mm_loadu_ps(s.add(i));
+            let lin = _mm_mul_ps(x, _mm_set1_ps(12.92));
+            let pw = _mm_mul_ps(pow_lanes(x, 1.0 / 2.4), _mm_set1_ps(1.055));
+            let pw = _mm_sub_ps(pw, _mm_set1_ps(0.055));
+            _mm_storeu_ps(d.add(i), srgb_lanes(x, lin, pw, 0.003_130_8));
+        }
+        len
+    }
+}
+
+/// AVX2 kernels, converting 16 channels at a time
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod avx2 {
+    use crate::gamma::{EXP2_COEF, LOG2_COEF, SQRT_HALF_BITS};
+    use crate::{Ch16, Ch32, Ch8};
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    /// Scale and round lanes to integers, with halves away from zero
+    #[target_feature(enable = "avx2")]
+    unsafe fn round_scaled(v: __m256, max: f32) -> __m256i {
+        let x = _mm256_mul_ps(v, _mm256_set1_ps(max));
+        // NaN becomes zero, as with `as` casts
+        let x = _mm256_max_ps(x, _mm256_setzero_ps());
+        let x = _mm256_min_ps(x, _mm256_set1_ps(max));
+        let t = _mm256_cvttps_epi32(x);
+        // Subtracting the truncated value is exact
+        let f = _mm256_sub_ps(x, _mm256_cvtepi32_ps(t));
+        let h = _mm256_set1_ps(0.5);
+        let up = _mm256_castps_si256(_mm256_cmp_ps(f, h, _CMP_GE_OQ));
+        _mm256_sub_epi32(t, up)
+    }
+
+    /// Split 256-bit lanes into halves
+    #[target_feature(enable = "avx2")]
+    unsafe fn halves(v: __m256i) -> (__m128i, __m128i) {
+        (_mm256_castsi256_si128(v), _mm256_extracti128_si256(v, 1))
+    }
+
+    /// Multiply 8-bit values in 16-bit lanes, as `Ch8` does
+    #[target_feature(enable = "avx2")]
+    unsafe fn mul_lanes_8(l: __m256i, r: __m256i) -> __m256i {
+        let l =
+            _mm256_or_si256(_mm256_slli_epi16(l, 4), _mm256_srli_epi16(l, 4));
+        let r =
+            _mm256_or_si256(_mm256_slli_epi16(r, 4), _mm256_srli_epi16(r, 4));
+        _mm256_mulhi_epu16(l, r)
+    }
+
+    /// Multiply 16-bit values in 32-bit lanes, as `Ch16` does
+    #[target_feature(enable = "avx2")]
+    unsafe fn mul_lanes_16(l: __m256i, r: __m256i) -> __m256i {
+        let l =
+            _mm256_or_si256(_mm256_slli_epi32(l, 8), _mm256_srli_epi32(l, 8));
+        let r =
+            _mm256_or_si256(_mm256_slli_epi32(r, 8), _mm256_srli_epi32(r, 8));
+        let even = _mm256_srli_epi64(_mm256_mul_epu32(l, r), 32);
+        let l = _mm256_srli_epi64(l, 32);
+        let r = _mm256_srli_epi64(r, 32);
+        let odd = _mm256_mul_epu32(l, r);
+        let mask = _mm256_set_epi32(-1, 0, -1, 0, -1, 0, -1, 0);
+        _mm256_or_si256(even, _mm256_and_si256(odd, mask))
+    }
+
+    /// Divide 16-bit values in 32-bit lanes, as `Ch16` does
+    #[target_feature(enable = "avx2")]
+    unsafe fn div_lanes_16(c: __m128i, a: __m128i) -> __m128i {
+        let n = _mm256_mul_pd(_mm256_cvtepi32_pd(c), _mm256_set1_pd(65536.0));
+        let q = _mm256_div_pd(n, _mm256_cvtepi32_pd(a));
+        let q = _mm256_cvttpd_epi32(_mm256_min_pd(q, _mm256_set1_pd(65535.0)));
+        _mm_andnot_si128(_mm_cmpeq_epi32(a, _mm_setzero_si128()), q)
+    }
+
+    /// Evaluate a polynomial with Horner's method, as `gamma` does
+    #[target_feature(enable = "avx2")]
+    unsafe fn horner(x: __m256d, coef: &[f64]) -> __m256d {
+        let (last, rest) = coef.split_last().expect("no coefficients");
+        let mut acc = _mm256_set1_pd(*last);
+        for c in rest.iter().rev() {
+            acc = _mm256_add_pd(_mm256_mul_pd(acc, x), _mm256_set1_pd(*c));
+        }
+        acc
+    }
+
+    /// Raise four mantissas, scaled by 2^`e`, to a power
+    #[target_feature(enable = "avx2")]
+    unsafe fn pow_quad(m: __m128, e: __m128i, g: f64) -> __m128 {
+        let m = _mm256_cvtps_pd(m);
+        let one = _mm256_set1_pd(1.0);
+        let t = _mm256_div_pd(_mm256_sub_pd(m, one), _mm256_add_pd(m, one));
+        let l = _mm256_mul_pd(t, horner(_mm256_mul_pd(t, t), &LOG2_COEF));
+        let y = _mm256_add_pd(l, _mm256_cvtepi32_pd(e));
+        let y = _mm256_mul_pd(y, _mm256_set1_pd(g));
+        let n = _mm256_cvttpd_epi32(_mm256_sub_pd(y, _mm256_set1_pd(0.5)));
+        let f = _mm256_sub_pd(y, _mm256_cvtepi32_pd(n));
+        let p = _mm_castps_si128(_mm256_cvtpd_ps(horner(f, &EXP2_COEF)));
+        _mm_castsi128_ps(_mm_add_epi32(p, _mm_slli_epi32(n, 23)))
+    }
+
+    /// Raise lanes to a power, as `srgb_powf` does
+    #[target_feature(enable = "avx2")]
+    unsafe fn pow_lanes(v: __m256, g: f64) -> __m256 {
+        let bits = _mm256_castps_si256(v);
+        let e = _mm256_sub_epi32(bits, _mm256_set1_epi32(SQRT_HALF_BITS));
+        let e = _mm256_srai_epi32(e, 23);
+        let m = _mm256_sub_epi32(bits, _mm256_slli_epi32(e, 23));
+        let m = _mm256_castsi256_ps(m);
+        let (elo, ehi) = halves(e);
+        let lo = pow_quad(_mm256_castps256_ps128(m), elo, g);
+        let hi = pow_quad(_mm256_extractf128_ps(m, 1), ehi, g);
+        _mm256_set_m128(hi, lo)
+    }
+
+    /// Finish sRGB lanes, as `SrgbValue` for `Ch32` does.
+    ///
+    /// * `x` Input lanes.
+    /// * `lin` Linear segment, used below `knee`.
+    /// * `pw` Power segment, used below 1.
+    #[target_feature(enable = "avx2")]
+    unsafe fn srgb_lanes(
+        x: __m256,
+        lin: __m256,
+        pw: __m256,
+        knee: f32,
+    ) -> __m256 {
+        let one = _mm256_set1_ps(1.0);
+        let z = _mm256_setzero_ps();
+        // NaN lanes compare false, and become 1 before clamping
+        let m = _mm256_cmp_ps(x, one, _CMP_LT_OQ);
+        let r = _mm256_blendv_ps(one, pw, m);
+        let m = _mm256_cmp_ps(x, _mm256_set1_ps(knee), _CMP_LT_OQ);
+        let r = _mm256_blendv_ps(r, lin, m);
+        let r = _mm256_blendv_ps(r, z, _mm256_cmp_ps(x, z, _CMP_LE_OQ));
+        _mm256_min_ps(r, one)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn widen_8_16(src: &[Ch8], dst: &mut [Ch16]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut __m256i;
+        for i in 0..len / 16 {
+            let v = _mm256_cvtepu8_epi16(_mm_loadu_si128(s.add(i)));
+            let v = _mm256_or_si256(v, _mm256_slli_epi16(v, 8));
+            _mm256_storeu_si256(d.add(i), v);
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn narrow_16_8(src: &[Ch16], dst: &mut [Ch8]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m256i;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        for i in 0..len / 16 {
+            let v = _mm256_srli_epi16(_mm256_loadu_si256(s.add(i)), 8);
+            let (lo, hi) = halves(v);
+            _mm_storeu_si128(d.add(i), _mm_packus_epi16(lo, hi));
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn float_8(src: &[Ch8], dst: &mut [Ch32]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const u8;
+        let d = dst.as_mut_ptr() as *mut f32;
+        let k = _mm256_set1_ps(255.0);
+        for i in (0..len).step_by(8) {
+            let v = _mm_loadl_epi64(s.add(i) as *const __m128i);
+            let v = _mm256_cvtepi32_ps(_mm256_cvtepu8_epi32(v));
+            _mm256_storeu_ps(d.add(i), _mm256_div_ps(v, k));
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn float_16(src: &[Ch16], dst: &mut [Ch32]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut f32;
+        let k = _mm256_set1_ps(65535.0);
+        for i in 0..len / 8 {
+            let v = _mm256_cvtepu16_epi32(_mm_loadu_si128(s.add(i)));
+            let v = _mm256_cvtepi32_ps(v);
+            _mm256_storeu_ps(d.add(i * 8), _mm256_div_ps(v, k));
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn fixed_8(src: &[Ch32], dst: &mut [Ch8]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut u8;
+        for i in (0..len).step_by(8) {
+            let r = round_scaled(_mm256_loadu_ps(s.add(i)), 255.0);
+            let (lo, hi) = halves(r);
+            let p = _mm_packs_epi32(lo, hi);
+            let p = _mm_packus_epi16(p, p);
+            _mm_storel_epi64(d.add(i) as *mut __m128i, p);
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn fixed_16(src: &[Ch32], dst: &mut [Ch16]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        for i in 0..len / 8 {
+            let r = round_scaled(_mm256_loadu_ps(s.add(i * 8)), 65535.0);
+            let (lo, hi) = halves(r);
+            _mm_storeu_si128(d.add(i), _mm_packus_epi32(lo, hi));
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn mul_8(src: &[Ch8], dst: &mut [Ch8]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        for i in 0..len / 16 {
+            let c = _mm256_cvtepu8_epi16(_mm_loadu_si128(d.add(i)));
+            let a = _mm256_cvtepu8_epi16(_mm_loadu_si128(s.add(i)));
+            let (lo, hi) = halves(mul_lanes_8(c, a));
+            _mm_storeu_si128(d.add(i), _mm_packus_epi16(lo, hi));
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn div_8(src: &[Ch8], dst: &mut [Ch8]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const u8;
+        let d = dst.as_mut_ptr() as *mut u8;
+        let z = _mm256_setzero_si256();
+        let k = _mm256_set1_ps(255.0);
+        for i in (0..len).step_by(8) {
+            let c = _mm_loadl_epi64(d.add(i) as *const __m128i);
+            let a = _mm_loadl_epi64(s.add(i) as *const __m128i);
+            let c = _mm256_cvtepu8_epi32(c);
+            let a = _mm256_cvtepu8_epi32(a);
+            let n = _mm256_cvtepi32_ps(_mm256_slli_epi32(c, 8));
+            let q = _mm256_div_ps(n, _mm256_cvtepi32_ps(a));
+            let q = _mm256_cvttps_epi32(_mm256_min_ps(q, k));
+            let q = _mm256_andnot_si256(_mm256_cmpeq_epi32(a, z), q);
+            let (lo, hi) = halves(q);
+            let p = _mm_packs_epi32(lo, hi);
+            let p = _mm_packus_epi16(p, p);
+            _mm_storel_epi64(d.add(i) as *mut __m128i, p);
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn mul_16(src: &[Ch16], dst: &mut [Ch16]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        for i in 0..len / 8 {
+            let c = _mm256_cvtepu16_epi32(_mm_loadu_si128(d.add(i)));
+            let a = _mm256_cvtepu16_epi32(_mm_loadu_si128(s.add(i)));
+            let (lo, hi) = halves(mul_lanes_16(c, a));
+            _mm_storeu_si128(d.add(i), _mm_packus_epi32(lo, hi));
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn div_16(src: &[Ch16], dst: &mut [Ch16]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const __m128i;
+        let d = dst.as_mut_ptr() as *mut __m128i;
+        for i in 0..len / 8 {
+            let c = _mm_loadu_si128(d.add(i));
+            let a = _mm_loadu_si128(s.add(i));
+            let lo = div_lanes_16(_mm_cvtepu16_epi32(c), _mm_cvtepu16_epi32(a));
+            let c = _mm_cvtepu16_epi32(_mm_srli_si128(c, 8));
+            let a = _mm_cvtepu16_epi32(_mm_srli_si128(a, 8));
+            let hi = div_lanes_16(c, a);
+            _mm_storeu_si128(d.add(i), _mm_packus_epi32(lo, hi));
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn mul_32(src: &[Ch32], dst: &mut [Ch32]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        for i in (0..len).step_by(8) {
+            let c = _mm256_loadu_ps(d.add(i));
+            let a = _mm256_loadu_ps(s.add(i));
+            _mm256_storeu_ps(d.add(i), _mm256_mul_ps(c, a));
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn div_32(src: &[Ch32], dst: &mut [Ch32]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        let z = _mm256_setzero_ps();
+        for i in (0..len).step_by(8) {
+            let c = _mm256_loadu_ps(d.add(i));
+            let a = _mm256_loadu_ps(s.add(i));
+            let q = _mm256_div_ps(c, a);
+            let q = _mm256_min_ps(q, _mm256_set1_ps(1.0));
+            let m = _mm256_cmp_ps(a, z, _CMP_GT_OQ);
+            _mm256_storeu_ps(d.add(i), _mm256_and_ps(m, q));
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn decode_srgb_32(
+        src: &[Ch32],
+        dst: &mut [Ch32],
+    ) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        for i in (0..len).step_by(8) {
+            let x = _mm256_loadu_ps(s.add(i));
+            let lin = _mm256_div_ps(x, _mm256_set1_ps(12.92));
+            let v = _mm256_add_ps(x, _mm256_set1_ps(0.055));
+            let v = _mm256_div_ps(v, _mm256_set1_ps(1.055));
+            let pw = pow_lanes(v, 2.4);
+            _mm256_storeu_ps(d.add(i), srgb_lanes(x, lin, pw, 0.04045));
+        }
+        len
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn encode_srgb_32(
+        src: &[Ch32],
+        dst: &mut [Ch32],
+    ) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        for i in (0..len).step_by(8) {
+            let x = _mm256_loadu_ps(s.add(i));
+            let lin = _mm256_mul_ps(x, _mm256_set1_ps(12.92));
+            let pw = pow_lanes(x, 1.0 / 2.4);
+            let pw = _mm256_mul_ps(pw, _mm256_set1_ps(1.055));
+            let pw = _mm256_sub_ps(pw, _mm256_set1_ps(0.055));
+            _mm256_storeu_ps(d.add(i), srgb_lanes(x, lin, pw, 0.003_130_8));
+        }
+        len
+    }
+}
+
+/// NEON kernels, converting 16 channels at a time
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use crate::gamma::{EXP2_COEF, LOG2_COEF, SQRT_HALF_BITS};
+    use crate::{Ch16, Ch32, Ch8};
+    use std::arch::aarch64::*;
+
+    /// Multiply 8-bit values in 16-bit lanes, as `Ch8` does
+    #[target_feature(enable = "neon")]
+    unsafe fn mul_lanes_8(l: uint16x8_t, r: uint16x8_t) -> uint16x8_t {
+        let l = vorrq_u16(vshlq_n_u16(l, 4), vshrq_n_u16(l, 4));
+        let r = vorrq_u16(vshlq_n_u16(r, 4), vshrq_n_u16(r, 4));
+        let lo = vmull_u16(vget_low_u16(l), vget_low_u16(r));
+        let hi = vmull_high_u16(l, r);
+        vcombine_u16(vshrn_n_u32(lo, 16), vshrn_n_u32(hi, 16))
+    }
+
+    /// Divide 8-bit values in 32-bit lanes, as `Ch8` does
+    #[target_feature(enable = "neon")]
+    unsafe fn div_lanes_8(c: uint32x4_t, a: uint32x4_t) -> uint32x4_t {
+        let n = vcvtq_f32_u32(vshlq_n_u32(c, 8));
+        let q = vdivq_f32(n, vcvtq_f32_u32(a));
+        let q = vcvtq_u32_f32(vminq_f32(q, vdupq_n_f32(255.0)));
+        vbicq_u32(q, vceqq_u32(a, vdupq_n_u32(0)))
+    }
+
+    /// Multiply 16-bit values in 32-bit lanes, as `Ch16` does
+    #[target_feature(enable = "neon")]
+    unsafe fn mul_lanes_16(l: uint32x4_t, r: uint32x4_t) -> uint32x4_t {
+        let l = vorrq_u32(vshlq_n_u32(l, 8), vshrq_n_u32(l, 8));
+        let r = vorrq_u32(vshlq_n_u32(r, 8), vshrq_n_u32(r, 8));
+        let lo = vmull_u32(vget_low_u32(l), vget_low_u32(r));
+        let hi = vmull_high_u32(l, r);
+        vcombine_u32(vshrn_n_u64(lo, 32), vshrn_n_u64(hi, 32))
+    }
+
+    /// Divide two 16-bit values in 64-bit lanes, as `Ch16` does
+    #[target_feature(enable = "neon")]
+    unsafe fn div_pair_16(c: uint64x2_t, a: uint64x2_t) -> uint32x2_t {
+        let n = vmulq_f64(vcvtq_f64_u64(c), vdupq_n_f64(65536.0));
+        let q = vdivq_f64(n, vcvtq_f64_u64(a));
+        vmovn_u64(vcvtq_u64_f64(vminq_f64(q, vdupq_n_f64(65535.0))))
+    }
+
+    /// Divide 16-bit values in 32-bit lanes, as `Ch16` does
+    #[target_feature(enable = "neon")]
+    unsafe fn div_lanes_16(c: uint32x4_t, a: uint32x4_t) -> uint32x4_t {
+        let lo =
+            div_pair_16(vmovl_u32(vget_low_u32(c)), vmovl_u32(vget_low_u32(a)));
+        let hi = div_pair_16(vmovl_high_u32(c), vmovl_high_u32(a));
+        let q = vcombine_u32(lo, hi);
+        vbicq_u32(q, vceqq_u32(a, vdupq_n_u32(0)))
+    }
+
+    /// Evaluate a polynomial with Horner's method, as `gamma` does
+    #[target_feature(enable = "neon")]
+    unsafe fn horner(x: float64x2_t, coef: &[f64]) -> float64x2_t {
+        let (last, rest) = coef.split_last().expect("no coefficients");
+        let mut acc = vdupq_n_f64(*last);
+        for c in rest.iter().rev() {
+            acc = vaddq_f64(vmulq_f64(acc, x), vdupq_n_f64(*c));
+        }
+        acc
+    }
+
+    /// Raise two mantissas, scaled by 2^`e`, to a power
+    #[target_feature(enable = "neon")]
+    unsafe fn pow_pair(m: float64x2_t, e: int64x2_t, g: f64) -> float32x2_t {
+        let one = vdupq_n_f64(1.0);
+        let t = vdivq_f64(vsubq_f64(m, one), vaddq_f64(m, one));
+        let l = vmulq_f64(t, horner(vmulq_f64(t, t), &LOG2_COEF));
+        let y = vmulq_f64(vaddq_f64(l, vcvtq_f64_s64(e)), vdupq_n_f64(g));
+        let n = vcvtq_s64_f64(vsubq_f64(y, vdupq_n_f64(0.5)));
+        let f = vsubq_f64(y, vcvtq_f64_s64(n));
+        let p = vreinterpret_s32_f32(vcvt_f32_f64(horner(f, &EXP2_COEF)));
+        let n = vshl_n_s32(vmovn_s64(n), 23);
+        vreinterpret_f32_s32(vadd_s32(p, n))
+    }
+
+    /// Raise lanes to a power, as `srgb_powf` does
+    #[target_feature(enable = "neon")]
+    unsafe fn pow_lanes(v: float32x4_t, g: f64) -> float32x4_t {
+        let bits = vreinterpretq_s32_f32(v);
+        let e = vsubq_s32(bits, vdupq_n_s32(SQRT_HALF_BITS));
+        let e = vshrq_n_s32(e, 23);
+        let m = vreinterpretq_f32_s32(vsubq_s32(bits, vshlq_n_s32(e, 23)));
+        let lo = vcvt_f64_f32(vget_low_f32(m));
+        let lo = pow_pair(lo, vmovl_s32(vget_low_s32(e)), g);
+        let hi = pow_pair(vcvt_high_f64_f32(m), vmovl_high_s32(e), g);
+        vcombine_f32(lo, hi)
+    }
+
+    /// Finish sRGB lanes, as `SrgbValue` for `Ch32` does.
+    ///
+    /// * `x` Input lanes.
+    /// * `lin` Linear segment, used below `knee`.
+    /// * `pw` Power segment, used below 1.
+    #[target_feature(enable = "neon")]
+    unsafe fn srgb_lanes(
+        x: float32x4_t,
+        lin: float32x4_t,
+        pw: float32x4_t,
+        knee: f32,
+    ) -> float32x4_t {
+        let one = vdupq_n_f32(1.0);
+        let z = vdupq_n_f32(0.0);
+        // NaN lanes compare false, and become 1 before clamping
+        let r = vbslq_f32(vcltq_f32(x, one), pw, one);
+        let r = vbslq_f32(vcltq_f32(x, vdupq_n_f32(knee)), lin, r);
+        let r = vbslq_f32(vcleq_f32(x, z), z, r);
+        vminq_f32(r, one)
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn widen_8_16(src: &[Ch8], dst: &mut [Ch16]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const u8;
+        let d = dst.as_mut_ptr() as *mut u16;
+        for i in (0..len).step_by(16) {
+            let v = vld1q_u8(s.add(i));
+            let lo = vmovl_u8(vget_low_u8(v));
+            let hi = vmovl_high_u8(v);
+            vst1q_u16(d.add(i), vorrq_u16(lo, vshlq_n_u16(lo, 8)));
+            vst1q_u16(d.add(i + 8), vorrq_u16(hi, vshlq_n_u16(hi, 8)));
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn narrow_16_8(src: &[Ch16], dst: &mut [Ch8]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const u16;
+        let d = dst.as_mut_ptr() as *mut u8;
+        for i in (0..len).step_by(16) {
+            let lo = vshrn_n_u16(vld1q_u16(s.add(i)), 8);
+            let hi = vshrn_n_u16(vld1q_u16(s.add(i + 8)), 8);
+            vst1q_u8(d.add(i), vcombine_u8(lo, hi));
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn float_8(src: &[Ch8], dst: &mut [Ch32]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const u8;
+        let d = dst.as_mut_ptr() as *mut f32;
+        let k = vdupq_n_f32(255.0);
+        for i in (0..len).step_by(16) {
+            let v = vld1q_u8(s.add(i));
+            let lo = vmovl_u8(vget_low_u8(v));
+            let hi = vmovl_high_u8(v);
+            let w = [
+                vmovl_u16(vget_low_u16(lo)),
+                vmovl_high_u16(lo),
+                vmovl_u16(vget_low_u16(hi)),
+                vmovl_high_u16(hi),
+            ];
+            for (j, w) in w.iter().enumerate() {
+                let f = vdivq_f32(vcvtq_f32_u32(*w), k);
+                vst1q_f32(d.add(i + j * 4), f);
+            }
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn float_16(src: &[Ch16], dst: &mut [Ch32]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const u16;
+        let d = dst.as_mut_ptr() as *mut f32;
+        let k = vdupq_n_f32(65535.0);
+        for i in (0..len).step_by(8) {
+            let v = vld1q_u16(s.add(i));
+            let lo = vcvtq_f32_u32(vmovl_u16(vget_low_u16(v)));
+            let hi = vcvtq_f32_u32(vmovl_high_u16(v));
+            vst1q_f32(d.add(i), vdivq_f32(lo, k));
+            vst1q_f32(d.add(i + 4), vdivq_f32(hi, k));
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn fixed_8(src: &[Ch32], dst: &mut [Ch8]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut u8;
+        let k = vdupq_n_f32(255.0);
+        for i in (0..len).step_by(16) {
+            // Round to nearest with ties away from zero, saturating
+            let r = [
+                vcvtaq_u32_f32(vmulq_f32(vld1q_f32(s.add(i)), k)),
+                vcvtaq_u32_f32(vmulq_f32(vld1q_f32(s.add(i + 4)), k)),
+                vcvtaq_u32_f32(vmulq_f32(vld1q_f32(s.add(i + 8)), k)),
+                vcvtaq_u32_f32(vmulq_f32(vld1q_f32(s.add(i + 12)), k)),
+            ];
+            let lo = vcombine_u16(vqmovn_u32(r[0]), vqmovn_u32(r[1]));
+            let hi = vcombine_u16(vqmovn_u32(r[2]), vqmovn_u32(r[3]));
+            vst1q_u8(d.add(i), vcombine_u8(vqmovn_u16(lo), vqmovn_u16(hi)));
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn fixed_16(src: &[Ch32], dst: &mut [Ch16]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut u16;
+        let k = vdupq_n_f32(65535.0);
+        for i in (0..len).step_by(8) {
+            // Round to nearest with ties away from zero, saturating
+            let lo = vcvtaq_u32_f32(vmulq_f32(vld1q_f32(s.add(i)), k));
+            let hi = vcvtaq_u32_f32(vmulq_f32(vld1q_f32(s.add(i + 4)), k));
+            vst1q_u16(d.add(i), vcombine_u16(vqmovn_u32(lo), vqmovn_u32(hi)));
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn mul_8(src: &[Ch8], dst: &mut [Ch8]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const u8;
+        let d = dst.as_mut_ptr() as *mut u8;
+        for i in (0..len).step_by(16) {
+            let c = vld1q_u8(d.add(i));
+            let a = vld1q_u8(s.add(i));
+            let (cl, al) = (vmovl_u8(vget_low_u8(c)), vmovl_u8(vget_low_u8(a)));
+            let lo = mul_lanes_8(cl, al);
+            let hi = mul_lanes_8(vmovl_high_u8(c), vmovl_high_u8(a));
+            vst1q_u8(d.add(i), vcombine_u8(vmovn_u16(lo), vmovn_u16(hi)));
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn div_8(src: &[Ch8], dst: &mut [Ch8]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const u8;
+        let d = dst.as_mut_ptr() as *mut u8;
+        for i in (0..len).step_by(16) {
+            let c = vld1q_u8(d.add(i));
+            let a = vld1q_u8(s.add(i));
+            let (cl, ch) = (vmovl_u8(vget_low_u8(c)), vmovl_high_u8(c));
+            let (al, ah) = (vmovl_u8(vget_low_u8(a)), vmovl_high_u8(a));
+            let q0 = div_lanes_8(
+                vmovl_u16(vget_low_u16(cl)),
+                vmovl_u16(vget_low_u16(al)),
+            );
+            let q1 = div_lanes_8(vmovl_high_u16(cl), vmovl_high_u16(al));
+            let q2 = div_lanes_8(
+                vmovl_u16(vget_low_u16(ch)),
+                vmovl_u16(vget_low_u16(ah)),
+            );
+            let q3 = div_lanes_8(vmovl_high_u16(ch), vmovl_high_u16(ah));
+            let lo = vcombine_u16(vmovn_u32(q0), vmovn_u32(q1));
+            let hi = vcombine_u16(vmovn_u32(q2), vmovn_u32(q3));
+            vst1q_u8(d.add(i), vcombine_u8(vmovn_u16(lo), vmovn_u16(hi)));
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn mul_16(src: &[Ch16], dst: &mut [Ch16]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const u16;
+        let d = dst.as_mut_ptr() as *mut u16;
+        for i in (0..len).step_by(8) {
+            let c = vld1q_u16(d.add(i));
+            let a = vld1q_u16(s.add(i));
+            let cl = vmovl_u16(vget_low_u16(c));
+            let al = vmovl_u16(vget_low_u16(a));
+            let lo = mul_lanes_16(cl, al);
+            let hi = mul_lanes_16(vmovl_high_u16(c), vmovl_high_u16(a));
+            vst1q_u16(d.add(i), vcombine_u16(vmovn_u32(lo), vmovn_u32(hi)));
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn div_16(src: &[Ch16], dst: &mut [Ch16]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const u16;
+        let d = dst.as_mut_ptr() as *mut u16;
+        for i in (0..len).step_by(8) {
+            let c = vld1q_u16(d.add(i));
+            let a = vld1q_u16(s.add(i));
+            let cl = vmovl_u16(vget_low_u16(c));
+            let al = vmovl_u16(vget_low_u16(a));
+            let lo = div_lanes_16(cl, al);
+            let hi = div_lanes_16(vmovl_high_u16(c), vmovl_high_u16(a));
+            vst1q_u16(d.add(i), vcombine_u16(vmovn_u32(lo), vmovn_u32(hi)));
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn mul_32(src: &[Ch32], dst: &mut [Ch32]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        for i in (0..len).step_by(4) {
+            let c = vld1q_f32(d.add(i));
+            let a = vld1q_f32(s.add(i));
+            vst1q_f32(d.add(i), vmulq_f32(c, a));
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn div_32(src: &[Ch32], dst: &mut [Ch32]) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        for i in (0..len).step_by(4) {
+            let c = vld1q_f32(d.add(i));
+            let a = vld1q_f32(s.add(i));
+            let q = vminq_f32(vdivq_f32(c, a), vdupq_n_f32(1.0));
+            let m = vcgtq_f32(a, vdupq_n_f32(0.0));
+            let q = vandq_u32(vreinterpretq_u32_f32(q), m);
+            vst1q_f32(d.add(i), vreinterpretq_f32_u32(q));
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn decode_srgb_32(
+        src: &[Ch32],
+        dst: &mut [Ch32],
+    ) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        for i in (0..len).step_by(4) {
+            let x = vld1q_f32(s.add(i));
+            let lin = vdivq_f32(x, vdupq_n_f32(12.92));
+            let v = vaddq_f32(x, vdupq_n_f32(0.055));
+            let v = vdivq_f32(v, vdupq_n_f32(1.055));
+            let pw = pow_lanes(v, 2.4);
+            vst1q_f32(d.add(i), srgb_lanes(x, lin, pw, 0.04045));
+        }
+        len
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn encode_srgb_32(
+        src: &[Ch32],
+        dst: &mut [Ch32],
+    ) -> usize {
+        let len = src.len().min(dst.len()) & !15;
+        let s = src.as_ptr() as *const f32;
+        let d = dst.as_mut_ptr() as *mut f32;
+        for i in (0..len).step_by(4) {
+            let x = vld1q_f32(s.add(i));
+            let lin = vmulq_f32(x, vdupq_n_f32(12.92));
+            let pw = vmulq_f32(pow_lanes(x, 1.0 / 2.4), vdupq_n_f32(1.055));
+            let pw = vsubq_f32(pw, vdupq_n_f32(0.055));
+            vst1q_f32(d.add(i), srgb_lanes(x, lin, pw, 0.003_130_8));
+        }
+        len
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::*;
+
+    /// Xorshift pseudo-random numbers
+    struct Rng(u32);
+
+    impl Rng {
+        fn next(&mut self) -> u32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            self.0
+        }
+        fn f32(&mut self) -> f32 {
+            (self.next() >> 8) as f32 / 16_777_215.0
+        }
+    }
+
+    /// Get the bytes of a slice
+    fn bytes<T>(s: &[T]) -> &[u8] {
+        unsafe { s.align_to::<u8>().1 }
+    }
+
+    /// Check a kernel against scalar conversion
+    fn check<S, D, F>(
+        kernel: unsafe fn(&[S], &mut [D]) -> usize,
+        src: &[S],
+        dst: &[D],
+        scalar: F,
+    ) where
+        S: Copy,
+        D: Copy + std::fmt::Debug,
+        F: Fn(S, D) -> D,
+    {
+        let mut out = dst.to_vec();
+        let done = unsafe { kernel(src, &mut out) };
+        assert_eq!(done, src.len() & !15);
+        let expected: Vec<D> = src[..done]
+            .iter()
+            .zip(dst)
+            .map(|(s, d)| scalar(*s, *d))
+            .collect();
+        for (i, (o, e)) in out.iter().zip(&expected).enumerate() {
+            assert_eq!(bytes(&[*o]), bytes(&[*e]), "{}: {:?} {:?}", i, o, e);
+        }
+    }
+
+    /// Kernel test inputs
+    struct Inputs {
+        u8s: Vec<Ch8>,
+        u16s: Vec<Ch16>,
+        f32s: Vec<Ch32>,
+        raw_32: Vec<Ch32>,
+        pairs_8: (Vec<Ch8>, Vec<Ch8>),
+        pairs_16: (Vec<Ch16>, Vec<Ch16>),
+        pairs_32: (Vec<Ch32>, Vec<Ch32>),
+    }
+
+    impl Inputs {
+        fn new() -> Self {
+            let mut rng = Rng(0x1234_5678);
+            let u8s: Vec<Ch8> = (0..=255).map(Ch8::new).collect();
+            let u16s: Vec<Ch16> = (0..=u16::MAX).map(Ch16::new).collect();
+            let mut f32s = vec![Ch32::new(0.0), Ch32::new(-0.0)];
+            f32s.extend(u16s.iter().map(|c| Ch32::from(*c)));
+            // Values near rounding halves
+            for m in [255.0_f32, 65535.0].iter() {
+                for i in 0..*m as u32 {
+                    let h = (i as f32 + 0.5) / m;
+                    f32s.push(Ch32::new(h));
+                    f32s.push(Ch32::new(f32::from_bits(h.to_bits() - 1)));
+                    f32s.push(Ch32::new(f32::from_bits(h.to_bits() + 1)));
+                }
+            }
+            // Values near sRGB segment thresholds
+            for t in [0.003_130_8_f32, 0.04045].iter() {
+                for i in 0..64 {
+                    f32s.push(Ch32::new(f32::from_bits(t.to_bits() - 32 + i)));
+                }
+            }
+            f32s.extend((0..100_000).map(|_| Ch32::new(rng.f32())));
+            // Values which can only come from raw bytes
+            let raw = [f32::NAN, -f32::NAN, 1.5, f32::INFINITY, -1.0, 1e-40];
+            let raw_32 = raw
+                .iter()
+                .cycle()
+                .take(32)
+                .map(|v| unsafe { std::mem::transmute::<f32, Ch32>(*v) })
+                .collect();
+            let pairs_8 = (0..=u16::MAX)
+                .map(|i| (Ch8::new(i as u8), Ch8::new((i >> 8) as u8)))
+                .unzip();
+            let edges = [0, 1, 2, 0xFF, 0x100, 0x7FFF, 0x8000, 0xFFFE, 0xFFFF];
+            let mut pairs_16: (Vec<Ch16>, Vec<Ch16>) = edges
+                .iter()
+                .flat_map(|a| edges.iter().map(move |c| (*a, *c)))
+                .map(|(a, c)| (Ch16::new(a), Ch16::new(c)))
+                .unzip();
+            for _ in 0..200_000 {
+                let v = rng.next();
+                pairs_16.0.push(Ch16::new(v as u16));
+                pairs_16.1.push(Ch16::new((v >> 16) as u16));
+            }
+            let mut pairs_32 = (vec![], vec![]);
+            for i in 0..200_000 {
+                let a = match i % 16 {
+                    0 => 0.0,
+                    1 => 1.0,
+                    _ => rng.f32(),
+                };
+                pairs_32.0.push(Ch32::new(a));
+                pairs_32.1.push(Ch32::new(rng.f32()));
+            }
+            Inputs {
+                u8s,
+                u16s,
+                f32s,
+                raw_32,
+                pairs_8,
+                pairs_16,
+                pairs_32,
+            }
+        }
+    }
+
+    macro_rules! check_kernels {
+        ($isa:ident, $inp:expr) => {
+            let inp = &$inp;
+            let (u8s, u16s, f32s) = (&inp.u8s, &inp.u16s, &inp.f32s);
+            let c16 = vec![Ch16::default(); u8s.len()];
+            check($isa::widen_8_16, u8s, &c16, |s, _| s.into());
+            let c8 = vec![Ch8::default(); u16s.len()];
+            check($isa::narrow_16_8, u16s, &c8, |s, _| s.into());
+            let c32 = vec![Ch32::default(); u8s.len()];
+            check($isa::float_8, u8s, &c32, |s, _| s.into());
+            let c32 = vec![Ch32::default(); u16s.len()];
+            check($isa::float_16, u16s, &c32, |s, _| s.into());
+            let c8 = vec![Ch8::default(); f32s.len()];
+            check($isa::fixed_8, f32s, &c8, |s, _| s.into());
+            let c16 = vec![Ch16::default(); f32s.len()];
+            check($isa::fixed_16, f32s, &c16, |s, _| s.into());
+            let (a, c) = &inp.pairs_8;
+            check($isa::mul_8, a, c, |a, c| c * a);
+            check($isa::div_8, a, c, |a, c| c / a);
+            let (a, c) = &inp.pairs_16;
+            check($isa::mul_16, a, c, |a, c| c * a);
+            check($isa::div_16, a, c, |a, c| c / a);
+            let (a, c) = &inp.pairs_32;
+            check($isa::mul_32, a, c, |a, c| c * a);
+            check($isa::div_32, a, c, |a, c| c / a);
+            for s in [f32s, &inp.raw_32].iter() {
+                let c32 = vec![Ch32::default(); s.len()];
+                check($isa::decode_srgb_32, s, &c32, |s, _| s.decode_srgb());
+                check($isa::encode_srgb_32, s, &c32, |s, _| s.encode_srgb());
+            }
+        };
+    }
+
+    #[test]
+    fn kernels() {
+        let inputs = Inputs::new();
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("sse2") {
+                check_kernels!(sse2, inputs);
+            }
+            if is_x86_feature_detected!("avx2") {
+                check_kernels!(avx2, inputs);
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                check_kernels!(neon, inputs);
+            }
+        }
+        let _ = inputs;
+    }
+
+    /// Check slice conversion against converting each pixel
+    fn check_slice<S, D>()
+    where
+        S: Pixel,
+        D: Pixel,
+        D::Chan: From<S::Chan>,
+    {
+        let mut rng = Rng(0x9E37_79B9);
+        let src: Vec<S> = (0..1_027)
+            .map(|_| {
+                S::with_rgba([
+                    S::Chan::from(rng.f32()),
+                    S::Chan::from(rng.f32()),
+                    S::Chan::from(rng.f32()),
+                    S::Chan::from(rng.f32()),
+                ])
+            })
+            .collect();
+        let mut dst = vec![D::default(); src.len()];
+        Conversion::default().convert_slice(&src, &mut dst);
+        let expected: Vec<D> = src.iter().map(|p| p.convert()).collect();
+        assert_eq!(bytes(&dst), bytes(&expected));
+    }
+
+    #[test]
+    fn slices() {
+        assert!(layout::<SRgba8>() == Some((4, Some(3))));
+        assert!(layout::<SArgb16>() == Some((4, Some(0))));
+        assert!(layout::<Rgb32>() == Some((3, None)));
+        assert!(layout::<Mask8>() == Some((1, Some(0))));
+        assert!(layout::<Hsv8>().is_none());
+        check_slice::<SRgba8, Rgba8p>();
+        check_slice::<SRgba8, Rgba16p>();
+        check_slice::<SRgba8, SRgba32p>();
+        check_slice::<Rgba8p, SRgba8>();
+        check_slice::<SRgba8p, SRgba16>();
+        check_slice::<SRgba16, SRgba8p>();
+        check_slice::<SRgba16p, SRgba32>();
+        check_slice::<SRgba32, SRgba32p>();
+        check_slice::<Rgba32p, SRgba32>();
+        check_slice::<Rgba16p, Rgba8p>();
+        check_slice::<Rgba32p, SRgba8>();
+        check_slice::<Rgba32, Rgba16p>();
+        check_slice::<SRgb8, Rgb16>();
+        check_slice::<SRgb16, SRgb8>();
+        check_slice::<Rgb32, SRgb8>();
+        check_slice::<SRgb32, Rgb32>();
+        check_slice::<Rgb32, SRgb32>();
+        check_slice::<SGrayAlpha8, GrayAlpha32p>();
+        check_slice::<SGray16, Gray8>();
+        check_slice::<SArgb8p, Argb16>();
+        check_slice::<SBgra8, Bgra32p>();
+        check_slice::<Mask8, Mask16>();
+        check_slice::<Mask32, Mask8>();
+        check_slice::<Rgb8, Rgba8p>();
+        check_slice::<SRgb8, SBgr8>();
+        check_slice::<P3Rgb8, SRgb8>();
+    }
+}
@@ -0,0 +1,187 @@
+// video.rs     Video encoding items.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+//! Module for video encoding items
+use crate::private::Sealed;
+use crate::{gamma, gamut, Ch16, Channel};
+use std::any::{Any, TypeId};
+use std::fmt::Debug;
+
+/// Matrix coefficients for converting between *RGB* and *YCbCr*.
+///
+/// Each matrix also determines the RGB [primaries] and [gamma] mode of the
+/// gamma encoded *red*, *green* and *blue* which it is applied to.
+///
+/// This trait is *sealed*, and cannot be implemented outside of this crate.
+///
+/// [gamma]: ../gamma/trait.Mode.html
+/// [primaries]: ../gamut/trait.Primaries.html
+pub trait Matrix:
+    Any + Copy + Clone + Debug + Default + PartialEq + Send + Sync + Sealed
+{
+    /// Contribution of *red* to *luma*
+    const KR: f32;
+    /// Contribution of *blue* to *luma*
+    const KB: f32;
+    /// RGB primaries
+    type Primaries: gamut::Primaries;
+    /// Gamma mode
+    type Gamma: gamma::Mode;
+}
+
+/// ITU-R BT.601 (standard definition) matrix coefficients, used by JPEG
+///
+/// As in JPEG (JFIF), these apply to [sRGB] primaries and [gamma].
+///
+/// [gamma]: ../gamma/struct.Srgb.html
+/// [sRGB]: ../gamut/struct.Srgb.html
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Bt601;
+
+/// ITU-R BT.709 (high definition) matrix coefficients
+///
+/// These apply to BT.709 ([sRGB]) primaries with [Rec. 709] gamma.
+///
+/// [Rec. 709]: ../gamma/struct.Rec709.html
+/// [sRGB]: ../gamut/struct.Srgb.html
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Bt709;
+
+/// ITU-R BT.2020 (ultra high definition) non-constant luminance matrix
+/// coefficients
+///
+/// These apply to [Rec. 2020] primaries with [Rec. 709] gamma.
+///
+/// [Rec. 709]: ../gamma/struct.Rec709.html
+/// [Rec. 2020]: ../gamut/struct.Rec2020.html
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Bt2020;
+
+impl Matrix for Bt601 {
+    const KR: f32 = 0.299;
+    const KB: f32 = 0.114;
+    type Primaries = gamut::Srgb;
+    type Gamma = gamma::Srgb;
+}
+
+impl Matrix for Bt709 {
+    const KR: f32 = 0.2126;
+    const KB: f32 = 0.0722;
+    type Primaries = gamut::Srgb;
+    type Gamma = gamma::Rec709;
+}
+
+impl Matrix for Bt2020 {
+    const KR: f32 = 0.2627;
+    const KB: f32 = 0.0593;
+    type Primaries = gamut::Rec2020;
+    type Gamma = gamma::Rec709;
+}
+
+/// Quantization range of *YCbCr* components.
+///
+/// Limited range code values are defined for 8 bits, and scaled by 256 for
+/// [Ch16](../struct.Ch16.html).  [Ch32](../struct.Ch32.html) uses the 8-bit
+/// code values divided by 255.
+///
+/// This trait is *sealed*, and cannot be implemented outside of this crate.
+pub trait Range:
+    Any + Copy + Clone + Debug + Default + PartialEq + Send + Sync + Sealed
+{
+    /// Encode *luma* (0 to 1) into a `Channel` value.
+    fn encode_luma<C: Channel>(y: f32) -> C;
+    /// Decode *luma* (0 to 1) from a `Channel` value.
+    fn decode_luma<C: Channel>(c: C) -> f32;
+    /// Encode *chroma* (-0.5 to 0.5) into a `Channel` value.
+    fn encode_chroma<C: Channel>(c: f32) -> C;
+    /// Decode *chroma* (-0.5 to 0.5) from a `Channel` value.
+    fn decode_chroma<C: Channel>(c: C) -> f32;
+}
+
+/// Full range: *luma* uses all code values, with *chroma* centered on 128
+/// (8-bit), as used by JPEG
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Full;
+
+/// Limited (studio) range: *luma* from 16 to 235 and *chroma* from 16 to 240
+/// (8-bit), as used by broadcast video
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Limited;
+
+/// Get the 8-bit code value scale and maximum code value of a `Channel`
+fn code_scale<C: Channel>() -> (f32, f32) {
+    if TypeId::of::<C>() == TypeId::of::<Ch16>() {
+        (256.0, 65535.0)
+    } else {
+        (1.0, 255.0)
+    }
+}
+
+/// Get the center (zero) *chroma* value of a `Channel`
+fn chroma_center<C: Channel>() -> f32 {
+    let (scale, max) = code_scale::<C>();
+    128.0 * scale / max
+}
+
+impl Range for Full {
+    fn encode_luma<C: Channel>(y: f32) -> C {
+        C::from(y)
+    }
+    fn decode_luma<C: Channel>(c: C) -> f32 {
+        c.into()
+    }
+    fn encode_chroma<C: Channel>(c: f32) -> C {
+        C::from(chroma_center::<C>() + c)
+    }
+    fn decode_chroma<C: Channel>(c: C) -> f32 {
+        let c: f32 = c.into();
+        c - chroma_center::<C>()
+    }
+}
+
+impl Range for Limited {
+    fn encode_luma<C: Channel>(y: f32) -> C {
+        let (scale, max) = code_scale::<C>();
+        C::from((16.0 + 219.0 * y) * scale / max)
+    }
+    fn decode_luma<C: Channel>(c: C) -> f32 {
+        let (scale, max) = code_scale::<C>();
+        let c: f32 = c.into();
+        (c * max / scale - 16.0) / 219.0
+    }
+    fn encode_chroma<C: Channel>(c: f32) -> C {
+        let (scale, max) = code_scale::<C>();
+        C::from((128.0 + 224.0 * c) * scale / max)
+    }
+    fn decode_chroma<C: Channel>(c: C) -> f32 {
+        let (scale, max) = code_scale::<C>();
+        let c: f32 = c.into();
+        (c * max / scale - 128.0) / 224.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Ch32, Ch8};
+    #[test]
+    fn full_range() {
+        assert_eq!(Full::encode_chroma::<Ch8>(0.0), Ch8::new(128));
+        assert_eq!(Full::encode_chroma::<Ch16>(0.0), Ch16::new(32768));
+        assert_eq!(Full::encode_luma::<Ch16>(1.0), Ch16::new(65535));
+        assert_eq!(Full::decode_chroma(Ch8::new(128)), 0.0);
+    }
+    #[test]
+    fn limited_range() {
+        assert_eq!(Limited::encode_luma::<Ch8>(0.0), Ch8::new(16));
+        assert_eq!(Limited::encode_luma::<Ch8>(1.0), Ch8::new(235));
+        assert_eq!(Limited::encode_chroma::<Ch8>(-0.5), Ch8::new(16));
+        assert_eq!(Limited::encode_chroma::<Ch8>(0.5), Ch8::new(240));
+        assert_eq!(Limited::encode_luma::<Ch16>(0.0), Ch16::new(4096));
+        assert_eq!(Limited::encode_luma::<Ch16>(1.0), Ch16::new(60160));
+        assert_eq!(Limited::encode_chroma::<Ch16>(0.5), Ch16::new(61440));
+        assert_eq!(Limited::encode_luma::<Ch32>(0.0), Ch32::new(16.0 / 255.0));
+        assert!((Limited::decode_luma(Ch8::new(235)) - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,827 @@
+// view.rs      Borrowed raster views.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::raster::{Raster, RasterIter, Region};
+use crate::{Error, Pixel};
+use std::marker::PhantomData;
+use std::mem::{align_of, size_of};
+
+/// Number of pixels in each band of a parallel `set_region`
+#[cfg(feature = "rayon")]
+const BAND_PIXELS: usize = 1 << 18;
+
+/// Borrowed view of pixels arranged as a rectangular array, with a row
+/// stride.
+///
+/// Rows of a view may be padded, as in frames from memory-mapped files, GPU
+/// readbacks or FFI buffers.  Pixels are never copied.
+///
+/// ### View a padded buffer
+/// ```
+/// # use pix::*;
+/// // 2x2 pixels, with 2 bytes of padding per row
+/// let buf = [1, 2, 3, 4, 5, 6, 0, 0, 7, 8, 9, 10, 11, 12];
+/// let view = RasterView::<SRgb8>::from_u8_slice(2, 2, 8, &buf);
+/// assert_eq!(view.pixel(0, 1), SRgb8::new(7, 8, 9));
+/// let r = RasterBuilder::<SRgba8>::new().with_view(&view);
+/// ```
+#[derive(Clone, Copy)]
+pub struct RasterView<'a, P: Pixel> {
+    width: u32,
+    height: u32,
+    stride: usize,
+    data: *const u8,
+    _pixels: PhantomData<&'a [P]>,
+}
+
+/// Mutable borrowed view of pixels arranged as a rectangular array, with a
+/// row stride.
+///
+/// This is a mutable version of [RasterView](struct.RasterView.html).
+/// Views of disjoint regions can be modified at the same time, such as tiles
+/// from `Raster`::[tiles_mut](struct.Raster.html#method.tiles_mut).
+///
+/// ### Fill a padded buffer
+/// ```
+/// # use pix::*;
+/// let mut buf = [0; 14];
+/// let mut view =
+///     RasterViewMut::<SRgb8>::from_u8_slice_mut(2, 2, 8, &mut buf);
+/// view.set_region(view.region(), SRgb8::new(1, 2, 3));
+/// assert_eq!(buf, [1, 2, 3, 1, 2, 3, 0, 0, 1, 2, 3, 1, 2, 3]);
+/// ```
+pub struct RasterViewMut<'a, P: Pixel> {
+    width: u32,
+    height: u32,
+    stride: usize,
+    data: *mut u8,
+    _pixels: PhantomData<&'a mut [P]>,
+}
+
+unsafe impl<P: Pixel + Sync> Send for RasterView<'_, P> {}
+
+unsafe impl<P: Pixel + Sync> Sync for RasterView<'_, P> {}
+
+unsafe impl<P: Pixel + Send> Send for RasterViewMut<'_, P> {}
+
+unsafe impl<P: Pixel + Sync> Sync for RasterViewMut<'_, P> {}
+
+/// Check that a buffer can hold a view.
+///
+/// * `width` Width of view.
+/// * `height` Height of view.
+/// * `stride` Row stride, in bytes.
+/// * `data` Buffer of pixel data.
+fn check_view<P>(
+    width: u32,
+    height: u32,
+    stride: usize,
+    data: &[u8],
+) -> Result<(), Error>
+where
+    P: Pixel,
+{
+    let row = (width as usize)
+        .checked_mul(size_of::<P>())
+        .ok_or(Error::SizeOverflow)?;
+    if stride < row {
+        return Err(Error::InvalidStride);
+    }
+    if stride % align_of::<P>() != 0
+        || data.as_ptr() as usize % align_of::<P>() != 0
+    {
+        return Err(Error::Misaligned);
+    }
+    if height > 0 {
+        let len = stride
+            .checked_mul(height as usize - 1)
+            .and_then(|n| n.checked_add(row))
+            .ok_or(Error::SizeOverflow)?;
+        if data.len() < len {
+            return Err(Error::BufferLength {
+                expected: len,
+                actual: data.len(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Get byte offset of a `Region`, clipped to a view.
+///
+/// * `reg` Region within view.
+/// * `view` Region of entire view.
+/// * `stride` Row stride, in bytes.
+fn clip_region<P: Pixel>(
+    reg: Region,
+    view: Region,
+    stride: usize,
+) -> (Region, usize) {
+    let reg = view.intersection(reg);
+    if reg.width > 0 && reg.height > 0 {
+        let offset = reg.y as usize * stride + reg.x as usize * size_of::<P>();
+        (reg, offset)
+    } else {
+        (Region::new(0, 0, 0, 0), 0)
+    }
+}
+
+/// Get view of a pixel slice as a `u8` slice.
+fn u8_slice<P: Pixel>(pix: &[P]) -> &[u8] {
+    unsafe { pix.align_to::<u8>().1 }
+}
+
+/// Get view of a pixel slice as a mutable `u8` slice.
+fn u8_slice_mut<P: Pixel>(pix: &mut [P]) -> &mut [u8] {
+    unsafe { pix.align_to_mut::<u8>().1 }
+}
+
+impl<'a, P: Pixel> From<&'a Raster<P>> for RasterView<'a, P> {
+    /// Get a view of an entire `Raster`.
+    fn from(raster: &'a Raster<P>) -> Self {
+        let stride = raster.width() as usize * size_of::<P>();
+        RasterView {
+            width: raster.width(),
+            height: raster.height(),
+            stride,
+            data: raster.as_u8_slice().as_ptr(),
+            _pixels: PhantomData,
+        }
+    }
+}
+
+impl<'a, P: Pixel> From<&'a mut Raster<P>> for RasterViewMut<'a, P> {
+    /// Get a mutable view of an entire `Raster`.
+    fn from(raster: &'a mut Raster<P>) -> Self {
+        let stride = raster.width() as usize * size_of::<P>();
+        RasterViewMut {
+            width: raster.width(),
+            height: raster.height(),
+            stride,
+            data: raster.as_u8_slice_mut().as_mut_ptr(),
+            _pixels: PhantomData,
+        }
+    }
+}
+
+impl<'a, P: Pixel> RasterView<'a, P> {
+    /// Create a view of a pixel slice.
+    ///
+    /// * `width` Width of view.
+    /// * `height` Height of view.
+    /// * `stride` Row stride, in bytes.
+    /// * `pixels` Pixel data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is not a multiple of the pixel alignment, is less
+    /// than `width` * `std::mem::size_of::<P>()`, or if `pixels` is too
+    /// small.
+    pub fn new(
+        width: u32,
+        height: u32,
+        stride: usize,
+        pixels: &'a [P],
+    ) -> Self {
+        Self::from_u8_slice(width, height, stride, u8_slice(pixels))
+    }
+    /// Create a view of a `u8` slice.
+    ///
+    /// * `width` Width of view.
+    /// * `height` Height of view.
+    /// * `stride` Row stride, in bytes.
+    /// * `buffer` Buffer of pixel data (in native-endian byte order).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` or `stride` is not aligned for `P`, if `stride` is
+    /// less than `width` * `std::mem::size_of::<P>()`, or if `buffer` is too
+    /// small.
+    pub fn from_u8_slice(
+        width: u32,
+        height: u32,
+        stride: usize,
+        buffer: &'a [u8],
+    ) -> Self {
+        Self::try_from_u8_slice(width, height, stride, buffer)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Try to create a view of a `u8` slice.
+    ///
+    /// This is the same as
+    /// [from_u8_slice](struct.RasterView.html#method.from_u8_slice), but
+    /// returns an [Error](enum.Error.html) instead of panicking.
+    ///
+    /// ## Example
+    /// ```
+    /// # use pix::*;
+    /// let buf = [0; 13];
+    /// let v = RasterView::<SRgb8>::try_from_u8_slice(2, 2, 8, &buf);
+    /// assert_eq!(
+    ///     v.err(),
+    ///     Some(Error::BufferLength {
+    ///         expected: 14,
+    ///         actual: 13
+    ///     })
+    /// );
+    /// ```
+    pub fn try_from_u8_slice(
+        width: u32,
+        height: u32,
+        stride: usize,
+        buffer: &'a [u8],
+    ) -> Result<Self, Error> {
+        check_view::<P>(width, height, stride, buffer)?;
+        Ok(RasterView {
+            width,
+            height,
+            stride,
+            data: buffer.as_ptr(),
+            _pixels: PhantomData,
+        })
+    }
+    /// Get width of view.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    /// Get height of view.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    /// Get row stride, in bytes.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+    /// Get `Region` of entire view.
+    pub fn region(&self) -> Region {
+        Region::new(0, 0, self.width(), self.height())
+    }
+    /// Get a view of a `Region`, sharing pixels with this view.
+    ///
+    /// * `reg` Region within view, clipped to its bounds.
+    pub fn view<R>(&self, reg: R) -> RasterView<'a, P>
+    where
+        R: Into<Region>,
+    {
+        let (reg, offset) =
+            clip_region::<P>(reg.into(), self.region(), self.stride);
+        RasterView {
+            width: reg.width,
+            height: reg.height,
+            stride: self.stride,
+            // Safety: region is within the view
+            data: unsafe { self.data.add(offset) },
+            _pixels: PhantomData,
+        }
+    }
+    /// Get one pixel.
+    pub fn pixel(&self, x: u32, y: u32) -> P {
+        let row = self.as_slice_row(y);
+        row[x as usize]
+    }
+    /// Get an `Iterator` of pixels within a `Region`.
+    ///
+    /// * `reg` Region within view.
+    pub fn region_iter<R>(&self, reg: R) -> RasterIter<'a, P>
+    where
+        R: Into<Region>,
+    {
+        RasterIter::new(*self, reg.into())
+    }
+    /// Get view of a row of pixels as a slice.
+    pub fn as_slice_row(&self, y: u32) -> &'a [P] {
+        assert!(y < self.height);
+        // Safety: the buffer was checked when the view was created
+        unsafe {
+            let ptr = self.data.add(y as usize * self.stride);
+            std::slice::from_raw_parts(ptr as *const P, self.width as usize)
+        }
+    }
+    /// Get view of a row of pixels as a `u8` slice.
+    pub fn as_u8_slice_row(&self, y: u32) -> &'a [u8] {
+        u8_slice(self.as_slice_row(y))
+    }
+}
+
+impl<'a, P: Pixel> RasterViewMut<'a, P> {
+    /// Create a mutable view of a pixel slice.
+    ///
+    /// * `width` Width of view.
+    /// * `height` Height of view.
+    /// * `stride` Row stride, in bytes.
+    /// * `pixels` Pixel data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is not a multiple of the pixel alignment, is less
+    /// than `width` * `std::mem::size_of::<P>()`, or if `pixels` is too
+    /// small.
+    pub fn new(
+        width: u32,
+        height: u32,
+        stride: usize,
+        pixels: &'a mut [P],
+    ) -> Self {
+        Self::from_u8_slice_mut(width, height, stride, u8_slice_mut(pixels))
+    }
+    /// Create a mutable view of a `u8` slice.
+    ///
+    /// * `width` Width of view.
+    /// * `height` Height of view.
+    /// * `stride` Row stride, in bytes.
+    /// * `buffer` Buffer of pixel data (in native-endian byte order).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` or `stride` is not aligned for `P`, if `stride` is
+    /// less than `width` * `std::mem::size_of::<P>()`, or if `buffer` is too
+    /// small.
+    pub fn from_u8_slice_mut(
+        width: u32,
+        height: u32,
+        stride: usize,
+        buffer: &'a mut [u8],
+    ) -> Self {
+        Self::try_from_u8_slice_mut(width, height, stride, buffer)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+    /// Try to create a mutable view of a `u8` slice.
+    ///
+    /// This is the same as
+    /// [from_u8_slice_mut](struct.RasterViewMut.html#method.from_u8_slice_mut),
+    /// but returns an [Error](enum.Error.html) instead of panicking.
+    pub fn try_from_u8_slice_mut(
+        width: u32,
+        height: u32,
+        stride: usize,
+        buffer: &'a mut [u8],
+    ) -> Result<Self, Error> {
+        check_view::<P>(width, height, stride, buffer)?;
+        Ok(RasterViewMut {
+            width,
+            height,
+            stride,
+            data: buffer.as_mut_ptr(),
+            _pixels: PhantomData,
+        })
+    }
+    /// Get width of view.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    /// Get height of view.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    /// Get row stride, in bytes.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+    /// Get `Region` of entire view.
+    pub fn region(&self) -> Region {
+        Region::new(0, 0, self.width(), self.height())
+    }
+    /// Get an immutable view.
+    pub fn as_view(&self) -> RasterView<'_, P> {
+        RasterView {
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+            data: self.data,
+            _pixels: PhantomData,
+        }
+    }
+    /// Get a mutable view of a `Region`, sharing pixels with this view.
+    ///
+    /// * `reg` Region within view, clipped to its bounds.
+    pub fn view_mut<R>(&mut self, reg: R) -> RasterViewMut<'_, P>
+    where
+        R: Into<Region>,
+    {
+        let view = RasterViewMut {
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+            data: self.data,
+            _pixels: PhantomData,
+        };
+        view.into_view_mut(reg.into())
+    }
+    /// Convert into a mutable view of a `Region`.
+    pub(crate) fn into_view_mut(self, reg: Region) -> Self {
+        let (reg, offset) = clip_region::<P>(reg, self.region(), self.stride);
+        RasterViewMut {
+            width: reg.width,
+            height: reg.height,
+            stride: self.stride,
+            // Safety: region is within the view
+            data: unsafe { self.data.add(offset) },
+            _pixels: PhantomData,
+        }
+    }
+    /// Split into two disjoint views at a row.
+    ///
+    /// * `y` Row of split, clipped to height.  The first view contains rows
+    ///   above `y`; the second view contains row `y` and all rows below it.
+    pub fn split_at_row(self, y: u32) -> (Self, Self) {
+        let y = y.min(self.height);
+        let bottom = Region::new(0, y as i32, self.width, self.height - y);
+        let top = RasterViewMut {
+            height: y,
+            ..self
+        };
+        let bottom = self.into_view_mut(bottom);
+        (top, bottom)
+    }
+    /// Split into two disjoint views at a column.
+    ///
+    /// * `x` Column of split, clipped to width.  The first view contains
+    ///   columns left of `x`; the second view contains column `x` and all
+    ///   columns right of it.
+    pub fn split_at_col(self, x: u32) -> (Self, Self) {
+        let x = x.min(self.width);
+        let right = Region::new(x as i32, 0, self.width - x, self.height);
+        let left = RasterViewMut {
+            width: x,
+            ..self
+        };
+        let right = self.into_view_mut(right);
+        (left, right)
+    }
+    /// Split into disjoint tiles.
+    ///
+    /// * `width` Width of each tile.
+    /// * `height` Height of each tile.
+    ///
+    /// Tiles are in row-major order, each with its `Region` within the
+    /// view.  Tiles at the right and bottom edges are clipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is zero.
+    pub fn into_tiles(
+        self,
+        width: u32,
+        height: u32,
+    ) -> Vec<(Region, RasterViewMut<'a, P>)> {
+        assert!(width > 0 && height > 0, "empty tile");
+        let mut tiles = vec![];
+        for y in (0..self.height).step_by(height as usize) {
+            for x in (0..self.width).step_by(width as usize) {
+                let reg = Region::new(x as i32, y as i32, width, height);
+                let reg = self.region().intersection(reg);
+                // Each tile region is disjoint
+                let tile = RasterViewMut {
+                    width: self.width,
+                    height: self.height,
+                    stride: self.stride,
+                    data: self.data,
+                    _pixels: PhantomData,
+                };
+                tiles.push((reg, tile.into_view_mut(reg)));
+            }
+        }
+        tiles
+    }
+    /// Get one pixel.
+    pub fn pixel(&self, x: u32, y: u32) -> P {
+        let row = self.as_slice_row(y);
+        row[x as usize]
+    }
+    /// Set one pixel value.
+    pub fn set_pixel<S>(&mut self, x: u32, y: u32, p: S)
+    where
+        P: From<S>,
+    {
+        let row = self.as_slice_row_mut(y);
+        row[x as usize] = p.into();
+    }
+    /// Clear all pixels to format default.
+    pub fn clear(&mut self) {
+        self.for_each_row(|_, row| {
+            for p in row.iter_mut() {
+                *p = P::default();
+            }
+        });
+    }
+    /// Call a function with each row of pixels.
+    ///
+    /// Rows are processed in parallel with the `rayon` feature.
+    pub(crate) fn for_each_row<F>(&mut self, f: F)
+    where
+        F: Fn(u32, &mut [P]) + Send + Sync,
+    {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            if self.width > 0 && self.height > 1 {
+                // Rows are disjoint, and borrowed for the lifetime of self
+                let view = RasterViewMut {
+                    width: self.width,
+                    height: self.height,
+                    stride: self.stride,
+                    data: self.data,
+                    _pixels: PhantomData,
+                };
+                view.into_tiles(self.width, 1).into_par_iter().for_each(
+                    |(reg, mut row)| f(reg.y as u32, row.as_slice_row_mut(0)),
+                );
+                return;
+            }
+        }
+        for y in 0..self.height {
+            f(y, self.as_slice_row_mut(y));
+        }
+    }
+    /// Get an `Iterator` of pixels within a `Region`.
+    ///
+    /// * `reg` Region within view.
+    pub fn region_iter<R>(&self, reg: R) -> RasterIter<'_, P>
+    where
+        R: Into<Region>,
+    {
+        RasterIter::new(self.as_view(), reg.into())
+    }
+    /// Set a `Region` using a pixel `Iterator`.
+    ///
+    /// * `reg` Region within view.
+    /// * `it` `Iterator` of pixels in `Region`.
+    pub fn set_region<R, S, I>(&mut self, reg: R, mut it: I)
+    where
+        R: Into<Region>,
+        S: Pixel,
+        P::Chan: From<S::Chan>,
+        I: Iterator<Item = S>,
+    {
+        let reg = reg.into();
+        let x0 = if reg.x >= 0 {
+            reg.x as u32
+        } else {
+            self.width()
+        };
+        let x1 = self.width().min(x0 + reg.width);
+        let (x0, x1) = (x0 as usize, x1 as usize);
+        let y0 = if reg.y >= 0 {
+            reg.y as u32
+        } else {
+            self.height()
+        };
+        let y1 = self.height().min(y0 + reg.height);
+        if y0 < y1 && x0 < x1 {
+            #[cfg(not(feature = "rayon"))]
+            for yi in y0..y1 {
+                let row = self.as_slice_row_mut(yi);
+                for p in row[x0..x1].iter_mut() {
+                    if let Some(s) = it.next() {
+                        *p = s.convert();
+                    }
+                }
+            }
+            #[cfg(feature = "rayon")]
+            {
+                // Take a band of pixels in order, then convert rows in
+                // parallel
+                let width = x1 - x0;
+                let rows = (BAND_PIXELS / width).max(1) as u32;
+                let mut y = y0;
+                while y < y1 {
+                    let height = rows.min(y1 - y);
+                    let len = width * height as usize;
+                    let band: Vec<S> = it.by_ref().take(len).collect();
+                    let reg =
+                        Region::new(x0 as i32, y as i32, width as u32, height);
+                    self.view_mut(reg).for_each_row(|yi, row| {
+                        let i = (yi as usize * width).min(band.len());
+                        let j = (i + width).min(band.len());
+                        for (p, s) in row.iter_mut().zip(&band[i..j]) {
+                            *p = s.convert();
+                        }
+                    });
+                    if band.len() < len {
+                        break;
+                    }
+                    y += height;
+                }
+            }
+        }
+    }
+    /// Get view of a row of pixels as a slice.
+    pub fn as_slice_row(&self, y: u32) -> &[P] {
+        assert!(y < self.height);
+        let ptr = unsafe { self.data.add(y as usize * self.stride) };
+        // Safety: the buffer was checked when the view was created
+        unsafe {
+            std::slice::from_raw_parts(ptr as *const P, self.width as usize)
+        }
+    }
+    /// Get view of a row of pixels as a mutable slice.
+    pub fn as_slice_row_mut(&mut self, y: u32) -> &mut [P] {
+        assert!(y < self.height);
+        let ptr = unsafe { self.data.add(y as usize * self.stride) };
+        // Safety: the buffer was checked when the view was created
+        unsafe {
+            std::slice::from_raw_parts_mut(ptr as *mut P, self.width as usize)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    #[test]
+    fn padded_u8() {
+        let buf = [
+            0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0xFF, 0xFF, 0xFF,
+            0x11, 0x21, 0x31, 0x41, 0x51, 0x61, 0x71, 0xFF, 0xFF, 0xFF,
+        ];
+        let view = RasterView::<Mask8>::from_u8_slice(7, 2, 10, &buf);
+        assert_eq!(view.stride(), 10);
+        assert_eq!(view.pixel(6, 1), Mask8::new(0x71));
+        assert_eq!(view.as_slice_row(0).len(), 7);
+        let view = RasterView::<SRgb8>::from_u8_slice(2, 2, 10, &buf);
+        assert_eq!(view.pixel(1, 1), SRgb8::new(0x41, 0x51, 0x61));
+        let v: Vec<_> = view.region_iter((1, 0, 1, 2)).collect();
+        assert_eq!(v, [SRgb8::new(0x40, 0x50, 0x60), view.pixel(1, 1)]);
+        let mut r = RasterBuilder::<SGray8>::new().with_clear(3, 3);
+        r.set_region((1, 1, 2, 2), view.region_iter(view.region()));
+        assert_eq!(r.pixel(0, 0), SGray8::new(0));
+        assert_eq!(r.pixel(2, 2), SGray8::new(0x61));
+    }
+
+    #[test]
+    fn padded_pixels() {
+        let mut pix = vec![Gray16::new(0xFFFF); 9];
+        let mut view = RasterViewMut::new(2, 3, 6, &mut pix);
+        view.clear();
+        view.set_pixel(1, 2, Gray16::new(0x1234));
+        view.set_region((0, 0, 1, 2), Gray8::new(0x80));
+        assert_eq!(view.pixel(0, 1), Gray16::new(0x8080));
+        let r = RasterBuilder::<Gray16>::new().with_view(&view.as_view());
+        assert_eq!(r.pixel(1, 2), Gray16::new(0x1234));
+        assert_eq!(pix[2], Gray16::new(0xFFFF));
+        assert_eq!(pix[3], Gray16::new(0x8080));
+        assert_eq!(pix[7], Gray16::new(0x1234));
+        let view = RasterView::new(3, 3, 6, &pix);
+        assert_eq!(view.pixel(2, 0), Gray16::new(0xFFFF));
+    }
+
+    #[test]
+    fn sub_views() {
+        let mut r = RasterBuilder::<SGray8>::new().with_clear(5, 4);
+        r.set_pixel(3, 2, SGray8::new(0x32));
+        let v = r.view((2, 1, 10, 10));
+        assert_eq!((v.width(), v.height()), (3, 3));
+        assert_eq!(v.pixel(1, 1), SGray8::new(0x32));
+        let v = v.view((1, 1, 1, 1));
+        assert_eq!(v.pixel(0, 0), SGray8::new(0x32));
+        let v = r.view((-2, 7, 4, 4));
+        assert_eq!((v.width(), v.height()), (0, 0));
+        let mut v = r.view_mut((1, 1, 3, 2));
+        v.clear();
+        let mut sub = v.view_mut((2, 1, 1, 1));
+        sub.set_pixel(0, 0, SGray8::new(0xFF));
+        assert_eq!(r.pixel(3, 2), SGray8::new(0xFF));
+    }
+
+    #[test]
+    fn split() {
+        let mut r = RasterBuilder::<Gray16>::new().with_clear(5, 4);
+        let (top, bottom) = r.view_mut(r.region()).split_at_row(1);
+        let (mut left, mut right) = bottom.split_at_col(2);
+        let mut top = top;
+        top.set_region(top.region(), Gray16::new(1));
+        left.set_region(left.region(), Gray16::new(2));
+        right.set_region(right.region(), Gray16::new(3));
+        assert_eq!((right.width(), right.height()), (3, 3));
+        assert_eq!(r.pixel(4, 0), Gray16::new(1));
+        assert_eq!(r.pixel(1, 3), Gray16::new(2));
+        assert_eq!(r.pixel(2, 1), Gray16::new(3));
+        let tiles = r.tiles_mut(2, 3);
+        assert_eq!(tiles.len(), 6);
+        for (i, (reg, mut tile)) in tiles.into_iter().enumerate() {
+            assert_eq!(tile.width(), reg.width());
+            assert_eq!(tile.height(), reg.height());
+            tile.set_region(tile.region(), Gray16::new(i as u16));
+        }
+        assert_eq!(r.pixel(4, 0), Gray16::new(2));
+        assert_eq!(r.pixel(0, 3), Gray16::new(3));
+        assert_eq!(r.pixel(4, 3), Gray16::new(5));
+    }
+
+    #[test]
+    fn large_region() {
+        let mut r = RasterBuilder::<SGray8>::new().with_clear(700, 500);
+        let len = 600 * 450 + 17;
+        let it = (0..len).map(|i| SGray8::new((i % 251) as u8));
+        r.set_region((10, 10, 600, 480), it);
+        for y in 0..500 {
+            for x in 0..700 {
+                let (i, j) = (x as usize, y as usize);
+                let e = if (10..610).contains(&i) && (10..490).contains(&j) {
+                    let n = (j - 10) * 600 + i - 10;
+                    if n < len {
+                        (n % 251) as u8
+                    } else {
+                        0
+                    }
+                } else {
+                    0
+                };
+                assert_eq!(r.pixel(x, y), SGray8::new(e));
+            }
+        }
+        let r16 = RasterBuilder::<SRgba16>::new().with_raster(&r);
+        for (p, s) in r16.as_slice().iter().zip(r.as_slice()) {
+            assert_eq!(*p, s.convert());
+        }
+    }
+
+    #[test]
+    fn nan_bytes() {
+        #[repr(align(4))]
+        struct Aligned([u8; 8]);
+        let mut buf = Aligned([0; 8]);
+        buf.0[..4].copy_from_slice(&f32::NAN.to_ne_bytes());
+        buf.0[4..].copy_from_slice(&0.5_f32.to_ne_bytes());
+        let view = RasterView::<Gray32>::from_u8_slice(2, 1, 8, &buf.0);
+        let (a, b) = (view.pixel(0, 0), view.pixel(1, 0));
+        assert!(b.value() < a.value());
+        assert_eq!(a, a);
+        assert_eq!(a.difference(b), Gray32::new(0.0));
+        assert_eq!(b.difference(a), Gray32::new(0.0));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel_matches_serial() {
+        fn check(width: u32, height: u32, reg: Region, len: usize) {
+            let it = (0..len).map(|i| SGray8::new((i % 253) as u8));
+            let mut par =
+                RasterBuilder::<SRgb16>::new().with_clear(width, height);
+            par.set_region(reg, it.clone());
+            let mut ser =
+                RasterBuilder::<SRgb16>::new().with_clear(width, height);
+            let mut it = it;
+            let reg = ser.region().intersection(reg);
+            for y in reg.y..reg.bottom() {
+                for x in reg.x..reg.right() {
+                    if let Some(p) = it.next() {
+                        let p: SRgb16 = p.convert();
+                        ser.set_pixel(x as u32, y as u32, p);
+                    }
+                }
+            }
+            assert!(par.as_slice() == ser.as_slice());
+        }
+        let band = super::BAND_PIXELS;
+        let reg = Region::new(3, 5, 990, 990);
+        let rows = band / 990;
+        check(1000, 1000, reg, 990 * 990);
+        check(1000, 1000, reg, 990 * rows * 2 + 500);
+        check(1000, 1000, reg, 990 * rows);
+        let reg = Region::new(2, 0, band as u32 + 5, 3);
+        check(band as u32 + 7, 3, reg, band * 2);
+        let mut r = RasterBuilder::<Gray16>::new().with_clear(300, 400);
+        r.view_mut(r.region()).for_each_row(|y, row| {
+            for (x, p) in row.iter_mut().enumerate() {
+                *p = Gray16::new((y as usize * 300 + x) as u16);
+            }
+        });
+        for (i, p) in r.as_slice().iter().enumerate() {
+            assert_eq!(*p, Gray16::new(i as u16));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn small_stride() {
+        let buf = [0; 16];
+        let _ = RasterView::<SRgb8>::from_u8_slice(2, 2, 5, &buf);
+    }
+
+    #[test]
+    #[should_panic]
+    fn small_buffer() {
+        let buf = [0; 13];
+        let _ = RasterView::<SRgb8>::from_u8_slice(2, 2, 8, &buf);
+    }
+
+    #[test]
+    fn try_view() {
+        let mut buf = [0; 16];
+        let v = RasterView::<SRgb8>::try_from_u8_slice(2, 2, 5, &buf);
+        assert_eq!(v.err(), Some(Error::InvalidStride));
+        let v = RasterView::<SRgb8>::try_from_u8_slice(u32::MAX, 2, 0, &buf);
+        assert_eq!(v.err(), Some(Error::InvalidStride));
+        let v = RasterViewMut::<Gray16>::try_from_u8_slice_mut(
+            2, 2, 5, &mut buf,
+        );
+        assert_eq!(v.err(), Some(Error::Misaligned));
+        let v = RasterView::<SRgb8>::try_from_u8_slice(2, 3, usize::MAX, &buf);
+        assert_eq!(v.err(), Some(Error::SizeOverflow));
+        let v = RasterView::<SRgb8>::try_from_u8_slice(2, 2, 8, &buf);
+        assert!(v.is_ok());
+    }
+}
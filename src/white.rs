@@ -0,0 +1,204 @@
+// white.rs     White points.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+//! Module for white point items
+use crate::format;
+use crate::matrix::{invert, mul_vec, Matrix3};
+use crate::private::Sealed;
+use std::any::Any;
+use std::fmt::Debug;
+
+/// Reference white for tristimulus color models.
+///
+/// This trait is *sealed*, and cannot be implemented outside of this crate.
+pub trait Point:
+    Any + Copy + Clone + Debug + Default + PartialEq + Send + Sync + Sealed
+{
+    /// CIE 1931 *XYZ* tristimulus values (2° observer), with *Y* normalized
+    /// to 1.
+    const XYZ: [f64; 3];
+
+    /// Runtime descriptor of the white point
+    const FORMAT: format::White;
+}
+
+/// CIE standard illuminant D50 (horizon light), used by ICC profiles
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct D50;
+
+/// CIE standard illuminant D55 (mid-morning / mid-afternoon daylight)
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct D55;
+
+/// ACES white point (approximately CIE D60), used by ACES color spaces
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct D60;
+
+/// CIE standard illuminant D65 (noon daylight), used by sRGB
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct D65;
+
+/// CIE standard illuminant D75 (north sky daylight)
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct D75;
+
+/// CIE standard illuminant E (equal energy)
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct E;
+
+impl Point for D50 {
+    const FORMAT: format::White = format::White::D50;
+    const XYZ: [f64; 3] = [0.964_22, 1.0, 0.825_21];
+}
+
+impl Point for D55 {
+    const FORMAT: format::White = format::White::D55;
+    const XYZ: [f64; 3] = [0.956_82, 1.0, 0.921_49];
+}
+
+impl Point for D60 {
+    const FORMAT: format::White = format::White::D60;
+    const XYZ: [f64; 3] = [0.952_646, 1.0, 1.008_825];
+}
+
+impl Point for D65 {
+    const FORMAT: format::White = format::White::D65;
+    const XYZ: [f64; 3] = [0.950_47, 1.0, 1.088_83];
+}
+
+impl Point for D75 {
+    const FORMAT: format::White = format::White::D75;
+    const XYZ: [f64; 3] = [0.949_72, 1.0, 1.226_38];
+}
+
+impl Point for E {
+    const FORMAT: format::White = format::White::E;
+    const XYZ: [f64; 3] = [1.0, 1.0, 1.0];
+}
+
+/// Chromatic adaptation transform.
+///
+/// Colors are adapted between white points by scaling cone responses in a
+/// transformed space (von Kries-style adaptation).  Each transform uses a
+/// different cone response matrix.
+///
+/// The transform is selected with [Conversion] settings.  It is used
+/// between RGB [primaries], and between the white points of [Xyz], [Lab]
+/// and [Lch] colors and any other format.
+///
+/// [Conversion]: ../struct.Conversion.html
+/// [Lab]: ../struct.Lab.html
+/// [Lch]: ../struct.Lch.html
+/// [primaries]: ../gamut/trait.Primaries.html
+/// [Xyz]: ../struct.Xyz.html
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Adaptation {
+    /// Bradford transform, as used by ICC profiles
+    #[default]
+    Bradford,
+    /// CAT02 transform, from the CIECAM02 color appearance model
+    Cat02,
+    /// Von Kries transform, using the Hunt-Pointer-Estevez cone responses
+    VonKries,
+}
+
+/// Bradford cone response matrix
+const BRADFORD: Matrix3 = [
+    [0.895_1, 0.266_4, -0.161_4],
+    [-0.750_2, 1.713_5, 0.036_7],
+    [0.038_9, -0.068_5, 1.029_6],
+];
+
+/// CAT02 cone response matrix
+const CAT02: Matrix3 = [
+    [0.732_8, 0.429_6, -0.162_4],
+    [-0.703_6, 1.697_5, 0.006_1],
+    [0.003_0, 0.013_6, 0.983_4],
+];
+
+/// Hunt-Pointer-Estevez cone response matrix (normalized to D65)
+const HUNT_POINTER_ESTEVEZ: Matrix3 = [
+    [0.400_24, 0.707_60, -0.080_81],
+    [-0.226_30, 1.165_32, 0.045_70],
+    [0.0, 0.0, 0.918_22],
+];
+
+/// Inverse Bradford cone response matrix
+const BRADFORD_INV: Matrix3 = invert(&BRADFORD);
+
+/// Inverse CAT02 cone response matrix
+const CAT02_INV: Matrix3 = invert(&CAT02);
+
+/// Inverse Hunt-Pointer-Estevez cone response matrix
+const HUNT_POINTER_ESTEVEZ_INV: Matrix3 = invert(&HUNT_POINTER_ESTEVEZ);
+
+impl Adaptation {
+    /// Get the cone response matrix and its inverse
+    fn matrices(self) -> (&'static Matrix3, &'static Matrix3) {
+        match self {
+            Adaptation::Bradford => (&BRADFORD, &BRADFORD_INV),
+            Adaptation::Cat02 => (&CAT02, &CAT02_INV),
+            Adaptation::VonKries => {
+                (&HUNT_POINTER_ESTEVEZ, &HUNT_POINTER_ESTEVEZ_INV)
+            }
+        }
+    }
+
+    /// Adapt *XYZ* tristimulus values from one white point to another.
+    ///
+    /// * `xyz` Tristimulus values relative to `src` white.
+    /// * `src` Source white point.
+    /// * `dst` Destination white point.
+    pub(crate) fn adapt(
+        self,
+        xyz: [f64; 3],
+        src: [f64; 3],
+        dst: [f64; 3],
+    ) -> [f64; 3] {
+        if src == dst {
+            return xyz;
+        }
+        let (m, inv) = self.matrices();
+        let s = mul_vec(m, src);
+        let d = mul_vec(m, dst);
+        let lms = mul_vec(m, xyz);
+        let lms = [
+            lms[0] * d[0] / s[0],
+            lms[1] * d[1] / s[1],
+            lms[2] * d[2] / s[2],
+        ];
+        mul_vec(inv, lms)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn adapt_white() {
+        use Adaptation::*;
+        for a in [Bradford, Cat02, VonKries].iter() {
+            let xyz = a.adapt(D65::XYZ, D65::XYZ, D50::XYZ);
+            for (a, b) in xyz.iter().zip(D50::XYZ.iter()) {
+                assert!((a - b).abs() < 1e-5);
+            }
+            let xyz = a.adapt(D50::XYZ, D50::XYZ, D65::XYZ);
+            for (a, b) in xyz.iter().zip(D65::XYZ.iter()) {
+                assert!((a - b).abs() < 1e-5);
+            }
+        }
+    }
+    #[test]
+    fn adapt_color() {
+        // sRGB red, from D65 to D50
+        let xyz = [0.412_4, 0.212_6, 0.019_3];
+        let b = Adaptation::Bradford.adapt(xyz, D65::XYZ, D50::XYZ);
+        let c = Adaptation::Cat02.adapt(xyz, D65::XYZ, D50::XYZ);
+        let v = Adaptation::VonKries.adapt(xyz, D65::XYZ, D50::XYZ);
+        assert!((b[0] - 0.436_1).abs() < 1e-3);
+        assert!((b[1] - 0.222_5).abs() < 1e-3);
+        assert!((b[2] - 0.013_9).abs() < 1e-3);
+        assert!(b != c && b != v && c != v);
+    }
+}
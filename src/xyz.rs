@@ -0,0 +1,326 @@
+// xyz.rs       CIE XYZ color model.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{
+    self, AChannel, Opaque, Premultiplied, Straight, Translucent,
+};
+use crate::format::Model;
+use crate::gamma::Linear;
+use crate::gamut::CieXyz;
+use crate::white::{self, D65};
+use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel};
+use std::marker::PhantomData;
+
+/// CIE 1931 XYZ tristimulus [color model].
+///
+/// The components are *X*, *Y* (luminance) and *Z*, with optional *[alpha]*.
+/// Each component is relative to the [white point], so [Channel::MAX] for all
+/// three is reference white.  Components are always in linear light.
+///
+/// [alpha]: alpha/trait.AChannel.html
+/// [Channel::MAX]: trait.Channel.html#associatedconstant.MAX
+/// [color model]: trait.ColorModel.html
+/// [white point]: white/trait.Point.html
+///
+/// ### Convert from `Rgb`
+/// ```
+/// # use pix::*;
+/// let xyz: Xyz16 = SRgb8::new(0xFF, 0xFF, 0xFF).convert();
+/// assert_eq!(xyz, Xyz16::new(0xFFFF, 0xFFFF, 0xFFFF));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Xyz<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    components: [C; 3],
+    alpha: A,
+    mode: PhantomData<M>,
+    white: PhantomData<W>,
+}
+
+impl<C, A, M, W> Xyz<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying *X*,
+    /// *Y* and *Z* values.
+    pub fn new<H>(x: H, y: H, z: H) -> Self
+    where
+        C: From<H>,
+        A: From<Opaque<C>>,
+    {
+        Self::with_alpha(x, y, z, Opaque::default())
+    }
+    /// Create a [Translucent](alpha/struct.Translucent.html) color by
+    /// specifying *X*, *Y*, *Z* and *alpha* values.
+    pub fn with_alpha<H, B>(x: H, y: H, z: H, alpha: B) -> Self
+    where
+        C: From<H>,
+        A: From<B>,
+    {
+        let components = [C::from(x), C::from(y), C::from(z)];
+        let alpha = A::from(alpha);
+        Xyz {
+            components,
+            alpha,
+            mode: PhantomData,
+            white: PhantomData,
+        }
+    }
+    /// Get the *X* component.
+    pub fn x(self) -> C {
+        self.components[0]
+    }
+    /// Get the *Y* (luminance) component.
+    pub fn y(self) -> C {
+        self.components[1]
+    }
+    /// Get the *Z* component.
+    pub fn z(self) -> C {
+        self.components[2]
+    }
+}
+
+impl<C, A, M, W> ColorModel for Xyz<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    type Chan = C;
+    const MODEL: Model = Model::Xyz;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
+
+    /// Get all non-alpha components
+    fn components(&self) -> &[Self::Chan] {
+        &self.components
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.alpha.value()
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    ///
+    /// The [primaries](gamut/struct.CieXyz.html) are *X*, *Y* and *Z*, so
+    /// the components are unchanged.
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        [self.x(), self.y(), self.z(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        Xyz::with_alpha(rgba[0], rgba[1], rgba[2], rgba[3])
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let x = if self.x() > rhs.x() {
+            self.x() - rhs.x()
+        } else {
+            rhs.x() - self.x()
+        };
+        let y = if self.y() > rhs.y() {
+            self.y() - rhs.y()
+        } else {
+            rhs.y() - self.y()
+        };
+        let z = if self.z() > rhs.z() {
+            self.z() - rhs.z()
+        } else {
+            rhs.z() - self.z()
+        };
+        let a = if self.alpha() > rhs.alpha() {
+            self.alpha() - rhs.alpha()
+        } else {
+            rhs.alpha() - self.alpha()
+        };
+        Xyz::with_alpha(x, y, z, a)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        self.x() <= rhs.x()
+            && self.y() <= rhs.y()
+            && self.z() <= rhs.z()
+            && self.alpha() <= rhs.alpha()
+    }
+}
+
+impl<C, A, M, W> Pixel for Xyz<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    type Alpha = M;
+    type Gamma = Linear;
+    type Primaries = CieXyz<W>;
+}
+
+impl<C, A, M, W> Iterator for Xyz<C, A, M, W>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<C, M, W> From<Xyz<C, Translucent<C>, M, W>> for Xyz<C, Opaque<C>, M, W>
+where
+    C: Channel,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    fn from(c: Xyz<C, Translucent<C>, M, W>) -> Self {
+        Xyz::new(c.x(), c.y(), c.z())
+    }
+}
+
+impl<C, M, W> From<Xyz<C, Opaque<C>, M, W>> for Xyz<C, Translucent<C>, M, W>
+where
+    C: Channel,
+    M: alpha::Mode,
+    W: white::Point,
+{
+    fn from(c: Xyz<C, Opaque<C>, M, W>) -> Self {
+        Xyz::with_alpha(c.x(), c.y(), c.z(), C::MAX)
+    }
+}
+
+/// [Xyz](struct.Xyz.html) 8-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Xyz8 = Xyz<Ch8, Opaque<Ch8>, Straight, D65>;
+/// [Xyz](struct.Xyz.html) 16-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Xyz16 = Xyz<Ch16, Opaque<Ch16>, Straight, D65>;
+/// [Xyz](struct.Xyz.html) 32-bit [opaque](alpha/struct.Opaque.html) (no alpha)
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Xyz32 = Xyz<Ch32, Opaque<Ch32>, Straight, D65>;
+
+/// [Xyz](struct.Xyz.html) 8-bit [straight](alpha/struct.Straight.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Xyza8 = Xyz<Ch8, Translucent<Ch8>, Straight, D65>;
+/// [Xyz](struct.Xyz.html) 16-bit [straight](alpha/struct.Straight.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Xyza16 = Xyz<Ch16, Translucent<Ch16>, Straight, D65>;
+/// [Xyz](struct.Xyz.html) 32-bit [straight](alpha/struct.Straight.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Xyza32 = Xyz<Ch32, Translucent<Ch32>, Straight, D65>;
+
+/// [Xyz](struct.Xyz.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Xyza8p = Xyz<Ch8, Translucent<Ch8>, Premultiplied, D65>;
+/// [Xyz](struct.Xyz.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Xyza16p = Xyz<Ch16, Translucent<Ch16>, Premultiplied, D65>;
+/// [Xyz](struct.Xyz.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [D65](white/struct.D65.html) white point [pixel](trait.Pixel.html) format.
+pub type Xyza32p = Xyz<Ch32, Translucent<Ch32>, Premultiplied, D65>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+    use crate::alpha::{Opaque, Straight};
+    use crate::white::D50;
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<Xyz8>(), 3);
+        assert_eq!(std::mem::size_of::<Xyz16>(), 6);
+        assert_eq!(std::mem::size_of::<Xyz32>(), 12);
+        assert_eq!(std::mem::size_of::<Xyza8>(), 4);
+        assert_eq!(std::mem::size_of::<Xyza16>(), 8);
+        assert_eq!(std::mem::size_of::<Xyza32>(), 16);
+    }
+
+    #[test]
+    fn rgb_to_xyz() {
+        assert_eq!(Xyz8::new(111, 54, 5), SRgb8::new(0xFF, 0, 0).convert());
+        assert_eq!(Xyz8::new(0, 0, 0), SRgb8::new(0, 0, 0).convert());
+        assert_eq!(
+            Xyz8::new(0xFF, 0xFF, 0xFF),
+            SRgb8::new(0xFF, 0xFF, 0xFF).convert()
+        );
+        type Xyz8D50 = Xyz<Ch8, Opaque<Ch8>, Straight, D50>;
+        assert_eq!(
+            Xyz8D50::new(0xFF, 0xFF, 0xFF),
+            SRgb8::new(0xFF, 0xFF, 0xFF).convert()
+        );
+        assert_eq!(
+            Xyza16::with_alpha(0x7FFF, 0x7FFF, 0x7FFF, 0x4000),
+            Rgba16::with_alpha(0x7FFF, 0x7FFF, 0x7FFF, 0x4000).convert()
+        );
+    }
+
+    #[test]
+    fn shadows() {
+        let xyz: Xyz16 = SRgb8::new(8, 8, 8).convert();
+        assert_eq!(xyz, Xyz16::new(159, 159, 159));
+        let xyz: Xyz16 = SRgb8::new(12, 12, 12).convert();
+        assert_eq!(SRgb8::new(12, 12, 12), xyz.convert());
+    }
+
+    #[test]
+    fn wide_gamut() {
+        let p = Rec2020Rgb16::new(0, 0xFFFF, 0);
+        let xyz: Xyz32 = p.convert();
+        let back: Rec2020Rgb16 = xyz.convert();
+        assert_eq!(back, p);
+    }
+
+    #[test]
+    fn f64_precision() {
+        use crate::gamut::{DisplayP3, Primaries};
+        use crate::white::D65;
+        type P3Rgb64 =
+            Rgb<Ch64, Opaque<Ch64>, Straight, gamma::Srgb, DisplayP3>;
+        type Xyz64 = Xyz<Ch64, Opaque<Ch64>, Straight, D65>;
+        let p = P3Rgb64::new(0.25, 0.5, 0.75);
+        let xyz: Xyz64 = p.convert();
+        let lin =
+            [0.25_f64, 0.5, 0.75].map(|v| ((v + 0.055) / 1.055).powf(2.4));
+        let m = DisplayP3::TO_XYZ;
+        let y = m[1][0] * lin[0] + m[1][1] * lin[1] + m[1][2] * lin[2];
+        assert!((f64::from(xyz.y()) - y).abs() < 1e-12);
+        let back: P3Rgb64 = xyz.convert();
+        assert!((f64::from(back.red()) - 0.25).abs() < 1e-12);
+        assert!((f64::from(back.green()) - 0.5).abs() < 1e-12);
+        assert!((f64::from(back.blue()) - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = SRgb8::new(r, g, b);
+                    let xyz: Xyz32 = rgb.convert();
+                    let back: SRgb16 = xyz.convert();
+                    assert_eq!(rgb, back.convert());
+                }
+            }
+        }
+    }
+}
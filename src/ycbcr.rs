@@ -0,0 +1,387 @@
+// ycbcr.rs     YCbCr color model.
+//
+// Copyright (c) 2020  Douglas P Lau
+//
+use crate::alpha::{
+    self, AChannel, Opaque, Premultiplied, Straight, Translucent,
+};
+use crate::format::Model;
+use crate::hue::rgb_to_f32;
+use crate::video::{self, Bt601, Full};
+use crate::{Ch16, Ch32, Ch8, Channel, ColorModel, Pixel};
+use std::marker::PhantomData;
+
+/// YCbCr [color model], used for digital video and JPEG.
+///
+/// The components are *luma* (*Y′*), *blue-difference chroma* (*Cb*) and
+/// *red-difference chroma* (*Cr*), with optional *[alpha]*.  Components are
+/// derived from gamma encoded *red*, *green* and *blue*, using [matrix]
+/// coefficients and quantized using a [range].  The matrix also determines
+/// the RGB primaries and gamma mode.
+///
+/// [alpha]: alpha/trait.AChannel.html
+/// [color model]: trait.ColorModel.html
+/// [matrix]: video/trait.Matrix.html
+/// [range]: video/trait.Range.html
+///
+/// ### Convert from `Rgb`
+/// ```
+/// # use pix::*;
+/// let ycc: YCbCr8 = SRgb8::new(0xFF, 0x00, 0x00).convert();
+/// assert_eq!(ycc, YCbCr8::new(76, 85, 255));
+/// ```
+///
+/// ### Convert from limited range BT.709
+/// ```
+/// # use pix::*;
+/// use pix::alpha::{Opaque, Straight};
+/// use pix::video::{Bt709, Limited};
+/// type YCbCr709 = YCbCr<Ch8, Opaque<Ch8>, Straight, Bt709, Limited>;
+/// let rgb: SRgb8 = YCbCr709::new(235, 128, 128).convert();
+/// assert_eq!(rgb, SRgb8::new(0xFF, 0xFF, 0xFF));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct YCbCr<C, A, M, K, R>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    K: video::Matrix,
+    R: video::Range,
+{
+    components: [C; 3],
+    alpha: A,
+    mode: PhantomData<M>,
+    matrix: PhantomData<K>,
+    range: PhantomData<R>,
+}
+
+impl<C, A, M, K, R> YCbCr<C, A, M, K, R>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    K: video::Matrix,
+    R: video::Range,
+{
+    /// Create an [Opaque](alpha/struct.Opaque.html) color by specifying
+    /// *luma*, *blue-difference chroma* and *red-difference chroma*.
+    pub fn new<H>(y: H, cb: H, cr: H) -> Self
+    where
+        C: From<H>,
+        A: From<Opaque<C>>,
+    {
+        Self::with_alpha(y, cb, cr, Opaque::default())
+    }
+    /// Create a [Translucent](alpha/struct.Translucent.html) color by
+    /// specifying *luma*, *blue-difference chroma*, *red-difference chroma*
+    /// and *alpha*.
+    pub fn with_alpha<H, B>(y: H, cb: H, cr: H, alpha: B) -> Self
+    where
+        C: From<H>,
+        A: From<B>,
+    {
+        let components = [C::from(y), C::from(cb), C::from(cr)];
+        let alpha = A::from(alpha);
+        YCbCr {
+            components,
+            alpha,
+            mode: PhantomData,
+            matrix: PhantomData,
+            range: PhantomData,
+        }
+    }
+    /// Get the *luma* (*Y′*) component.
+    pub fn y(self) -> C {
+        self.components[0]
+    }
+    /// Get the *blue-difference chroma* (*Cb*) component.
+    pub fn cb(self) -> C {
+        self.components[1]
+    }
+    /// Get the *red-difference chroma* (*Cr*) component.
+    pub fn cr(self) -> C {
+        self.components[2]
+    }
+}
+
+impl<C, A, M, K, R> ColorModel for YCbCr<C, A, M, K, R>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    K: video::Matrix,
+    R: video::Range,
+{
+    type Chan = C;
+    const MODEL: Model = Model::YCbCr;
+    const HAS_ALPHA: bool = A::TRANSLUCENT;
+
+    /// Get all non-alpha components
+    fn components(&self) -> &[Self::Chan] {
+        &self.components
+    }
+
+    /// Get the *alpha* component
+    fn alpha(self) -> Self::Chan {
+        self.alpha.value()
+    }
+
+    /// Convert to *red*, *green*, *blue* and *alpha* components
+    fn to_rgba(self) -> [Self::Chan; 4] {
+        let y = R::decode_luma(self.y());
+        let cb = R::decode_chroma(self.cb());
+        let cr = R::decode_chroma(self.cr());
+        let kg = 1.0 - K::KR - K::KB;
+        let red = y + 2.0 * (1.0 - K::KR) * cr;
+        let blue = y + 2.0 * (1.0 - K::KB) * cb;
+        let green = (y - K::KR * red - K::KB * blue) / kg;
+        [red.into(), green.into(), blue.into(), self.alpha()]
+    }
+
+    /// Convert from *red*, *green*, *blue* and *alpha* components
+    fn with_rgba(rgba: [Self::Chan; 4]) -> Self {
+        let [red, green, blue] = rgb_to_f32(rgba);
+        let kg = 1.0 - K::KR - K::KB;
+        let y = K::KR * red + kg * green + K::KB * blue;
+        let cb = (blue - y) / (2.0 * (1.0 - K::KB));
+        let cr = (red - y) / (2.0 * (1.0 - K::KR));
+        let y = R::encode_luma::<C>(y);
+        let cb = R::encode_chroma::<C>(cb);
+        let cr = R::encode_chroma::<C>(cr);
+        YCbCr::with_alpha(y, cb, cr, rgba[3])
+    }
+
+    /// Get channel-wise difference
+    fn difference(self, rhs: Self) -> Self {
+        let y = if self.y() > rhs.y() {
+            self.y() - rhs.y()
+        } else {
+            rhs.y() - self.y()
+        };
+        let cb = if self.cb() > rhs.cb() {
+            self.cb() - rhs.cb()
+        } else {
+            rhs.cb() - self.cb()
+        };
+        let cr = if self.cr() > rhs.cr() {
+            self.cr() - rhs.cr()
+        } else {
+            rhs.cr() - self.cr()
+        };
+        let a = if self.alpha() > rhs.alpha() {
+            self.alpha() - rhs.alpha()
+        } else {
+            rhs.alpha() - self.alpha()
+        };
+        YCbCr::with_alpha(y, cb, cr, a)
+    }
+
+    /// Check if all `Channel`s are within threshold
+    fn within_threshold(self, rhs: Self) -> bool {
+        self.y() <= rhs.y()
+            && self.cb() <= rhs.cb()
+            && self.cr() <= rhs.cr()
+            && self.alpha() <= rhs.alpha()
+    }
+}
+
+impl<C, A, M, K, R> Pixel for YCbCr<C, A, M, K, R>
+where
+    C: Channel,
+    A: AChannel<Chan = C> + From<C>,
+    M: alpha::Mode,
+    K: video::Matrix,
+    R: video::Range,
+{
+    type Alpha = M;
+    type Gamma = K::Gamma;
+    type Primaries = K::Primaries;
+}
+
+impl<C, A, M, K, R> Iterator for YCbCr<C, A, M, K, R>
+where
+    C: Channel,
+    A: AChannel<Chan = C>,
+    M: alpha::Mode,
+    K: video::Matrix,
+    R: video::Range,
+{
+    type Item = Self;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(*self)
+    }
+}
+
+impl<C, M, K, R> From<YCbCr<C, Translucent<C>, M, K, R>>
+    for YCbCr<C, Opaque<C>, M, K, R>
+where
+    C: Channel,
+    M: alpha::Mode,
+    K: video::Matrix,
+    R: video::Range,
+{
+    fn from(c: YCbCr<C, Translucent<C>, M, K, R>) -> Self {
+        YCbCr::new(c.y(), c.cb(), c.cr())
+    }
+}
+
+impl<C, M, K, R> From<YCbCr<C, Opaque<C>, M, K, R>>
+    for YCbCr<C, Translucent<C>, M, K, R>
+where
+    C: Channel,
+    M: alpha::Mode,
+    K: video::Matrix,
+    R: video::Range,
+{
+    fn from(c: YCbCr<C, Opaque<C>, M, K, R>) -> Self {
+        YCbCr::with_alpha(c.y(), c.cb(), c.cr(), C::MAX)
+    }
+}
+
+/// [YCbCr](struct.YCbCr.html) 8-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha)
+/// [BT.601](video/struct.Bt601.html) [full range](video/struct.Full.html)
+/// [pixel](trait.Pixel.html) format.
+pub type YCbCr8 = YCbCr<Ch8, Opaque<Ch8>, Straight, Bt601, Full>;
+/// [YCbCr](struct.YCbCr.html) 16-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha)
+/// [BT.601](video/struct.Bt601.html) [full range](video/struct.Full.html)
+/// [pixel](trait.Pixel.html) format.
+pub type YCbCr16 = YCbCr<Ch16, Opaque<Ch16>, Straight, Bt601, Full>;
+/// [YCbCr](struct.YCbCr.html) 32-bit [opaque](alpha/struct.Opaque.html) (no
+/// alpha)
+/// [BT.601](video/struct.Bt601.html) [full range](video/struct.Full.html)
+/// [pixel](trait.Pixel.html) format.
+pub type YCbCr32 = YCbCr<Ch32, Opaque<Ch32>, Straight, Bt601, Full>;
+
+/// [YCbCr](struct.YCbCr.html) 8-bit [straight](alpha/struct.Straight.html)
+/// alpha
+/// [BT.601](video/struct.Bt601.html) [full range](video/struct.Full.html)
+/// [pixel](trait.Pixel.html) format.
+pub type YCbCra8 = YCbCr<Ch8, Translucent<Ch8>, Straight, Bt601, Full>;
+/// [YCbCr](struct.YCbCr.html) 16-bit [straight](alpha/struct.Straight.html)
+/// alpha
+/// [BT.601](video/struct.Bt601.html) [full range](video/struct.Full.html)
+/// [pixel](trait.Pixel.html) format.
+pub type YCbCra16 = YCbCr<Ch16, Translucent<Ch16>, Straight, Bt601, Full>;
+/// [YCbCr](struct.YCbCr.html) 32-bit [straight](alpha/struct.Straight.html)
+/// alpha
+/// [BT.601](video/struct.Bt601.html) [full range](video/struct.Full.html)
+/// [pixel](trait.Pixel.html) format.
+pub type YCbCra32 = YCbCr<Ch32, Translucent<Ch32>, Straight, Bt601, Full>;
+
+/// [YCbCr](struct.YCbCr.html) 8-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [BT.601](video/struct.Bt601.html) [full range](video/struct.Full.html)
+/// [pixel](trait.Pixel.html) format.
+pub type YCbCra8p = YCbCr<Ch8, Translucent<Ch8>, Premultiplied, Bt601, Full>;
+/// [YCbCr](struct.YCbCr.html) 16-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [BT.601](video/struct.Bt601.html) [full range](video/struct.Full.html)
+/// [pixel](trait.Pixel.html) format.
+pub type YCbCra16p = YCbCr<Ch16, Translucent<Ch16>, Premultiplied, Bt601, Full>;
+/// [YCbCr](struct.YCbCr.html) 32-bit
+/// [premultiplied](alpha/struct.Premultiplied.html) alpha
+/// [BT.601](video/struct.Bt601.html) [full range](video/struct.Full.html)
+/// [pixel](trait.Pixel.html) format.
+pub type YCbCra32p = YCbCr<Ch32, Translucent<Ch32>, Premultiplied, Bt601, Full>;
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+    use crate::alpha::{Opaque, Straight};
+    use crate::video::{Bt2020, Bt709, Full, Limited};
+
+    type YCbCr709 = YCbCr<Ch8, Opaque<Ch8>, Straight, Bt709, Limited>;
+    type YCbCr709_16 = YCbCr<Ch16, Opaque<Ch16>, Straight, Bt709, Limited>;
+    type YCbCr2020 = YCbCr<Ch32, Opaque<Ch32>, Straight, Bt2020, Full>;
+    type YCbCr2020_16 = YCbCr<Ch16, Opaque<Ch16>, Straight, Bt2020, Limited>;
+
+    #[test]
+    fn check_sizes() {
+        assert_eq!(std::mem::size_of::<YCbCr8>(), 3);
+        assert_eq!(std::mem::size_of::<YCbCr16>(), 6);
+        assert_eq!(std::mem::size_of::<YCbCr32>(), 12);
+        assert_eq!(std::mem::size_of::<YCbCra8>(), 4);
+        assert_eq!(std::mem::size_of::<YCbCra16>(), 8);
+        assert_eq!(std::mem::size_of::<YCbCra32>(), 16);
+    }
+
+    #[test]
+    fn rgb_to_ycbcr() {
+        assert_eq!(YCbCr8::new(0, 128, 128), SRgb8::new(0, 0, 0).convert());
+        assert_eq!(
+            YCbCr8::new(255, 128, 128),
+            SRgb8::new(0xFF, 0xFF, 0xFF).convert()
+        );
+        assert_eq!(YCbCr8::new(150, 44, 21), SRgb8::new(0, 0xFF, 0).convert());
+        assert_eq!(
+            YCbCr16::new(0xFFFF, 0x8000, 0x8000),
+            SRgb8::new(0xFF, 0xFF, 0xFF).convert()
+        );
+        assert_eq!(
+            YCbCr709::new(16, 128, 128),
+            SRgb8::new(0, 0, 0).convert()
+        );
+        assert_eq!(
+            YCbCr709::new(63, 102, 240),
+            SRgb8::new(0xFF, 0, 0).convert()
+        );
+        assert_eq!(
+            YCbCr709_16::new(60160, 32768, 32768),
+            SRgb8::new(0xFF, 0xFF, 0xFF).convert()
+        );
+    }
+
+    #[test]
+    fn ycbcr_to_rgb() {
+        assert_eq!(
+            Rec709Rgb8::new(0, 0, 0),
+            YCbCr709::new(16, 128, 128).convert()
+        );
+        assert_eq!(
+            Rec709Rgb8::new(0, 0, 0),
+            YCbCr709::new(0, 128, 128).convert()
+        );
+        assert_eq!(
+            Rec709Rgb8::new(0xFF, 0x01, 0x00),
+            YCbCr709::new(63, 102, 240).convert()
+        );
+        assert_eq!(SRgb8::new(0xFE, 0, 0), YCbCr8::new(76, 85, 255).convert());
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = SRgb8::new(r, g, b);
+                    let ycc: YCbCr16 = rgb.convert();
+                    assert_eq!(rgb, ycc.convert());
+                    let rgb = Rec709Rgb8::new(r, g, b);
+                    let ycc: YCbCr709_16 = rgb.convert();
+                    assert_eq!(rgb, ycc.convert());
+                    let rgb = Rec2020Rgb8::new(r, g, b);
+                    let ycc: YCbCr2020 = rgb.convert();
+                    let back: Rec2020Rgb16 = ycc.convert();
+                    assert_eq!(rgb, back.convert());
+                    let ycc: YCbCr2020_16 = rgb.convert();
+                    assert_eq!(rgb, ycc.convert());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wide_gamut() {
+        let rgb = Rec2020Rgb16::new(0, 0xFFFF, 0);
+        let ycc: YCbCr2020_16 = rgb.convert();
+        let back: Rec2020Rgb16 = ycc.convert();
+        assert_eq!(back.red(), Ch16::new(0));
+        assert!(u16::from(back.green()) > 0xFFF0);
+        assert_eq!(back.blue(), Ch16::new(0));
+    }
+}